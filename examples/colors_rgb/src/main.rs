@@ -8,6 +8,7 @@ use ratzilla::{
         style::Color,
         widgets::Widget,
     },
+    utils::frame_step,
     WebRenderer,
 };
 use examples_shared::backend::{BackendType, MultiBackendBuilder};
@@ -33,9 +34,9 @@ struct ColorsWidget {
     /// the size of the widget changes.
     colors: Vec<Vec<Color>>,
 
-    /// the number of elapsed frames that have passed - used to animate the colors by shifting the
-    /// x index by the frame number
-    frame_count: usize,
+    /// the number of elapsed frames at 60 FPS - used to animate the colors by shifting the x index
+    /// by the frame number at the same speed regardless of the display refresh rate
+    frame_count: f64,
 }
 
 /// Widget impl for `ColorsWidget`
@@ -49,7 +50,7 @@ impl Widget for &mut ColorsWidget {
         let colors = &self.colors;
         for (xi, x) in (area.left()..area.right()).enumerate() {
             // animate the colors by shifting the x index by the frame number
-            let xi = (xi + self.frame_count) % (area.width as usize);
+            let xi = (xi + self.frame_count as usize) % (area.width as usize);
             for (yi, y) in (area.top()..area.bottom()).enumerate() {
                 // render a half block character for each row of pixels with the foreground color
                 // set to the color of the pixel and the background color set to the color of the
@@ -59,7 +60,7 @@ impl Widget for &mut ColorsWidget {
                 buf[Position::new(x, y)].set_char('▀').set_fg(fg).set_bg(bg);
             }
         }
-        self.frame_count += 1;
+        self.frame_count += frame_step();
     }
 }
 
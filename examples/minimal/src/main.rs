@@ -15,6 +15,7 @@ use ratzilla::backend::webgl2::WebGl2BackendOptions;
 
 fn main() -> io::Result<()> {
     let counter = Rc::new(RefCell::new(0));
+    let repeats = Rc::new(RefCell::new(0));
     let mouse_position = Rc::new(RefCell::new((0, 0)));
     let mouse_button = Rc::new(RefCell::new(None::<MouseButton>));
     let mouse_event_kind = Rc::new(RefCell::new(None::<MouseEventKind>));
@@ -28,15 +29,23 @@ fn main() -> io::Result<()> {
 
     terminal.on_key_event({
         let counter_cloned = counter.clone();
+        let repeats_cloned = repeats.clone();
         move |key_event| {
             if key_event.code == KeyCode::Char(' ') {
-                let mut counter = counter_cloned.borrow_mut();
-                *counter += 1;
+                // Holding Space repeats the press
+                if key_event.is_repeat() {
+                    let mut repeats = repeats_cloned.borrow_mut();
+                    *repeats += 1;
+                } else {
+                    let mut counter = counter_cloned.borrow_mut();
+                    *counter += 1;
+                }
             }
         }
     });
 
-    terminal.on_mouse_event({
+    // The mouse is not tracked with the WebGL2 backend
+    let mouse_events = terminal.on_mouse_event({
         let mouse_position_cloned = mouse_position.clone();
         let mouse_button_cloned = mouse_button.clone();
         let mouse_event_kind_cloned = mouse_event_kind.clone();
@@ -49,9 +58,13 @@ fn main() -> io::Result<()> {
             *mouse_event_kind = Some(mouse_event.event);
         }
     });
+    if let Err(error) = mouse_events {
+        ratzilla::web_sys::console::warn_1(&error.to_string().into());
+    }
 
     terminal.draw_web(move |f| {
         let counter = counter.borrow();
+        let repeats = repeats.borrow();
         let mouse_position = mouse_position.borrow();
         let mouse_button = mouse_button.borrow();
         let mouse_event_kind = mouse_event_kind.borrow();
@@ -59,6 +72,7 @@ fn main() -> io::Result<()> {
         f.render_widget(
             Paragraph::new(format!(
                 "Space pressed: {counter}\n\
+                Space repeated: {repeats}\n\
                 MouseX: {:?}\n\
                 MouseY: {:?}\n\
                 MouseButton: {mouse_button:?}\n\
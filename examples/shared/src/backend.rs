@@ -1,177 +1,14 @@
 use crate::{fps, utils::inject_backend_footer};
 use ratzilla::{
-    backend::{canvas::CanvasBackendOptions, dom::DomBackendOptions, webgl2::WebGl2BackendOptions},
+    backend::{
+        canvas::CanvasBackendOptions, dom::DomBackendOptions, multi,
+        webgl2::WebGl2BackendOptions,
+    },
     ratatui::{backend::Backend, prelude::backend::ClearType, Terminal, TerminalOptions},
-    CanvasBackend, DomBackend, WebGl2Backend,
 };
-use std::{convert::TryFrom, fmt, io};
-use web_sys::{window, Url};
+use std::io;
 
-/// Available backend types
-#[derive(Debug, Clone, Copy, Default, PartialEq)]
-pub enum BackendType {
-    #[default]
-    Dom,
-    Canvas,
-    WebGl2,
-}
-
-impl BackendType {
-    /// Get the string representation
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            BackendType::Dom => "dom",
-            BackendType::Canvas => "canvas",
-            BackendType::WebGl2 => "webgl2",
-        }
-    }
-}
-
-impl TryFrom<String> for BackendType {
-    type Error = String;
-
-    fn try_from(s: String) -> Result<Self, Self::Error> {
-        match s.to_lowercase().as_str() {
-            "dom" => Ok(BackendType::Dom),
-            "canvas" => Ok(BackendType::Canvas),
-            "webgl2" => Ok(BackendType::WebGl2),
-            _ => Err(format!(
-                "Invalid backend type: '{s}'. Valid options are: dom, canvas, webgl2"
-            )),
-        }
-    }
-}
-
-impl fmt::Display for BackendType {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.as_str())
-    }
-}
-
-/// Enum wrapper for different Ratzilla backends that implements the Ratatui Backend trait.
-///
-/// This enum allows switching between different rendering backends at runtime while
-/// providing a unified interface. All backend operations are delegated to the wrapped
-/// backend implementation.
-///
-/// # Backends
-///
-/// - `Dom`: HTML DOM-based rendering with accessibility features
-/// - `Canvas`: Canvas 2D API rendering with full Unicode support  
-/// - `WebGl2`: GPU-accelerated rendering using WebGL2 and beamterm-renderer
-pub enum RatzillaBackend {
-    Dom(DomBackend),
-    Canvas(CanvasBackend),
-    WebGl2(WebGl2Backend),
-}
-
-impl RatzillaBackend {
-    /// Get the backend type for this backend instance.
-    pub fn backend_type(&self) -> BackendType {
-        match self {
-            RatzillaBackend::Dom(_) => BackendType::Dom,
-            RatzillaBackend::Canvas(_) => BackendType::Canvas,
-            RatzillaBackend::WebGl2(_) => BackendType::WebGl2,
-        }
-    }
-}
-
-impl Backend for RatzillaBackend {
-    type Error = io::Error;
-
-    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
-    where
-        I: Iterator<Item = (u16, u16, &'a ratzilla::ratatui::buffer::Cell)>,
-    {
-        match self {
-            RatzillaBackend::Dom(backend) => backend.draw(content),
-            RatzillaBackend::Canvas(backend) => backend.draw(content),
-            RatzillaBackend::WebGl2(backend) => backend.draw(content),
-        }
-    }
-
-    fn flush(&mut self) -> io::Result<()> {
-        match self {
-            RatzillaBackend::Dom(backend) => backend.flush(),
-            RatzillaBackend::Canvas(backend) => backend.flush(),
-            RatzillaBackend::WebGl2(backend) => backend.flush(),
-        }
-    }
-
-    fn size(&self) -> io::Result<ratzilla::ratatui::layout::Size> {
-        match self {
-            RatzillaBackend::Dom(backend) => backend.size(),
-            RatzillaBackend::Canvas(backend) => backend.size(),
-            RatzillaBackend::WebGl2(backend) => backend.size(),
-        }
-    }
-
-    fn hide_cursor(&mut self) -> io::Result<()> {
-        match self {
-            RatzillaBackend::Dom(backend) => backend.hide_cursor(),
-            RatzillaBackend::Canvas(backend) => backend.hide_cursor(),
-            RatzillaBackend::WebGl2(backend) => backend.hide_cursor(),
-        }
-    }
-
-    fn show_cursor(&mut self) -> io::Result<()> {
-        match self {
-            RatzillaBackend::Dom(backend) => backend.show_cursor(),
-            RatzillaBackend::Canvas(backend) => backend.show_cursor(),
-            RatzillaBackend::WebGl2(backend) => backend.show_cursor(),
-        }
-    }
-
-    fn get_cursor_position(&mut self) -> io::Result<ratzilla::ratatui::layout::Position> {
-        match self {
-            RatzillaBackend::Dom(backend) => backend.get_cursor_position(),
-            RatzillaBackend::Canvas(backend) => backend.get_cursor_position(),
-            RatzillaBackend::WebGl2(backend) => backend.get_cursor_position(),
-        }
-    }
-
-    fn set_cursor_position<P: Into<ratzilla::ratatui::layout::Position>>(
-        &mut self,
-        position: P,
-    ) -> io::Result<()> {
-        match self {
-            RatzillaBackend::Dom(backend) => backend.set_cursor_position(position),
-            RatzillaBackend::Canvas(backend) => backend.set_cursor_position(position),
-            RatzillaBackend::WebGl2(backend) => backend.set_cursor_position(position),
-        }
-    }
-
-    fn clear(&mut self) -> io::Result<()> {
-        match self {
-            RatzillaBackend::Dom(backend) => backend.clear(),
-            RatzillaBackend::Canvas(backend) => backend.clear(),
-            RatzillaBackend::WebGl2(backend) => backend.clear(),
-        }
-    }
-
-    fn append_lines(&mut self, n: u16) -> io::Result<()> {
-        match self {
-            RatzillaBackend::Dom(backend) => backend.append_lines(n),
-            RatzillaBackend::Canvas(backend) => backend.append_lines(n),
-            RatzillaBackend::WebGl2(backend) => backend.append_lines(n),
-        }
-    }
-
-    fn window_size(&mut self) -> io::Result<ratzilla::ratatui::backend::WindowSize> {
-        match self {
-            RatzillaBackend::Dom(backend) => backend.window_size(),
-            RatzillaBackend::Canvas(backend) => backend.window_size(),
-            RatzillaBackend::WebGl2(backend) => backend.window_size(),
-        }
-    }
-
-    fn clear_region(&mut self, clear_type: ClearType) -> Result<(), Self::Error> {
-        match clear_type {
-            ClearType::All => self.clear(),
-            _ => Err(io::Error::other("unimplemented")),
-        }
-    }
-}
+pub use ratzilla::backend::multi::{BackendType, RatzillaBackend};
 
 /// Backend wrapper that automatically tracks FPS by recording frames on each flush.
 ///
@@ -180,6 +17,8 @@ impl Backend for RatzillaBackend {
 /// The FPS data can be accessed through the `fps` module functions.
 pub struct FpsTrackingBackend {
     inner: RatzillaBackend,
+    /// The backend type that the footer was injected for.
+    backend_type: BackendType,
 }
 
 impl FpsTrackingBackend {
@@ -187,7 +26,10 @@ impl FpsTrackingBackend {
     ///
     /// Frame timing will be recorded automatically on each successful flush operation.
     pub fn new(backend: RatzillaBackend) -> Self {
-        Self { inner: backend }
+        Self {
+            backend_type: backend.backend_type(),
+            inner: backend,
+        }
     }
 
     /// Get the backend type for the wrapped backend.
@@ -218,6 +60,11 @@ impl Backend for FpsTrackingBackend {
         if result.is_ok() {
             fps::record_frame();
         }
+        // Update the footer if the backend fell back to another one
+        if self.inner.backend_type() != self.backend_type {
+            self.backend_type = self.inner.backend_type();
+            let _ = inject_backend_footer(self.backend_type);
+        }
         result
     }
 
@@ -295,12 +142,8 @@ impl Backend for FpsTrackingBackend {
 /// ```
 #[derive(Debug, Default)]
 pub struct MultiBackendBuilder {
-    default_backend: BackendType,
-
+    inner: multi::MultiBackendBuilder,
     terminal_options: TerminalOptions,
-    canvas_options: CanvasBackendOptions,
-    dom_options: DomBackendOptions,
-    webgl2_options: WebGl2BackendOptions,
 }
 
 impl MultiBackendBuilder {
@@ -309,11 +152,19 @@ impl MultiBackendBuilder {
     /// The fallback backend will be used if no backend is specified in the URL query parameters.
     pub fn with_fallback(default_backend: BackendType) -> Self {
         Self {
-            default_backend,
+            inner: multi::MultiBackendBuilder::with_fallback(default_backend),
             ..Self::default()
         }
     }
 
+    /// Fall back to another backend if the selected backend fails mid-session.
+    ///
+    /// See [`multi::MultiBackendBuilder::safe_fallback`].
+    pub fn safe_fallback(mut self, enabled: bool) -> Self {
+        self.inner = self.inner.safe_fallback(enabled);
+        self
+    }
+
     /// Set terminal configuration options.
     ///
     /// These options control terminal behavior such as viewport behavior and drawing settings.
@@ -327,7 +178,7 @@ impl MultiBackendBuilder {
     /// These options control Canvas 2D rendering behavior such as font settings,
     /// cursor appearance, and Unicode support.
     pub fn canvas_options(mut self, options: CanvasBackendOptions) -> Self {
-        self.canvas_options = options;
+        self.inner = self.inner.canvas_options(options);
         self
     }
 
@@ -336,7 +187,7 @@ impl MultiBackendBuilder {
     /// These options control DOM rendering behavior such as accessibility features,
     /// element styling, and focus management.
     pub fn dom_options(mut self, options: DomBackendOptions) -> Self {
-        self.dom_options = options;
+        self.inner = self.inner.dom_options(options);
         self
     }
 
@@ -345,7 +196,7 @@ impl MultiBackendBuilder {
     /// These options control WebGL2 rendering behavior such as shader configuration,
     /// GPU memory management, and performance settings.
     pub fn webgl2_options(mut self, options: WebGl2BackendOptions) -> Self {
-        self.webgl2_options = options;
+        self.inner = self.inner.webgl2_options(options);
         self
     }
 
@@ -353,7 +204,8 @@ impl MultiBackendBuilder {
     ///
     /// This method:
     /// 1. Determines the backend type from URL query parameters or fallback
-    /// 2. Creates the appropriate backend with the configured options
+    /// 2. Creates the appropriate backend with the configured options, using
+    ///    [`multi::MultiBackendBuilder`]
     /// 3. Wraps the backend with FPS tracking
     /// 4. Creates and returns the terminal with the selected backend
     /// 5. Injects a backend footer into the DOM (best effort)
@@ -379,13 +231,8 @@ impl MultiBackendBuilder {
     /// println!("Using {backend_type} backend");
     /// ```
     pub fn build_terminal(self) -> io::Result<Terminal<FpsTrackingBackend>> {
-        let backend_type = parse_backend_from_url(self.default_backend);
-        let backend = create_backend_with_options(
-            backend_type,
-            Some(self.dom_options),
-            Some(self.canvas_options),
-            Some(self.webgl2_options),
-        )?;
+        let backend = self.inner.build_backend()?;
+        let backend_type = backend.backend_type();
 
         // Initialize FPS recorder
         fps::init_fps_recorder();
@@ -406,57 +253,3 @@ impl From<BackendType> for MultiBackendBuilder {
         MultiBackendBuilder::with_fallback(backend_type)
     }
 }
-
-/// Parse the backend type from URL query parameters, with fallback to default.
-///
-/// Checks for a `?backend=<type>` query parameter in the current page URL.
-/// Valid backend types are "dom", "canvas", and "webgl2" (case-insensitive).
-/// If no valid backend is found in the URL, returns the provided default.
-fn parse_backend_from_url(default: BackendType) -> BackendType {
-    window()
-        .and_then(|w| w.location().href().ok())
-        .and_then(|url| Url::new(url.as_str()).ok())
-        .and_then(|url| url.search_params().get("backend"))
-        .and_then(|backend| BackendType::try_from(backend).ok())
-        .unwrap_or(default)
-}
-
-/// Create a backend instance with the specified type and options.
-///
-/// Creates the appropriate backend variant (DOM, Canvas, or WebGL2) using the provided
-/// configuration options. Options default to `Default::default()` if `None` is provided.
-///
-/// # Arguments
-///
-/// * `backend_type` - The type of backend to create
-/// * `dom_options` - Configuration options for DOM backend (if applicable)
-/// * `canvas_options` - Configuration options for Canvas backend (if applicable)  
-/// * `webgl2_options` - Configuration options for WebGL2 backend (if applicable)
-///
-/// # Returns
-///
-/// The created backend wrapped in a `RatzillaBackend` enum.
-///
-/// # Errors
-///
-/// Returns an error if the backend creation fails (e.g., WebGL2 not supported).
-fn create_backend_with_options(
-    backend_type: BackendType,
-    dom_options: Option<DomBackendOptions>,
-    canvas_options: Option<CanvasBackendOptions>,
-    webgl2_options: Option<WebGl2BackendOptions>,
-) -> io::Result<RatzillaBackend> {
-    use RatzillaBackend::*;
-
-    match backend_type {
-        BackendType::Dom => Ok(Dom(DomBackend::new_with_options(
-            dom_options.unwrap_or_default(),
-        )?)),
-        BackendType::Canvas => Ok(Canvas(CanvasBackend::new_with_options(
-            canvas_options.unwrap_or_default(),
-        )?)),
-        BackendType::WebGl2 => Ok(WebGl2(WebGl2Backend::new_with_options(
-            webgl2_options.unwrap_or_default(),
-        )?)),
-    }
-}
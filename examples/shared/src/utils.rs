@@ -1,29 +1,10 @@
+use ratzilla::utils::render_overlay_html;
 use wasm_bindgen::JsValue;
 use crate::backend::BackendType;
 
 /// Inject HTML footer with backend switching links
 pub(crate) fn inject_backend_footer(current_backend: BackendType) -> Result<(), JsValue> {
     let window = web_sys::window().ok_or("No window")?;
-    let document = window.document().ok_or("No document")?;
-
-    // Remove existing footer if present
-    if let Some(existing) = document.get_element_by_id("ratzilla-backend-footer") {
-        existing.remove();
-    }
-
-    // Create footer element
-    let footer = document.create_element("div")?;
-    footer.set_id("ratzilla-backend-footer");
-
-    // Set footer styles
-    footer.set_attribute(
-        "style",
-        "position: fixed; bottom: 0; left: 0; right: 0; \
-         background: rgba(0,0,0,0.8); color: white; \
-         padding: 8px 16px; font-family: monospace; font-size: 12px; \
-         display: flex; justify-content: center; gap: 16px; \
-         border-top: 1px solid #333; z-index: 1000;",
-    )?;
 
     // Get current URL without backend param - use relative URL to avoid protocol issues
     let location = window.location();
@@ -64,12 +45,15 @@ pub(crate) fn inject_backend_footer(current_backend: BackendType) -> Result<(),
         links.join(" | ")
     );
 
-    footer.set_inner_html(&footer_html);
-
-    // Append to body
-    let body = document.body().ok_or("No body")?;
-    body.append_child(&footer)?;
-
-    Ok(())
+    render_overlay_html(
+        "ratzilla-backend-footer",
+        &footer_html,
+        "position: fixed; bottom: 0; left: 0; right: 0; \
+         background: rgba(0,0,0,0.8); color: white; \
+         padding: 8px 16px; font-family: monospace; font-size: 12px; \
+         display: flex; justify-content: center; gap: 16px; \
+         border-top: 1px solid #333; z-index: 1000;",
+    )
+    .map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
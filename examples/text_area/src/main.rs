@@ -2,11 +2,12 @@ use std::{cell::RefCell, io, rc::Rc};
 
 use examples_shared::backend::{BackendType, MultiBackendBuilder};
 use ratzilla::{
-    event::{KeyCode, KeyEvent},
+    event::{KeyCode, KeyEvent, TextInput},
     ratatui::{
         prelude::*,
         widgets::{Block, Borders, Paragraph},
     },
+    utils::is_submit_combo,
     WebRenderer,
 };
 
@@ -25,6 +26,26 @@ fn main() -> io::Result<()> {
         }
     });
 
+    terminal
+        .on_paste_event({
+            let event_state = app.clone();
+            move |paste_event| {
+                let mut state = event_state.borrow_mut();
+                state.textarea.insert_str(paste_event.text);
+            }
+        })
+        .expect("failed to register the paste handler");
+
+    terminal
+        .on_text_input({
+            let event_state = app.clone();
+            move |input| {
+                let mut state = event_state.borrow_mut();
+                state.handle_text_input(input);
+            }
+        })
+        .expect("failed to register the text input handler");
+
     terminal.draw_web({
         let render_state = app.clone();
         move |frame| {
@@ -72,9 +93,35 @@ impl<'a> App<'a> {
         frame.render_widget(&status, chunks[1]);
     }
 
+    fn handle_text_input(&mut self, input: TextInput) {
+        self.status_text = std::format!("Last text input: {input:?}");
+
+        match input {
+            TextInput::Insert(text) => {
+                self.textarea.insert_str(text);
+            }
+            TextInput::Enter => self.textarea.insert_newline(),
+            TextInput::Backspace => {
+                self.textarea.delete_char();
+            }
+            TextInput::Delete => {
+                self.textarea.delete_next_char();
+            }
+        }
+    }
+
     fn handle_events(&mut self, key_event: KeyEvent) {
         self.status_text = std::format!("Last key pressed: {key_event:?}");
 
+        // Enter inserts a newline, while Ctrl+Enter (Cmd+Enter on macOS) submits
+        if is_submit_combo(&key_event) {
+            let lines = self.textarea.lines().len();
+            self.status_text = std::format!("Submitted {lines} line(s)");
+            self.textarea.select_all();
+            self.textarea.cut();
+            return;
+        }
+
         if let Some(key) = try_convert_code(key_event.code) {
             self.textarea.input(tui_textarea::Input {
                 key,
@@ -11,7 +11,7 @@ use ratzilla::ratatui::{
     style::Color,
     widgets::{Block, Paragraph},
 };
-use ratzilla::{event::KeyCode, WebRenderer};
+use ratzilla::{event::KeyCode, utils::set_cursor_shape, WebRenderer};
 use examples_shared::backend::{BackendType, MultiBackendBuilder};
 use ratzilla::backend::dom::DomBackendOptions;
 use ratzilla::backend::webgl2::WebGl2BackendOptions;
@@ -60,6 +60,8 @@ struct App {
     input_mode: InputMode,
     /// History of recorded messages
     messages: Vec<String>,
+    /// Shape of the cursor in the editor area.
+    cursor_shape: CursorShape,
 }
 
 enum InputMode {
@@ -74,6 +76,7 @@ impl App {
             input_mode: InputMode::Normal,
             messages: Vec::new(),
             character_index: 0,
+            cursor_shape: CursorShape::SteadyUnderScore,
         }
     }
 
@@ -141,13 +144,25 @@ impl App {
         self.reset_cursor();
     }
 
+    fn next_cursor_shape(&mut self) {
+        self.cursor_shape = match self.cursor_shape {
+            CursorShape::SteadyUnderScore => CursorShape::SteadyBar,
+            CursorShape::SteadyBar => CursorShape::SteadyBlock,
+            CursorShape::SteadyBlock => CursorShape::BlinkingUnderScore,
+            CursorShape::BlinkingUnderScore => CursorShape::BlinkingBar,
+            CursorShape::BlinkingBar => CursorShape::BlinkingBlock,
+            _ => CursorShape::SteadyUnderScore,
+        };
+        set_cursor_shape(self.cursor_shape);
+    }
+
     fn handle_events(&mut self, key_event: KeyEvent) {
         match self.input_mode {
-            InputMode::Normal => {
-                if let KeyCode::Char('e') = key_event.code {
-                    self.input_mode = InputMode::Editing;
-                }
-            }
+            InputMode::Normal => match key_event.code {
+                KeyCode::Char('e') => self.input_mode = InputMode::Editing,
+                KeyCode::Char('c') => self.next_cursor_shape(),
+                _ => {}
+            },
             InputMode::Editing => match key_event.code {
                 KeyCode::Enter => self.submit_message(),
                 KeyCode::Char(to_insert) => self.enter_char(to_insert),
@@ -170,7 +185,13 @@ impl App {
 
         let (msg, style) = match self.input_mode {
             InputMode::Normal => (
-                vec!["Press ".into(), "e".bold(), " to start editing.".bold()],
+                vec![
+                    "Press ".into(),
+                    "e".bold(),
+                    " to start editing, ".bold(),
+                    "c".bold(),
+                    format!(" to change the cursor ({:?}).", self.cursor_shape).bold(),
+                ],
                 Style::default().add_modifier(Modifier::RAPID_BLINK),
             ),
             InputMode::Editing => (
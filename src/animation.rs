@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use ratatui::layout::{Position, Rect};
+
+/// The order in which the cells of a [`Stagger`] start their animation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StaggerOrder {
+    /// From the left column to the right column.
+    #[default]
+    LeftToRight,
+    /// From the right column to the left column.
+    RightToLeft,
+    /// From the top row to the bottom row.
+    TopToBottom,
+    /// From the bottom row to the top row.
+    BottomToTop,
+    /// From the top left corner to the bottom right corner.
+    Diagonal,
+    /// From the center to the edges.
+    CenterOut,
+}
+
+/// Per-cell timing offsets for staggered animations.
+///
+/// Effects that share one clock animate all cells in lockstep. A [`Stagger`]
+/// delays the animation of each cell based on its position instead, so e.g. a
+/// reveal can sweep diagonally over the area, with the last cell starting
+/// `spread` after the first one.
+///
+/// This works with any effect that computes its state per cell, such as a
+/// custom `tachyonfx` shader or effect function:
+///
+/// ```rust
+/// use std::time::Duration;
+/// use ratzilla::{
+///     animation::{Stagger, StaggerOrder},
+///     ratatui::layout::{Position, Rect},
+/// };
+///
+/// let stagger = Stagger::new(StaggerOrder::Diagonal, Duration::from_millis(500));
+/// let area = Rect::new(0, 0, 11, 11);
+/// let elapsed = Duration::from_millis(250);
+///
+/// // Each cell fades in over 100ms, starting at its offset
+/// let duration = Duration::from_millis(100);
+/// assert_eq!(stagger.progress(area, Position::new(0, 0), elapsed, duration), 1.0);
+/// assert_eq!(stagger.progress(area, Position::new(10, 10), elapsed, duration), 0.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stagger {
+    /// The order of the cells.
+    order: StaggerOrder,
+    /// Delay between the first and the last cell.
+    spread: Duration,
+}
+
+impl Stagger {
+    /// Constructs a new [`Stagger`] with the given order and the delay between
+    /// the first and the last cell.
+    pub fn new(order: StaggerOrder, spread: Duration) -> Self {
+        Self { order, spread }
+    }
+
+    /// Returns the phase of the cell at the given position within the area,
+    /// from `0.0` for the first cell to `1.0` for the last cell.
+    pub fn phase(&self, area: Rect, position: Position) -> f32 {
+        /// Returns the position of `value` within `length` as a fraction.
+        fn fraction(value: u16, length: u16) -> f32 {
+            (value as f32 / length.saturating_sub(1).max(1) as f32).min(1.0)
+        }
+
+        let x = position.x.saturating_sub(area.x);
+        let y = position.y.saturating_sub(area.y);
+        match self.order {
+            StaggerOrder::LeftToRight => fraction(x, area.width),
+            StaggerOrder::RightToLeft => 1.0 - fraction(x, area.width),
+            StaggerOrder::TopToBottom => fraction(y, area.height),
+            StaggerOrder::BottomToTop => 1.0 - fraction(y, area.height),
+            StaggerOrder::Diagonal => fraction(x + y, (area.width + area.height).saturating_sub(1)),
+            StaggerOrder::CenterOut => {
+                // Cells are about twice as high as wide
+                let dx = (x as f32 - area.width.saturating_sub(1) as f32 / 2.0).abs();
+                let dy = (y as f32 - area.height.saturating_sub(1) as f32 / 2.0).abs() * 2.0;
+                let max_dx = area.width.saturating_sub(1) as f32 / 2.0;
+                let max_dy = area.height.saturating_sub(1) as f32;
+                let max = max_dx.hypot(max_dy);
+                if max == 0.0 {
+                    0.0
+                } else {
+                    (dx.hypot(dy) / max).min(1.0)
+                }
+            }
+        }
+    }
+
+    /// Returns the delay of the animation of the cell at the given position.
+    pub fn delay(&self, area: Rect, position: Position) -> Duration {
+        self.spread.mul_f32(self.phase(area, position))
+    }
+
+    /// Returns the progress of the animation of the cell at the given position,
+    /// from `0.0` to `1.0`.
+    ///
+    /// The `elapsed` time is the time of the shared clock, while `duration` is
+    /// the duration of the animation of a single cell.
+    pub fn progress(
+        &self,
+        area: Rect,
+        position: Position,
+        elapsed: Duration,
+        duration: Duration,
+    ) -> f32 {
+        let delay = self.delay(area, position);
+        if elapsed < delay {
+            return 0.0;
+        }
+        if duration.is_zero() {
+            return 1.0;
+        }
+        ((elapsed - delay).as_secs_f32() / duration.as_secs_f32()).min(1.0)
+    }
+
+    /// Returns the total duration of the animation, i.e. the time until the
+    /// animation of the last cell is finished.
+    pub fn total_duration(&self, duration: Duration) -> Duration {
+        self.spread + duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stagger_phase() {
+        let area = Rect::new(2, 1, 5, 3);
+        let phase =
+            |order, x, y| Stagger::new(order, Duration::ZERO).phase(area, Position::new(x, y));
+        assert_eq!(phase(StaggerOrder::LeftToRight, 2, 1), 0.0);
+        assert_eq!(phase(StaggerOrder::LeftToRight, 4, 1), 0.5);
+        assert_eq!(phase(StaggerOrder::RightToLeft, 6, 3), 0.0);
+        assert_eq!(phase(StaggerOrder::TopToBottom, 2, 3), 1.0);
+        assert_eq!(phase(StaggerOrder::BottomToTop, 2, 3), 0.0);
+        assert_eq!(phase(StaggerOrder::Diagonal, 2, 1), 0.0);
+        assert_eq!(phase(StaggerOrder::Diagonal, 6, 3), 1.0);
+        assert_eq!(phase(StaggerOrder::CenterOut, 4, 2), 0.0);
+        assert_eq!(phase(StaggerOrder::CenterOut, 6, 3), 1.0);
+    }
+}
@@ -0,0 +1,114 @@
+use std::fmt::Write;
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier, Style},
+    text::Span,
+};
+
+/// SGR parameters of the modifiers.
+const MODIFIERS: [(Modifier, u8); 9] = [
+    (Modifier::BOLD, 1),
+    (Modifier::DIM, 2),
+    (Modifier::ITALIC, 3),
+    (Modifier::UNDERLINED, 4),
+    (Modifier::SLOW_BLINK, 5),
+    (Modifier::RAPID_BLINK, 6),
+    (Modifier::REVERSED, 7),
+    (Modifier::HIDDEN, 8),
+    (Modifier::CROSSED_OUT, 9),
+];
+
+/// Serializes the given buffer into text with ANSI escape sequences.
+///
+/// The colors and modifiers of the cells are written as SGR (Select Graphic
+/// Rendition) sequences, so that printing the text in a terminal reproduces
+/// the buffer. Every line ends with a reset sequence and a newline.
+pub(crate) fn buffer_to_ansi(buffer: &Buffer) -> String {
+    let mut output = String::new();
+    let area = buffer.area;
+    for y in area.top()..area.bottom() {
+        let mut style = None;
+        let mut skip = 0;
+        for x in area.left()..area.right() {
+            let Some(cell) = buffer.cell((x, y)) else {
+                continue;
+            };
+            // Skip the cells that are covered by a wide character
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            if style != Some(cell.style()) {
+                write_sgr(&mut output, cell.style());
+                style = Some(cell.style());
+            }
+            output.push_str(cell.symbol());
+            skip = Span::raw(cell.symbol()).width().saturating_sub(1);
+        }
+        output.push_str("\x1b[0m\n");
+    }
+    output
+}
+
+/// Writes the SGR sequence of the given style, starting from the default style.
+fn write_sgr(output: &mut String, style: Style) {
+    output.push_str("\x1b[0");
+    for (modifier, code) in MODIFIERS {
+        if style.add_modifier.contains(modifier) {
+            let _ = write!(output, ";{code}");
+        }
+    }
+    if let Some(color) = style.fg {
+        write_color(output, color, 30);
+    }
+    if let Some(color) = style.bg {
+        write_color(output, color, 40);
+    }
+    output.push('m');
+}
+
+/// Writes the SGR parameters of the given color.
+///
+/// The `base` is `30` for foreground and `40` for background colors.
+fn write_color(output: &mut String, color: Color, base: u8) {
+    let _ = match color {
+        Color::Reset => write!(output, ";{}", base + 9),
+        Color::Black => write!(output, ";{base}"),
+        Color::Red => write!(output, ";{}", base + 1),
+        Color::Green => write!(output, ";{}", base + 2),
+        Color::Yellow => write!(output, ";{}", base + 3),
+        Color::Blue => write!(output, ";{}", base + 4),
+        Color::Magenta => write!(output, ";{}", base + 5),
+        Color::Cyan => write!(output, ";{}", base + 6),
+        Color::Gray => write!(output, ";{}", base + 7),
+        Color::DarkGray => write!(output, ";{}", base + 60),
+        Color::LightRed => write!(output, ";{}", base + 61),
+        Color::LightGreen => write!(output, ";{}", base + 62),
+        Color::LightYellow => write!(output, ";{}", base + 63),
+        Color::LightBlue => write!(output, ";{}", base + 64),
+        Color::LightMagenta => write!(output, ";{}", base + 65),
+        Color::LightCyan => write!(output, ";{}", base + 66),
+        Color::White => write!(output, ";{}", base + 67),
+        Color::Indexed(index) => write!(output, ";{};5;{index}", base + 8),
+        Color::Rgb(r, g, b) => write!(output, ";{};2;{r};{g};{b}", base + 8),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn test_buffer_to_ansi() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 2));
+        buffer.set_string(0, 0, "ab", Style::new().red().bold());
+        buffer.set_string(0, 1, "界", Style::new().bg(Color::Rgb(1, 2, 3)));
+        assert_eq!(
+            buffer_to_ansi(&buffer),
+            "\x1b[0;1;31;49mab\x1b[0;39;49m  \x1b[0m\n\
+             \x1b[0;39;48;2;1;2;3m界\x1b[0;39;49m  \x1b[0m\n"
+        );
+    }
+}
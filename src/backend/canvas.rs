@@ -1,14 +1,33 @@
 use bitvec::{bitvec, prelude::BitVec};
 use ratatui::{backend::ClearType, layout::Rect};
-use std::io::{Error as IoError, Result as IoResult};
+use std::{
+    cell::RefCell,
+    io::{Error as IoError, Result as IoResult},
+    ops::Range,
+    rc::Rc,
+    time::Duration,
+};
 
 use crate::{
     backend::{
-        color::{actual_bg_color, actual_fg_color},
+        color::{
+            actual_bg_color, actual_fg_color, bright_color, contrast_ratio, to_rgb,
+            MIN_CURSOR_CONTRAST,
+        },
+        control::control_pictures,
+        cursor::{CursorBlink, DEFAULT_BLINK_INTERVAL},
+        fallback::AsciiFallback,
+        multi::BackendType,
+        osc,
         utils::*,
     },
     error::Error,
-    CursorShape,
+    render::{
+        add_event_listener, is_frame_skipped, record_backend_type, record_cursor_position,
+        request_redraw, take_cursor_shape, watch_container, watch_focus,
+    },
+    widgets::graphics::GraphicsLayer,
+    CssPosition, CursorShape,
 };
 use ratatui::{
     backend::WindowSize,
@@ -18,34 +37,89 @@ use ratatui::{
     style::{Color, Modifier},
 };
 use web_sys::{
-    js_sys::{Boolean, Map},
-    wasm_bindgen::{JsCast, JsValue},
+    js_sys::{Boolean, Map, Reflect},
+    wasm_bindgen::{prelude::Closure, JsCast, JsValue},
+    HtmlCanvasElement,
 };
 
-/// Width of a single cell.
+/// Default width of a single cell.
 ///
 /// This will be used for multiplying the cell's x position to get the actual pixel
-/// position on the canvas.
+/// position on the canvas, unless overridden by [`CanvasBackendOptions::cell_size`].
 const CELL_WIDTH: f64 = 10.0;
 
-/// Height of a single cell.
+/// Default height of a single cell.
 ///
 /// This will be used for multiplying the cell's y position to get the actual pixel
-/// position on the canvas.
+/// position on the canvas, unless overridden by [`CanvasBackendOptions::cell_size`].
 const CELL_HEIGHT: f64 = 19.0;
 
+/// Width of the [`CursorShape::SteadyBar`] cursor in pixels.
+const BAR_CURSOR_WIDTH: f64 = 1.0;
+
+/// Font used for drawing text.
+const FONT: &str = "16px monospace";
+
+/// Font used for drawing bold text with [`BoldMode::FontWeight`].
+const BOLD_FONT: &str = "bold 16px monospace";
+
+/// Default font used for drawing emoji.
+///
+/// Lists the common color emoji fonts of each platform, falling back to the
+/// monospace font that is used for the rest of the text.
+const DEFAULT_EMOJI_FONT: &str =
+    "16px \"Apple Color Emoji\", \"Segoe UI Emoji\", \"Noto Color Emoji\", monospace";
+
 /// Options for the [`CanvasBackend`].
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct CanvasBackendOptions {
     /// The element ID.
     grid_id: Option<String>,
     /// Override the automatically detected size.
     size: Option<(u32, u32)>,
+    /// Override the default cell size.
+    cell_size: Option<(u32, u32)>,
+    /// Override the default emoji font.
+    emoji_font: Option<String>,
+    /// Duration of the smooth cursor animation.
+    smooth_cursor: Option<Duration>,
+    /// The cursor shape.
+    cursor_shape: CursorShape,
+    /// Blink interval of the cursor.
+    cursor_blink_interval: Option<Duration>,
+    /// Initial position of the cursor.
+    cursor_position: Option<Position>,
+    /// How bold text is rendered.
+    bold_mode: BoldMode,
+    /// Aspect ratio of the canvas.
+    aspect_ratio: Option<(u32, u32)>,
+    /// Whether the graphics layer is enabled.
+    graphics_layer: bool,
+    /// Whether grapheme clusters split across cells are joined.
+    join_graphemes: bool,
+    /// Whether URLs in the text are clickable.
+    hyperlinks: bool,
+    /// Whether text antialiasing is disabled.
+    disable_antialiasing: bool,
+    /// Whether the canvas is rendered at the device pixel ratio.
+    track_pixel_ratio: bool,
+    /// Cursor colors for cells whose colors have too little contrast.
+    cursor_fallback_colors: Option<(Color, Color)>,
+    /// The CSS `position` of the canvas.
+    position: Option<CssPosition>,
+    /// The CSS `z-index` of the canvas.
+    z_index: Option<i32>,
     /// Always clip foreground drawing to the cell rectangle. Helpful when
     /// dealing with out-of-bounds rendering from problematic fonts. Enabling
     /// this option may cause some performance issues when dealing with large
     /// numbers of simultaneous changes.
     always_clip_cells: bool,
+    /// Substitutions for rendering Unicode symbols as ASCII, if enabled.
+    ascii_fallback: Option<AsciiFallback>,
+    /// Notation for rendering control characters, if enabled.
+    control_chars: bool,
+    /// Margin around the grid in cells.
+    margin: u16,
 }
 
 impl CanvasBackendOptions {
@@ -65,6 +139,338 @@ impl CanvasBackendOptions {
         self.size = Some(size);
         self
     }
+
+    /// Sets the size of a single cell, in pixels.
+    ///
+    /// The grid will consist of `canvas_size / cell_size` cells, regardless
+    /// of the font that is being used. This makes the layout deterministic,
+    /// but it is up to the application to make sure that the font matches
+    /// the given cell size.
+    pub fn cell_size(mut self, size: (u32, u32)) -> Self {
+        self.cell_size = Some(size);
+        self
+    }
+
+    /// Sets the font that is used for drawing emoji, in CSS font format.
+    ///
+    /// This should be a font that supports color emoji, e.g.
+    /// `16px "Noto Color Emoji"`. By default, the color emoji fonts of
+    /// the common platforms are used.
+    pub fn emoji_font(mut self, font: &str) -> Self {
+        self.emoji_font = Some(font.to_string());
+        self
+    }
+
+    /// Keeps the canvas at the given aspect ratio, e.g. `(4, 3)`.
+    ///
+    /// The canvas is sized to the largest size with this aspect ratio that fits
+    /// into the available area and centered in it, leaving bars on the sides that
+    /// show the background of the parent element. Like the size of the canvas,
+    /// this is computed once from the parent element when the backend is created.
+    ///
+    /// The mouse coordinates are not adjusted, subtract
+    /// [`CanvasBackend::grid_offset`] from them to get the position within the grid.
+    ///
+    /// This is not supported by the DOM backend, since its grid follows the size
+    /// of the window.
+    pub fn aspect_ratio(mut self, ratio: (u32, u32)) -> Self {
+        self.aspect_ratio = Some(ratio);
+        self
+    }
+
+    /// Surrounds the grid with a margin of the given number of blank cells.
+    ///
+    /// The margin is filled with the background color and is part of the
+    /// size of the canvas. See [`CanvasBackend::grid_offset`] for mapping the
+    /// coordinates of the mouse events to the grid.
+    pub fn margin(mut self, cells: u16) -> Self {
+        self.margin = cells;
+        self
+    }
+
+    /// Sets the foreground and background color of the block cursor for cells
+    /// with too little contrast.
+    ///
+    /// The block cursor reverses the colors of the cell below it, which makes it
+    /// nearly invisible if the foreground and background colors are similar.
+    /// In that case, these colors are used instead. Defaults to black on white.
+    pub fn cursor_fallback_colors(mut self, fg: Color, bg: Color) -> Self {
+        self.cursor_fallback_colors = Some((fg, bg));
+        self
+    }
+
+    /// Enables the graphics layer for drawing the [`Graphics`] widget.
+    ///
+    /// The graphics layer is a separate canvas on top of the text grid, which
+    /// allows drawing shapes with pixel precision instead of the sub-cell
+    /// resolution of Braille or block characters.
+    ///
+    /// [`Graphics`]: crate::widgets::Graphics
+    pub fn graphics_layer(mut self, enabled: bool) -> Self {
+        self.graphics_layer = enabled;
+        self
+    }
+
+    /// Disables text antialiasing, for crisp pixel or bitmap fonts.
+    ///
+    /// This sets the `font-smooth` and `-webkit-font-smoothing` CSS properties
+    /// of the canvas and the `textRendering` of its context for speed over
+    /// legibility, which skips the subpixel positioning of the glyphs. The
+    /// canvas is also scaled with `image-rendering: pixelated`, so it stays
+    /// crisp on high-DPI displays. Note that whether the glyphs are drawn
+    /// without antialiasing ultimately depends on the browser and platform.
+    pub fn disable_antialiasing(mut self) -> Self {
+        self.disable_antialiasing = true;
+        self
+    }
+
+    /// Renders the canvas at the device pixel ratio of the display.
+    ///
+    /// By default, the canvas has one pixel per CSS pixel, which looks blurry
+    /// on high-DPI displays. With this option, the canvas is rendered at the
+    /// resolution of the display instead. The device pixel ratio is checked
+    /// on every frame, so the canvas is re-rendered at the new resolution
+    /// when it changes, e.g. when the window is moved to another monitor or
+    /// the page is zoomed.
+    pub fn track_pixel_ratio(mut self, enabled: bool) -> Self {
+        self.track_pixel_ratio = enabled;
+        self
+    }
+
+    /// Joins the grapheme clusters that are split across cells.
+    ///
+    /// Complex emoji such as ZWJ sequences (e.g. 👨‍👩‍👧) or flags consist of
+    /// multiple code points, which end up in separate cells if a widget writes
+    /// them one by one. With this option, such a cluster is rendered as a single
+    /// glyph in its leading cell and the continuation cells are left blank.
+    pub fn join_graphemes(mut self, enabled: bool) -> Self {
+        self.join_graphemes = enabled;
+        self
+    }
+
+    /// Enables hyperlinks in the canvas.
+    ///
+    /// Any URL in the rendered text (starting with `http://` or `https://`) is
+    /// detected and opened in a new tab when clicked.
+    pub fn enable_hyperlinks(mut self) -> Self {
+        self.hyperlinks = true;
+        self
+    }
+
+    /// Sets how bold text is rendered.
+    ///
+    /// See [`BoldMode`] for the available modes.
+    pub fn bold_mode(mut self, mode: BoldMode) -> Self {
+        self.bold_mode = mode;
+        self
+    }
+
+    /// Enables smooth cursor movement with the given animation duration.
+    ///
+    /// Instead of jumping between positions, the cursor glides to its new
+    /// position over the given duration, similar to the "smooth cursor"
+    /// feature of modern editors.
+    pub fn smooth_cursor(mut self, duration: Duration) -> Self {
+        self.smooth_cursor = Some(duration);
+        self
+    }
+
+    /// Sets the cursor shape to use when cursor is visible.
+    pub fn cursor_shape(mut self, shape: CursorShape) -> Self {
+        self.cursor_shape = shape;
+        self
+    }
+
+    /// Shows the cursor at the given position from the start.
+    ///
+    /// The cursor is hidden until the first frame sets its position otherwise.
+    /// Note that ratatui moves or hides the cursor with every drawn frame,
+    /// depending on whether the frame sets a cursor position. Use
+    /// [`CursorShape::None`] to never show a cursor.
+    pub fn cursor_position(mut self, position: impl Into<Position>) -> Self {
+        self.cursor_position = Some(position.into());
+        self
+    }
+
+    /// Sets the interval of toggling a blinking cursor.
+    ///
+    /// This only applies to the blinking [`CursorShape`]s. Defaults to
+    /// [`DEFAULT_BLINK_INTERVAL`].
+    pub fn cursor_blink_interval(mut self, interval: Duration) -> Self {
+        self.cursor_blink_interval = Some(interval);
+        self
+    }
+
+    /// Sets the CSS `position` of the canvas.
+    ///
+    /// Use this together with [`CanvasBackendOptions::z_index`] for placing the terminal
+    /// in front of or behind other content of the page.
+    pub fn position(mut self, position: CssPosition) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Sets the CSS `z-index` of the canvas.
+    ///
+    /// Note that the `z-index` only has an effect on positioned elements,
+    /// see [`CanvasBackendOptions::position`].
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = Some(z_index);
+        self
+    }
+
+    /// Renders common Unicode symbols as ASCII.
+    ///
+    /// Box-drawing characters are substituted with `+-|`, arrows with `<>^v`
+    /// and so on, which keeps the output readable on targets that can't render
+    /// these glyphs. See [`AsciiFallback`] for the substitutions.
+    pub fn ascii_fallback(mut self, enabled: bool) -> Self {
+        self.ascii_fallback = enabled.then(AsciiFallback::default);
+        self
+    }
+
+    /// Sets a custom substitution table for rendering Unicode symbols as ASCII.
+    ///
+    /// This enables the ASCII fallback, see [`CanvasBackendOptions::ascii_fallback`].
+    pub fn ascii_fallback_table(mut self, table: AsciiFallback) -> Self {
+        self.ascii_fallback = Some(table);
+        self
+    }
+
+    /// Renders control characters visibly as Unicode control pictures.
+    ///
+    /// This applies to the cells whose symbols contain control characters,
+    /// see [`ControlChars::Pictures`](crate::backend::control::ControlChars::Pictures).
+    pub fn show_control_chars(mut self, enabled: bool) -> Self {
+        self.control_chars = enabled;
+        self
+    }
+}
+
+/// The detected URLs of each line, along with their ranges of cells.
+type LineLinks = Vec<Vec<(Range<usize>, String)>>;
+
+/// Adds the listeners for opening the detected URLs on click.
+fn add_hyperlink_listeners(
+    canvas: &HtmlCanvasElement,
+    links: Rc<RefCell<LineLinks>>,
+    cell_size: (f64, f64),
+) -> Result<(), Error> {
+    // Returns the URL below the mouse cursor, if any
+    let url_at = move |event: &web_sys::Event| {
+        let event = event.dyn_ref::<web_sys::MouseEvent>()?;
+        let x = (event.offset_x() as f64 / cell_size.0) as usize;
+        let y = (event.offset_y() as f64 / cell_size.1) as usize;
+        links
+            .borrow()
+            .get(y)?
+            .iter()
+            .find(|(range, _)| range.contains(&x))
+            .map(|(_, url)| url.clone())
+    };
+    let url_at = Rc::new(url_at);
+
+    let on_click = Closure::<dyn FnMut(_)>::new({
+        let url_at = url_at.clone();
+        move |event: web_sys::Event| {
+            if let Some(url) = url_at(&event) {
+                let _ = crate::utils::open_url(&url, true);
+            }
+        }
+    });
+    add_event_listener(canvas, "click", &Rc::new(on_click))?;
+
+    let style = canvas.style();
+    let on_mouse_move = Closure::<dyn FnMut(_)>::new(move |event: web_sys::Event| {
+        let cursor = if url_at(&event).is_some() {
+            "pointer"
+        } else {
+            ""
+        };
+        let _ = style.set_property("cursor", cursor);
+    });
+    add_event_listener(canvas, "mousemove", &Rc::new(on_mouse_move))
+}
+
+/// Rendering modes for bold text.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BoldMode {
+    /// Use the bold weight of the font.
+    #[default]
+    FontWeight,
+    /// Draw an outline around the glyphs to make them look heavier.
+    ///
+    /// This is useful for fonts without a bold face.
+    SyntheticStroke,
+    /// Use the bright variant of the foreground color instead of a heavier font.
+    ///
+    /// This is the classic terminal behavior, e.g. bold red text is rendered
+    /// as bright red. Colors without a bright variant are left unchanged.
+    BrightColor,
+}
+
+/// State of the smooth cursor animation.
+#[derive(Debug)]
+struct SmoothCursor {
+    /// Duration of the animation, in milliseconds.
+    duration: f64,
+    /// Position that the cursor moves from, in cells.
+    from: (f64, f64),
+    /// Position that the cursor moves to.
+    target: Option<Position>,
+    /// Start time of the animation, in milliseconds.
+    start: f64,
+    /// Position where the cursor was last drawn, in cells.
+    drawn: Option<(f64, f64)>,
+}
+
+impl SmoothCursor {
+    /// Constructs a new [`SmoothCursor`] with the given animation duration.
+    fn new(duration: Duration) -> Self {
+        Self {
+            duration: duration.as_secs_f64() * 1000.0,
+            from: (0.0, 0.0),
+            target: None,
+            start: 0.0,
+            drawn: None,
+        }
+    }
+
+    /// Sets the target position, starting a new animation if it changed.
+    ///
+    /// The cursor jumps to the target if it was hidden before.
+    fn set_target(&mut self, target: Option<Position>, now: f64) {
+        if target == self.target {
+            return;
+        }
+        let current = self.position(now);
+        self.target = target;
+        self.start = now;
+        if let Some(target) = target {
+            self.from = current.unwrap_or((target.x as f64, target.y as f64));
+        }
+    }
+
+    /// Returns the position of the cursor at the given time, in cells.
+    fn position(&self, now: f64) -> Option<(f64, f64)> {
+        let target = self.target?;
+        let progress = if self.duration > 0.0 {
+            ((now - self.start) / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        // Ease out cubic
+        let progress = 1.0 - (1.0 - progress).powi(3);
+        Some((
+            self.from.0 + (target.x as f64 - self.from.0) * progress,
+            self.from.1 + (target.y as f64 - self.from.1) * progress,
+        ))
+    }
+
+    /// Returns `true` if the cursor needs to be redrawn at the given time.
+    fn needs_redraw(&self, now: f64) -> bool {
+        self.position(now) != self.drawn
+    }
 }
 
 /// Canvas renderer.
@@ -76,6 +482,10 @@ struct Canvas {
     context: web_sys::CanvasRenderingContext2d,
     /// Background color.
     background_color: Color,
+    /// Size of the canvas in CSS pixels.
+    size: (u32, u32),
+    /// Number of canvas pixels per CSS pixel.
+    pixel_ratio: f64,
 }
 
 impl Canvas {
@@ -95,28 +505,81 @@ impl Canvas {
             &Boolean::from(JsValue::TRUE),
         );
         let context = canvas
-            .get_context_with_context_options("2d", &context_options)?
-            .ok_or_else(|| Error::UnableToRetrieveCanvasContext)?
+            .get_context_with_context_options("2d", &context_options)
+            .map_err(Error::from)
+            .and_then(|context| context.ok_or(Error::UnableToRetrieveCanvasContext))
+            // Remove the canvas again, so that another backend can be created
+            // in its place
+            .inspect_err(|_| canvas.remove())?
             .dyn_into::<web_sys::CanvasRenderingContext2d>()
             .expect("Unable to cast canvas context");
-        context.set_font("16px monospace");
+        context.set_font(FONT);
         context.set_text_baseline("top");
 
         Ok(Self {
             inner: canvas,
             context,
             background_color,
+            size: (width, height),
+            pixel_ratio: 1.0,
         })
     }
+
+    /// Renders the canvas at the given number of canvas pixels per CSS pixel.
+    ///
+    /// The canvas keeps its size on the page, while its resolution is scaled.
+    /// Since resizing the canvas resets its context, the content has to be
+    /// redrawn afterwards.
+    fn set_pixel_ratio(&mut self, ratio: f64) -> Result<(), Error> {
+        let (width, height) = self.size;
+        let style = self.inner.style();
+        style.set_property("width", &format!("{width}px"))?;
+        style.set_property("height", &format!("{height}px"))?;
+
+        // Keep the text rendering mode, which is reset along with the context
+        let text_rendering = Reflect::get(&self.context, &JsValue::from_str("textRendering"))?;
+        self.inner.set_width((width as f64 * ratio).round() as u32);
+        self.inner
+            .set_height((height as f64 * ratio).round() as u32);
+        self.context
+            .set_transform(ratio, 0.0, 0.0, ratio, 0.0, 0.0)?;
+        self.context.set_font(FONT);
+        self.context.set_text_baseline("top");
+        Reflect::set(
+            &self.context,
+            &JsValue::from_str("textRendering"),
+            &text_rendering,
+        )?;
+        self.pixel_ratio = ratio;
+        Ok(())
+    }
+
+    /// Disables the antialiasing of the text.
+    fn disable_antialiasing(&self) -> Result<(), Error> {
+        let style = self.inner.style();
+        disable_text_antialiasing(&style)?;
+        style.set_property("image-rendering", "pixelated")?;
+        Reflect::set(
+            &self.context,
+            &JsValue::from_str("textRendering"),
+            &JsValue::from_str("optimizeSpeed"),
+        )?;
+        Ok(())
+    }
 }
 
 /// Canvas backend.
 ///
 /// This backend renders the buffer onto a HTML canvas element.
+///
+/// Since every cell is drawn separately, font ligatures never span multiple
+/// cells, which keeps the grid aligned regardless of the font.
 #[derive(Debug)]
 pub struct CanvasBackend {
     /// Whether the canvas has been initialized.
     initialized: bool,
+    /// Whether the whole canvas is redrawn on the next flush.
+    redraw: bool,
     /// Always clip foreground drawing to the cell rectangle. Helpful when
     /// dealing with out-of-bounds rendering from problematic fonts. Enabling
     /// this option may cause some performance issues when dealing with large
@@ -128,12 +591,48 @@ pub struct CanvasBackend {
     prev_buffer: Vec<Vec<Cell>>,
     /// Changed buffer cells
     changed_cells: BitVec,
+    /// Width of a single cell.
+    cell_width: f64,
+    /// Height of a single cell.
+    cell_height: f64,
+    /// Offset of the letterboxed canvas.
+    letterbox_offset: (u32, u32),
+    /// Margin around the grid in pixels.
+    margin: (u32, u32),
+    /// Font used for drawing emoji.
+    emoji_font: String,
+    /// How bold text is rendered.
+    bold_mode: BoldMode,
+    /// Whether grapheme clusters split across cells are joined.
+    join_graphemes: bool,
+    /// Substitutions for rendering Unicode symbols as ASCII, if enabled.
+    ascii_fallback: Option<AsciiFallback>,
+    /// Notation for rendering control characters, if enabled.
+    control_chars: bool,
+    /// Whether the canvas is rendered at the device pixel ratio.
+    track_pixel_ratio: bool,
+    /// Detected URLs of each line, if hyperlinks are enabled.
+    links: Option<Rc<RefCell<LineLinks>>>,
+    /// Smooth cursor animation, if enabled.
+    smooth_cursor: Option<SmoothCursor>,
+    /// Graphics layer, if enabled.
+    graphics_layer: Option<GraphicsLayer>,
     /// Canvas.
     canvas: Canvas,
     /// Cursor position.
     cursor_position: Option<Position>,
     /// The cursor shape.
     cursor_shape: CursorShape,
+    /// Whether the cursor shape or visibility changed since the last flush.
+    cursor_shape_changed: bool,
+    /// Blinking state of the cursor.
+    cursor_blink: CursorBlink,
+    /// Color of the cursor, if set.
+    cursor_color: Option<Color>,
+    /// Position of the last drawn bar cursor.
+    drawn_bar_cursor: Option<Position>,
+    /// Cursor colors for cells whose colors have too little contrast.
+    cursor_fallback_colors: (Color, Color),
     /// Draw cell boundaries with specified color.
     debug_mode: Option<String>,
 }
@@ -157,23 +656,111 @@ impl CanvasBackend {
     pub fn new_with_options(options: CanvasBackendOptions) -> Result<Self, Error> {
         // Parent element of canvas (uses <body> unless specified)
         let parent = get_element_by_id_or_body(options.grid_id.as_ref())?;
+        watch_container(&parent);
 
         let (width, height) = options
             .size
             .unwrap_or_else(|| (parent.client_width() as u32, parent.client_height() as u32));
 
-        let canvas = Canvas::new(parent, width, height, Color::Black)?;
-        let buffer = get_sized_buffer_from_canvas(&canvas.inner);
+        let (cell_width, cell_height) = options
+            .cell_size
+            .map(|(w, h)| (w as f64, h as f64))
+            .unwrap_or((CELL_WIDTH, CELL_HEIGHT));
+
+        let (canvas_width, canvas_height) = options.aspect_ratio.map_or((width, height), |ratio| {
+            fit_aspect_ratio((width, height), ratio)
+        });
+        // The margin is drawn around the canvas, within its size
+        let margin = (
+            (options.margin as f64 * cell_width) as u32,
+            (options.margin as f64 * cell_height) as u32,
+        );
+        let mut canvas = Canvas::new(
+            parent.clone(),
+            canvas_width.saturating_sub(margin.0 * 2),
+            canvas_height.saturating_sub(margin.1 * 2),
+            Color::Black,
+        )?;
+        if options.margin > 0 {
+            set_margin(
+                &canvas.inner.style(),
+                margin,
+                &get_canvas_color(canvas.background_color, Color::Black),
+            )?;
+        }
+        if options.disable_antialiasing {
+            canvas.disable_antialiasing()?;
+        }
+        if options.track_pixel_ratio {
+            canvas.set_pixel_ratio(device_pixel_ratio()?)?;
+        }
+        let letterbox_offset = if options.aspect_ratio.is_some() {
+            letterbox_canvas(
+                &canvas.inner,
+                (width, height),
+                (canvas_width, canvas_height),
+            )?
+        } else {
+            (0, 0)
+        };
+        set_stacking(&canvas.inner, options.position, options.z_index)?;
+        watch_focus(&canvas.inner)?;
+        let graphics_layer = options
+            .graphics_layer
+            .then(|| GraphicsLayer::new(&parent))
+            .transpose()?;
+        let buffer = get_sized_buffer_from_canvas(&canvas.inner, cell_width, cell_height);
         let changed_cells = bitvec![0; buffer.len() * buffer[0].len()];
+        let links = if options.hyperlinks {
+            let links = Rc::new(RefCell::new(vec![Vec::new(); buffer.len()]));
+            add_hyperlink_listeners(&canvas.inner, links.clone(), (cell_width, cell_height))?;
+            Some(links)
+        } else {
+            None
+        };
+        let cursor_position = options.cursor_position.filter(|pos| {
+            buffer
+                .get(pos.y as usize)
+                .is_some_and(|line| (pos.x as usize) < line.len())
+        });
+        record_cursor_position(cursor_position);
+        record_backend_type(BackendType::Canvas);
         Ok(Self {
             prev_buffer: buffer.clone(),
             always_clip_cells: options.always_clip_cells,
             buffer,
             initialized: false,
+            redraw: false,
             changed_cells,
+            cell_width,
+            cell_height,
+            letterbox_offset,
+            margin,
+            emoji_font: options
+                .emoji_font
+                .unwrap_or_else(|| DEFAULT_EMOJI_FONT.to_string()),
+            smooth_cursor: options.smooth_cursor.map(SmoothCursor::new),
+            graphics_layer,
+            bold_mode: options.bold_mode,
+            join_graphemes: options.join_graphemes,
+            ascii_fallback: options.ascii_fallback,
+            control_chars: options.control_chars,
+            track_pixel_ratio: options.track_pixel_ratio,
+            links,
             canvas,
-            cursor_position: None,
-            cursor_shape: CursorShape::SteadyBlock,
+            cursor_position,
+            cursor_shape: options.cursor_shape,
+            cursor_shape_changed: false,
+            cursor_blink: CursorBlink::new(
+                options
+                    .cursor_blink_interval
+                    .unwrap_or(DEFAULT_BLINK_INTERVAL),
+            ),
+            cursor_color: None,
+            drawn_bar_cursor: None,
+            cursor_fallback_colors: options
+                .cursor_fallback_colors
+                .unwrap_or((Color::Black, Color::White)),
             debug_mode: None,
         })
     }
@@ -181,6 +768,36 @@ impl CanvasBackend {
     /// Sets the background color of the canvas.
     pub fn set_background_color(&mut self, color: Color) {
         self.canvas.background_color = color;
+        // Keep the margin in the background color
+        self.canvas
+            .inner
+            .style()
+            .set_property("border-color", &get_canvas_color(color, Color::Black))
+            .unwrap_or_default();
+        request_redraw();
+    }
+
+    /// Returns the offset of the canvas within the available area, in pixels.
+    ///
+    /// This is non-zero when the canvas is letterboxed (see
+    /// [`CanvasBackendOptions::aspect_ratio`]) and should be subtracted from the mouse
+    /// coordinates to get the position within the canvas.
+    pub fn letterbox_offset(&self) -> (u32, u32) {
+        self.letterbox_offset
+    }
+
+    /// Returns the offset of the grid within the available area, in pixels.
+    ///
+    /// This is the letterbox offset plus the margin around the grid, and
+    /// should be subtracted from the mouse coordinates to get the position
+    /// within the grid, see [`MouseEvent::relative_to`].
+    ///
+    /// [`MouseEvent::relative_to`]: crate::event::MouseEvent::relative_to
+    pub fn grid_offset(&self) -> (u32, u32) {
+        (
+            self.letterbox_offset.0 + self.margin.0,
+            self.letterbox_offset.1 + self.margin.1,
+        )
     }
 
     /// Returns the [`CursorShape`].
@@ -188,12 +805,28 @@ impl CanvasBackend {
         &self.cursor_shape
     }
 
+    /// Returns the canvas element that the backend renders to.
+    pub fn canvas(&self) -> &web_sys::HtmlCanvasElement {
+        &self.canvas.inner
+    }
+
     /// Set the [`CursorShape`].
     pub fn set_cursor_shape(mut self, shape: CursorShape) -> Self {
         self.cursor_shape = shape;
         self
     }
 
+    /// Sets the color of the cursor.
+    ///
+    /// This is the background of the block cursor and the color of the bar and
+    /// underscore cursors. [`Color::Reset`] restores the default cursor, which
+    /// reverses the colors of the cell.
+    pub fn set_cursor_color(&mut self, color: Color) {
+        self.cursor_color = (color != Color::Reset).then_some(color);
+        self.redraw = true;
+        request_redraw();
+    }
+
     /// Enable or disable debug mode to draw cells with a specified color.
     ///
     /// The format of the color is the same as the CSS color format, e.g.:
@@ -233,9 +866,12 @@ impl CanvasBackend {
         // traversing it once per cell; this is done to reduce the number of
         // WASM calls per cell.
         self.resolve_changed_cells(force_redraw);
+        self.invalidate_smooth_cursor();
+        self.invalidate_bar_cursor();
         self.draw_background()?;
         self.draw_symbols()?;
         self.draw_cursor()?;
+        self.draw_smooth_cursor()?;
         if self.debug_mode.is_some() {
             self.draw_debug()?;
         }
@@ -274,12 +910,16 @@ impl CanvasBackend {
     /// 2. Tracks the last foreground color used to avoid unnecessary style changes
     /// 3. Only creates clipping paths for potentially problematic glyphs (non-ASCII)
     /// or when `always_clip_cells` is enabled.
+    ///
+    /// Emoji are drawn with the configured emoji font and are clipped to two cells,
+    /// since they are rendered as wide characters. Bold text is drawn according to
+    /// the configured [`BoldMode`].
     fn draw_symbols(&mut self) -> Result<(), Error> {
         let changed_cells = &self.changed_cells;
         let mut index = 0;
 
         self.canvas.context.save();
-        let mut last_color = None;
+        let mut last_style = None;
         for (y, line) in self.buffer.iter().enumerate() {
             for (x, cell) in line.iter().enumerate() {
                 // Skip empty cells
@@ -287,42 +927,63 @@ impl CanvasBackend {
                     index += 1;
                     continue;
                 }
-                let color = actual_fg_color(cell);
+                let color = self.cell_colors(x, y, cell).0;
+                let is_emoji = is_emoji(cell.symbol());
+                // Emoji have no bold face
+                let mut bold = cell.modifier.contains(Modifier::BOLD) && !is_emoji;
+                let color = if bold && self.bold_mode == BoldMode::BrightColor {
+                    bold = false;
+                    bright_color(color)
+                } else {
+                    color
+                };
 
                 // We need to reset the canvas context state in two scenarios:
                 // 1. When we need to create a clipping path (for potentially problematic glyphs)
-                // 2. When the text color changes
+                // 2. When the text color or weight changes
                 if self.always_clip_cells || !cell.symbol().is_ascii() {
                     self.canvas.context.restore();
                     self.canvas.context.save();
 
+                    let width = if is_emoji { 2.0 } else { 1.0 } * self.cell_width;
                     self.canvas.context.begin_path();
                     self.canvas.context.rect(
-                        x as f64 * CELL_WIDTH,
-                        y as f64 * CELL_HEIGHT,
-                        CELL_WIDTH,
-                        CELL_HEIGHT,
+                        x as f64 * self.cell_width,
+                        y as f64 * self.cell_height,
+                        width,
+                        self.cell_height,
                     );
                     self.canvas.context.clip();
 
-                    last_color = None; // reset last color to avoid clipping
+                    // The font is restored along with the rest of the context state
+                    if is_emoji {
+                        self.canvas.context.set_font(&self.emoji_font);
+                    }
+
+                    last_style = None; // reset last style to avoid clipping
                     let color = get_canvas_color(color, Color::White);
                     self.canvas.context.set_fill_style_str(&color);
-                } else if last_color != Some(color) {
+                    if bold {
+                        self.set_bold_style(&color);
+                    }
+                } else if last_style != Some((color, bold)) {
                     self.canvas.context.restore();
                     self.canvas.context.save();
 
-                    last_color = Some(color);
+                    last_style = Some((color, bold));
 
                     let color = get_canvas_color(color, Color::White);
                     self.canvas.context.set_fill_style_str(&color);
+                    if bold {
+                        self.set_bold_style(&color);
+                    }
                 }
 
-                self.canvas.context.fill_text(
-                    cell.symbol(),
-                    x as f64 * CELL_WIDTH,
-                    y as f64 * CELL_HEIGHT,
-                )?;
+                let (x, y) = (x as f64 * self.cell_width, y as f64 * self.cell_height);
+                self.canvas.context.fill_text(cell.symbol(), x, y)?;
+                if bold && self.bold_mode == BoldMode::SyntheticStroke {
+                    self.canvas.context.stroke_text(cell.symbol(), x, y)?;
+                }
 
                 index += 1;
             }
@@ -332,6 +993,18 @@ impl CanvasBackend {
         Ok(())
     }
 
+    /// Sets up the canvas context for drawing bold text with the given color.
+    fn set_bold_style(&self, color: &str) {
+        match self.bold_mode {
+            BoldMode::FontWeight => self.canvas.context.set_font(BOLD_FONT),
+            BoldMode::SyntheticStroke => {
+                self.canvas.context.set_stroke_style_str(color);
+                self.canvas.context.set_line_width(0.5);
+            }
+            BoldMode::BrightColor => {}
+        }
+    }
+
     /// Draws the background of the cells.
     ///
     /// This function uses [`RowColorOptimizer`] to optimize the drawing of the background
@@ -348,10 +1021,10 @@ impl CanvasBackend {
 
             self.canvas.context.set_fill_style_str(&color);
             self.canvas.context.fill_rect(
-                rect.x as f64 * CELL_WIDTH,
-                rect.y as f64 * CELL_HEIGHT,
-                rect.width as f64 * CELL_WIDTH,
-                rect.height as f64 * CELL_HEIGHT,
+                rect.x as f64 * self.cell_width,
+                rect.y as f64 * self.cell_height,
+                rect.width as f64 * self.cell_width,
+                rect.height as f64 * self.cell_height,
             );
         };
 
@@ -362,7 +1035,7 @@ impl CanvasBackend {
                 if changed_cells[index] {
                     // Only calls `draw_region` if the color is different from the previous one
                     row_renderer
-                        .process_color((x, y), actual_bg_color(cell))
+                        .process_color((x, y), self.cell_colors(x, y, cell).1)
                         .map(draw_region);
                 } else {
                     // Cell is unchanged so we must flush any held region
@@ -380,18 +1053,71 @@ impl CanvasBackend {
         Ok(())
     }
 
+    /// Returns the actual foreground and background color of the given cell.
+    ///
+    /// The block cursor uses the cursor color as the background if it is set.
+    /// Otherwise, it uses the fallback cursor colors instead of the reversed
+    /// cell colors if they have too little contrast to be visible.
+    fn cell_colors(&self, x: usize, y: usize, cell: &Cell) -> (Color, Color) {
+        let colors = (actual_fg_color(cell), actual_bg_color(cell));
+        let is_block_cursor = self.cursor_shape.steady() == CursorShape::SteadyBlock
+            && self.smooth_cursor.is_none()
+            && self.cursor_blink.is_visible()
+            && self.cursor_position.is_some_and(|pos| {
+                pos.y as usize == y && cursor_cells(&self.buffer[y], pos.x as usize).contains(&x)
+            });
+        if !is_block_cursor {
+            return colors;
+        }
+        if let Some(color) = self.cursor_color {
+            return (colors.0, color);
+        }
+        let background = to_rgb(self.canvas.background_color, 0x000000);
+        let contrast = contrast_ratio(to_rgb(cell.fg, 0xffffff), to_rgb(cell.bg, background));
+        if contrast < MIN_CURSOR_CONTRAST {
+            self.cursor_fallback_colors
+        } else {
+            colors
+        }
+    }
+
     /// Draws the cursor on the canvas.
     fn draw_cursor(&mut self) -> Result<(), Error> {
-        if let Some(pos) = self.cursor_position {
+        if matches!(self.cursor_shape, CursorShape::None) {
+            return Ok(());
+        }
+        if let Some(pos) = self.bar_cursor_position() {
+            let (x, y) = (pos.x as usize, pos.y as usize);
+            let color = self
+                .cursor_color
+                .unwrap_or_else(|| self.cell_colors(x, y, &self.buffer[y][x]).0);
+            self.canvas.context.save();
+            self.canvas
+                .context
+                .set_fill_style_str(&get_canvas_color(color, Color::White));
+            self.canvas.context.fill_rect(
+                x as f64 * self.cell_width,
+                y as f64 * self.cell_height,
+                BAR_CURSOR_WIDTH,
+                self.cell_height,
+            );
+            self.canvas.context.restore();
+            self.drawn_bar_cursor = Some(pos);
+        } else if let Some(pos) = self.cursor_position {
             let cell = &self.buffer[pos.y as usize][pos.x as usize];
 
             if cell.modifier.contains(Modifier::UNDERLINED) {
                 self.canvas.context.save();
+                if let Some(color) = self.cursor_color {
+                    self.canvas
+                        .context
+                        .set_fill_style_str(&get_canvas_color(color, Color::White));
+                }
 
                 self.canvas.context.fill_text(
                     "_",
-                    pos.x as f64 * CELL_WIDTH,
-                    pos.y as f64 * CELL_HEIGHT,
+                    pos.x as f64 * self.cell_width,
+                    pos.y as f64 * self.cell_height,
                 )?;
 
                 self.canvas.context.restore();
@@ -401,6 +1127,104 @@ impl CanvasBackend {
         Ok(())
     }
 
+    /// Returns the position of the bar cursor, if it is drawn.
+    ///
+    /// The smooth cursor draws the bar as an overlay instead.
+    fn bar_cursor_position(&self) -> Option<Position> {
+        self.cursor_position.filter(|pos| {
+            self.cursor_shape.steady() == CursorShape::SteadyBar
+                && self.smooth_cursor.is_none()
+                && self.cursor_blink.is_visible()
+                && self
+                    .buffer
+                    .get(pos.y as usize)
+                    .is_some_and(|line| (pos.x as usize) < line.len())
+        })
+    }
+
+    /// Marks the cell below the previously drawn bar cursor as changed.
+    ///
+    /// This makes sure that the bar is erased when the cursor moves.
+    fn invalidate_bar_cursor(&mut self) {
+        let Some(pos) = self.drawn_bar_cursor.take() else {
+            return;
+        };
+        let (x, y) = (pos.x as usize, pos.y as usize);
+        if self.buffer.get(y).is_some_and(|line| x < line.len()) {
+            let offset: usize = self.buffer[..y].iter().map(Vec::len).sum();
+            self.changed_cells.set(offset + x, true);
+        }
+    }
+
+    /// Marks the cells below the previously drawn smooth cursor as changed.
+    ///
+    /// This makes sure that the cursor is erased before it is drawn again.
+    fn invalidate_smooth_cursor(&mut self) {
+        let Some((x, y)) = self
+            .smooth_cursor
+            .as_mut()
+            .and_then(|cursor| cursor.drawn.take())
+        else {
+            return;
+        };
+        // The cursor may overlap up to two cells in each direction
+        for row in y.floor() as usize..=y.ceil() as usize {
+            let Some(line) = self.buffer.get(row) else {
+                continue;
+            };
+            let offset: usize = self.buffer[..row].iter().map(Vec::len).sum();
+            for col in x.floor() as usize..=(x.ceil() as usize).min(line.len().saturating_sub(1)) {
+                self.changed_cells.set(offset + col, true);
+            }
+        }
+    }
+
+    /// Draws the smooth cursor at its current position.
+    ///
+    /// The cursor is drawn as an overlay that inverts the colors below it (or
+    /// with the cursor color, if set), so that it can be placed between cells
+    /// while it is moving.
+    fn draw_smooth_cursor(&mut self) -> Result<(), Error> {
+        let Some(cursor) = self.smooth_cursor.as_mut() else {
+            return Ok(());
+        };
+        let now = performance()?.now();
+        cursor.set_target(self.cursor_position, now);
+        let Some((x, y)) = cursor.position(now) else {
+            return Ok(());
+        };
+        cursor.drawn = Some((x, y));
+        if !self.cursor_blink.is_visible() {
+            return Ok(());
+        }
+
+        let (x, y) = (x * self.cell_width, y * self.cell_height);
+        let (y, width, height) = match self.cursor_shape.steady() {
+            CursorShape::SteadyBlock => (y, self.cell_width, self.cell_height),
+            CursorShape::SteadyUnderScore => (y + self.cell_height - 2.0, self.cell_width, 2.0),
+            CursorShape::SteadyBar => (y, BAR_CURSOR_WIDTH, self.cell_height),
+            _ => return Ok(()),
+        };
+
+        self.canvas.context.save();
+        match self.cursor_color {
+            Some(color) => self
+                .canvas
+                .context
+                .set_fill_style_str(&get_canvas_color(color, Color::White)),
+            None => {
+                self.canvas
+                    .context
+                    .set_global_composite_operation("difference")?;
+                self.canvas.context.set_fill_style_str("white");
+            }
+        }
+        self.canvas.context.fill_rect(x, y, width, height);
+        self.canvas.context.restore();
+
+        Ok(())
+    }
+
     /// Draws cell boundaries for debugging.
     fn draw_debug(&mut self) -> Result<(), Error> {
         self.canvas.context.save();
@@ -410,10 +1234,10 @@ impl CanvasBackend {
             for (x, _) in line.iter().enumerate() {
                 self.canvas.context.set_stroke_style_str(color);
                 self.canvas.context.stroke_rect(
-                    x as f64 * CELL_WIDTH,
-                    y as f64 * CELL_HEIGHT,
-                    CELL_WIDTH,
-                    CELL_HEIGHT,
+                    x as f64 * self.cell_width,
+                    y as f64 * self.cell_height,
+                    self.cell_width,
+                    self.cell_height,
                 );
             }
         }
@@ -432,25 +1256,84 @@ impl Backend for CanvasBackend {
     where
         I: Iterator<Item = (u16, u16, &'a Cell)>,
     {
+        // Nothing changed since the previous frame
+        if is_frame_skipped() {
+            return Ok(());
+        }
+
+        // Switch to the requested cursor shape, hiding the current cursor first
+        if let Some(shape) = take_cursor_shape() {
+            if let Some(pos) = self
+                .cursor_position
+                .filter(|_| self.smooth_cursor.is_none())
+            {
+                let shape = self.cursor_shape;
+                style_cursor_cells(&mut self.buffer[pos.y as usize], pos.x as usize, |style| {
+                    shape.hide(style)
+                });
+            }
+            self.cursor_shape_changed = self.cursor_shape != shape;
+            self.cursor_shape = shape;
+        }
+
+        let mut updated_lines = Vec::new();
         for (x, y, cell) in content {
             let y = y as usize;
             let x = x as usize;
             let line = &mut self.buffer[y];
             line.extend(std::iter::repeat_with(Cell::default).take(x.saturating_sub(line.len())));
-            line[x] = cell.clone();
+            line[x] = osc::handle_sequences(cell);
+            if let Some(cell) = self
+                .ascii_fallback
+                .as_ref()
+                .and_then(|fallback| fallback.apply(&line[x]))
+            {
+                line[x] = cell;
+            }
+            if let Some(cell) = self
+                .control_chars
+                .then(|| control_pictures(&line[x]))
+                .flatten()
+            {
+                line[x] = cell;
+            }
+            if updated_lines.last() != Some(&y) {
+                updated_lines.push(y);
+            }
         }
-
-        // Draw the cursor if set
-        if let Some(pos) = self.cursor_position {
-            let y = pos.y as usize;
-            let x = pos.x as usize;
-            let line = &mut self.buffer[y];
-            if x < line.len() {
-                let cursor_style = self.cursor_shape.show(line[x].style());
-                line[x].set_style(cursor_style);
+        for &y in &updated_lines {
+            attach_zero_width_chars(&mut self.buffer[y]);
+            if self.join_graphemes {
+                join_grapheme_clusters(&mut self.buffer[y]);
+            }
+        }
+        if let Some(links) = &self.links {
+            let mut links = links.borrow_mut();
+            for y in updated_lines {
+                links[y] = detect_urls(&self.buffer[y]);
             }
         }
 
+        // Draw the cursor if set (the smooth cursor is drawn as an overlay instead),
+        // unless a blinking cursor is off at the moment
+        if self.cursor_blink.update(self.cursor_shape)? {
+            self.cursor_shape_changed = true;
+        }
+        if let Some(pos) = self
+            .cursor_position
+            .filter(|_| self.smooth_cursor.is_none())
+        {
+            let shape = self.cursor_shape;
+            let visible = self.cursor_blink.is_visible();
+            style_cursor_cells(&mut self.buffer[pos.y as usize], pos.x as usize, |style| {
+                if visible {
+                    shape.show(style)
+                } else {
+                    shape.hide(style)
+                }
+            });
+        }
+
         Ok(())
     }
 
@@ -459,15 +1342,47 @@ impl Backend for CanvasBackend {
     /// This function is called after the [`CanvasBackend::draw`] function to
     /// actually render the content to the screen.
     fn flush(&mut self) -> IoResult<()> {
-        // Only runs once.
-        if !self.initialized {
+        if is_frame_skipped() {
+            return Ok(());
+        }
+
+        // Re-render at the new resolution if the device pixel ratio changed
+        if self.track_pixel_ratio {
+            let ratio = device_pixel_ratio()?;
+            if ratio != self.canvas.pixel_ratio {
+                self.canvas.set_pixel_ratio(ratio)?;
+                self.redraw = true;
+            }
+        }
+
+        if let Some(graphics_layer) = &self.graphics_layer {
+            let ratio = self.canvas.pixel_ratio;
+            graphics_layer.draw(
+                &self.canvas.inner,
+                (self.cell_width * ratio, self.cell_height * ratio),
+            )?;
+        }
+
+        // Only runs once (and after the resolution or cursor color changed).
+        if !self.initialized || std::mem::take(&mut self.redraw) {
             self.update_grid(true)?;
             self.prev_buffer = self.buffer.clone();
             self.initialized = true;
             return Ok(());
         }
 
-        if self.buffer != self.prev_buffer {
+        let now = performance()?.now();
+        let cursor_moving = self.smooth_cursor.as_mut().is_some_and(|cursor| {
+            cursor.set_target(self.cursor_position, now);
+            cursor.needs_redraw(now)
+        });
+        if cursor_moving {
+            // Keep drawing until the animation is done
+            request_redraw();
+        }
+        let cursor_shape_changed = std::mem::take(&mut self.cursor_shape_changed)
+            || self.drawn_bar_cursor != self.bar_cursor_position();
+        if self.buffer != self.prev_buffer || cursor_moving || cursor_shape_changed {
             self.update_grid(false)?;
         }
 
@@ -477,16 +1392,17 @@ impl Backend for CanvasBackend {
     }
 
     fn hide_cursor(&mut self) -> IoResult<()> {
-        if let Some(pos) = self.cursor_position {
-            let y = pos.y as usize;
-            let x = pos.x as usize;
-            let line = &mut self.buffer[y];
-            if x < line.len() {
-                let style = self.cursor_shape.hide(line[x].style());
-                line[x].set_style(style);
-            }
+        if let Some(pos) = self
+            .cursor_position
+            .filter(|_| self.smooth_cursor.is_none())
+        {
+            let shape = self.cursor_shape;
+            style_cursor_cells(&mut self.buffer[pos.y as usize], pos.x as usize, |style| {
+                shape.hide(style)
+            });
         }
         self.cursor_position = None;
+        record_cursor_position(None);
         Ok(())
     }
 
@@ -503,7 +1419,8 @@ impl Backend for CanvasBackend {
     }
 
     fn clear(&mut self) -> IoResult<()> {
-        self.buffer = get_sized_buffer();
+        self.buffer =
+            get_sized_buffer_from_canvas(&self.canvas.inner, self.cell_width, self.cell_height);
         Ok(())
     }
 
@@ -515,7 +1432,14 @@ impl Backend for CanvasBackend {
     }
 
     fn window_size(&mut self) -> IoResult<WindowSize> {
-        unimplemented!()
+        let size = self.size()?;
+        Ok(WindowSize {
+            columns_rows: size,
+            pixels: Size::new(
+                (size.width as f64 * self.cell_width) as u16,
+                (size.height as f64 * self.cell_height) as u16,
+            ),
+        })
     }
 
     fn get_cursor_position(&mut self) -> IoResult<Position> {
@@ -527,16 +1451,23 @@ impl Backend for CanvasBackend {
 
     fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> IoResult<()> {
         let new_pos = position.into();
-        if let Some(old_pos) = self.cursor_position {
-            let y = old_pos.y as usize;
-            let x = old_pos.x as usize;
-            let line = &mut self.buffer[y];
-            if x < line.len() && old_pos != new_pos {
-                let style = self.cursor_shape.hide(line[x].style());
-                line[x].set_style(style);
-            }
+        if let Some(old_pos) = self
+            .cursor_position
+            .filter(|pos| self.smooth_cursor.is_none() && *pos != new_pos)
+        {
+            let shape = self.cursor_shape;
+            style_cursor_cells(
+                &mut self.buffer[old_pos.y as usize],
+                old_pos.x as usize,
+                |style| shape.hide(style),
+            );
+        }
+        if self.cursor_position != Some(new_pos) {
+            // Keep the cursor visible while it moves
+            self.cursor_blink.reset();
         }
         self.cursor_position = Some(new_pos);
+        record_cursor_position(self.cursor_position);
         Ok(())
     }
 
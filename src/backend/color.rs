@@ -56,6 +56,64 @@ pub(super) fn actual_bg_color(cell: &Cell) -> Color {
     }
 }
 
+/// Returns the bright variant of the given color.
+///
+/// This maps the basic ANSI colors to their bright counterparts, which is how
+/// terminals traditionally render bold text. Other colors are returned as is.
+pub(super) fn bright_color(color: Color) -> Color {
+    match color {
+        Color::Black => Color::DarkGray,
+        Color::Red => Color::LightRed,
+        Color::Green => Color::LightGreen,
+        Color::Yellow => Color::LightYellow,
+        Color::Blue => Color::LightBlue,
+        Color::Magenta => Color::LightMagenta,
+        Color::Cyan => Color::LightCyan,
+        Color::Gray => Color::White,
+        Color::Indexed(code @ 0..=7) => Color::Indexed(code + 8),
+        color => color,
+    }
+}
+
+/// Blends the overlay color over the given color with the given opacity.
+///
+/// The opacity ranges from `0.0` (only `color`) to `1.0` (only `overlay`).
+/// [`Color::Reset`] is resolved to the given fallback RGB value.
+pub(crate) fn blend(color: Color, overlay: Color, opacity: f32, reset_fallback_rgb: u32) -> Color {
+    let color = to_rgb(color, reset_fallback_rgb);
+    let overlay = to_rgb(overlay, reset_fallback_rgb);
+    let opacity = opacity.clamp(0.0, 1.0);
+    let channel = |shift: u32| {
+        let color = ((color >> shift) & 0xff) as f32;
+        let overlay = ((overlay >> shift) & 0xff) as f32;
+        (color + (overlay - color) * opacity).round() as u8
+    };
+    Color::Rgb(channel(16), channel(8), channel(0))
+}
+
+/// Minimum contrast ratio between the foreground and background color of a
+/// cell for the block cursor (which reverses the colors) to be visible.
+pub(super) const MIN_CURSOR_CONTRAST: f64 = 1.5;
+
+/// Returns the contrast ratio of the given RGB values as defined by WCAG.
+///
+/// The ratio ranges from `1.0` (same luminance) to `21.0` (black and white).
+pub(super) fn contrast_ratio(a: u32, b: u32) -> f64 {
+    fn luminance(rgb: u32) -> f64 {
+        let channel = |shift: u32| {
+            let c = ((rgb >> shift) & 0xff) as f64 / 255.0;
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * channel(16) + 0.7152 * channel(8) + 0.0722 * channel(0)
+    }
+    let (a, b) = (luminance(a), luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
 /// Converts an indexed color (0-255) to an RGB value.
 fn indexed_color_to_rgb(index: u8) -> u32 {
     match index {
@@ -162,6 +220,14 @@ mod tests {
             });
     }
 
+    #[test]
+    fn test_bright_color() {
+        assert_eq!(bright_color(Color::Red), Color::LightRed);
+        assert_eq!(bright_color(Color::Indexed(1)), Color::Indexed(9));
+        assert_eq!(bright_color(Color::Indexed(9)), Color::Indexed(9));
+        assert_eq!(bright_color(Color::Rgb(1, 2, 3)), Color::Rgb(1, 2, 3));
+    }
+
     #[test]
     fn test_ansi_to_rgb() {
         // Test some basic ANSI colors
@@ -176,4 +242,25 @@ mod tests {
         assert_eq!(ansi_to_rgb(Color::Indexed(1)), Some((205, 0, 0)));
         assert_eq!(ansi_to_rgb(Color::Indexed(68)), Some((0x5f, 0x87, 0xd7)));
     }
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert_eq!(contrast_ratio(0x000000, 0xffffff), 21.0);
+        assert_eq!(contrast_ratio(0xffffff, 0x000000), 21.0);
+        assert_eq!(contrast_ratio(0x808080, 0x808080), 1.0);
+        assert!(contrast_ratio(0x202020, 0x252525) < MIN_CURSOR_CONTRAST);
+    }
+
+    #[test]
+    fn test_blend() {
+        assert_eq!(
+            blend(Color::White, Color::Black, 0.5, 0),
+            Color::Rgb(128, 128, 128)
+        );
+        assert_eq!(
+            blend(Color::Reset, Color::Black, 0.0, 0xffffff),
+            Color::Rgb(255, 255, 255)
+        );
+        assert_eq!(blend(Color::Red, Color::Black, 1.0, 0), Color::Rgb(0, 0, 0));
+    }
 }
@@ -0,0 +1,112 @@
+use std::borrow::Cow;
+
+use ratatui::buffer::Cell;
+
+/// The notation for rendering control characters.
+///
+/// Control characters (e.g. `ESC` or a tab) are not printable, so they are
+/// either dropped or rendered inconsistently. This makes them visible, which
+/// is useful for debugging and for log viewers that show raw bytes.
+///
+/// Note that Ratatui drops control characters from the text of the widgets,
+/// so they have to be escaped with [`escape_control_chars`] before rendering.
+/// The `show_control_chars` option of the backends handles the cells whose
+/// symbols are set directly instead, using [`ControlChars::Pictures`] since
+/// each control character has to fit in its cell.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ControlChars {
+    /// Caret notation, like `cat -v` (e.g. `^[` for `ESC` and `^I` for a tab).
+    #[default]
+    Caret,
+    /// Unicode control pictures (e.g. `␛` for `ESC` and `␉` for a tab).
+    Pictures,
+}
+
+impl ControlChars {
+    /// Returns the visible representation of the given control character.
+    fn escape(self, c: char) -> Option<Cow<'static, str>> {
+        let code = c as u32;
+        let text = match (self, code) {
+            (ControlChars::Caret, 0x00..=0x1f) => {
+                format!("^{}", char::from_u32(code + 0x40)?).into()
+            }
+            (ControlChars::Caret, 0x7f) => "^?".into(),
+            (ControlChars::Caret, 0x80..=0x9f) => {
+                format!("M-^{}", char::from_u32(code - 0x40)?).into()
+            }
+            (ControlChars::Pictures, 0x00..=0x1f) => {
+                char::from_u32(0x2400 + code)?.to_string().into()
+            }
+            (ControlChars::Pictures, 0x7f) => "␡".into(),
+            (ControlChars::Pictures, 0x80..=0x9f) => "␦".into(),
+            _ => return None,
+        };
+        Some(text)
+    }
+}
+
+/// Renders the control characters in the symbol of the given cell as control
+/// pictures, if any.
+///
+/// OSC escape sequences are left as is, since they are handled by the
+/// backends.
+pub(crate) fn control_pictures(cell: &Cell) -> Option<Cell> {
+    let symbol = cell.symbol();
+    if !symbol.contains(char::is_control) || symbol.contains("\x1b]") {
+        return None;
+    }
+    let mut cell = cell.clone();
+    cell.set_symbol(&escape_control_chars(symbol, ControlChars::Pictures));
+    Some(cell)
+}
+
+/// Returns the given text with the control characters made visible.
+///
+/// ```rust
+/// use ratzilla::backend::control::{escape_control_chars, ControlChars};
+///
+/// assert_eq!(escape_control_chars("\x1b[1mtab:\t", ControlChars::Caret), "^[[1mtab:^I");
+/// assert_eq!(escape_control_chars("\x1b[1m", ControlChars::Pictures), "␛[1m");
+/// ```
+pub fn escape_control_chars(text: &str, notation: ControlChars) -> Cow<'_, str> {
+    if !text.contains(char::is_control) {
+        return Cow::Borrowed(text);
+    }
+    let mut escaped = String::with_capacity(text.len() + 8);
+    for c in text.chars() {
+        match notation.escape(c) {
+            Some(text) => escaped.push_str(&text),
+            None => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_control_chars() {
+        assert_eq!(
+            escape_control_chars("\0\x7f\u{85}", ControlChars::Caret),
+            "^@^?M-^E"
+        );
+        assert_eq!(
+            escape_control_chars("a\nb\r", ControlChars::Pictures),
+            "a␊b␍"
+        );
+        assert!(matches!(
+            escape_control_chars("plain", ControlChars::Caret),
+            Cow::Borrowed("plain")
+        ));
+
+        let mut cell = Cell::default();
+        cell.set_symbol("\t");
+        let mut expected = cell.clone();
+        expected.set_symbol("␉");
+        assert_eq!(control_pictures(&cell), Some(expected));
+        cell.set_symbol("\x1b]8;;https://ratatui.rs\x07a\x1b]8;;\x07");
+        assert_eq!(control_pictures(&cell), None);
+    }
+}
@@ -1,29 +1,162 @@
+use std::time::Duration;
+
 use ratatui::style::Style;
 
+use crate::{
+    backend::utils::{get_document, performance},
+    error::Error,
+    render::is_cursor_blinking,
+};
+
+/// Default interval of the blinking cursor shapes.
+pub const DEFAULT_BLINK_INTERVAL: Duration = Duration::from_millis(530);
+
 /// Supported cursor shapes.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum CursorShape {
     /// A non blinking block cursor shape (█).
     #[default]
     SteadyBlock,
     /// A non blinking underscore cursor shape (_).
     SteadyUnderScore,
+    /// A non blinking vertical bar cursor shape (|).
+    ///
+    /// The bar is drawn on the left edge of the cell. The [`WebGl2Backend`]
+    /// approximates it with an underline, since it can't draw shapes within
+    /// a cell.
+    ///
+    /// [`WebGl2Backend`]: crate::WebGl2Backend
+    SteadyBar,
+    /// A blinking block cursor shape (█).
+    BlinkingBlock,
+    /// A blinking underscore cursor shape (_).
+    BlinkingUnderScore,
+    /// A blinking vertical bar cursor shape (|).
+    BlinkingBar,
+    /// No cursor is shown.
+    ///
+    /// This is useful for display-only applications, as it ensures that the
+    /// cursor position never leaves any artifacts on the screen.
+    None,
 }
 
 impl CursorShape {
+    /// Returns whether the cursor blinks.
+    ///
+    /// Blinking cursors are toggled by the render loop, so they stop blinking
+    /// (and stay visible) while the page is not focused.
+    pub fn is_blinking(&self) -> bool {
+        matches!(
+            self,
+            CursorShape::BlinkingBlock | CursorShape::BlinkingUnderScore | CursorShape::BlinkingBar
+        )
+    }
+
+    /// Returns the non blinking variant of the shape.
+    pub fn steady(&self) -> CursorShape {
+        match self {
+            CursorShape::BlinkingBlock => CursorShape::SteadyBlock,
+            CursorShape::BlinkingUnderScore => CursorShape::SteadyUnderScore,
+            CursorShape::BlinkingBar => CursorShape::SteadyBar,
+            shape => *shape,
+        }
+    }
+
     /// Transforms the given style to hide the cursor.
+    ///
+    /// The bar cursor is drawn by the backends separately, so it doesn't
+    /// change the style.
     pub fn hide(&self, style: Style) -> Style {
-        match self {
+        match self.steady() {
             CursorShape::SteadyBlock => style.not_reversed(),
             CursorShape::SteadyUnderScore => style.not_underlined(),
+            _ => style,
         }
     }
 
     /// Transforms the given style to show the cursor.
     pub fn show(&self, style: Style) -> Style {
-        match self {
+        match self.steady() {
             CursorShape::SteadyBlock => style.reversed(),
             CursorShape::SteadyUnderScore => style.underlined(),
+            _ => style,
+        }
+    }
+}
+
+/// The blinking state of the cursor.
+#[derive(Debug)]
+pub(crate) struct CursorBlink {
+    /// Time between toggling the cursor in milliseconds.
+    interval: f64,
+    /// Time when the cursor was last shown, if it blinks.
+    start: Option<f64>,
+    /// Whether the cursor is currently visible.
+    visible: bool,
+}
+
+impl CursorBlink {
+    /// Constructs a new [`CursorBlink`] with the given interval.
+    pub(crate) fn new(interval: Duration) -> Self {
+        Self {
+            interval: interval.as_secs_f64() * 1000.0,
+            start: None,
+            visible: true,
+        }
+    }
+
+    /// Returns whether the cursor is currently visible.
+    pub(crate) fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Shows the cursor and restarts blinking, e.g. after the cursor moved.
+    pub(crate) fn reset(&mut self) {
+        self.start = None;
+        self.visible = true;
+    }
+
+    /// Updates the visibility of a cursor with the given shape.
+    ///
+    /// The cursor stays visible while the page is not focused or the render
+    /// loop doesn't draw continuously. Returns whether the visibility changed.
+    pub(crate) fn update(&mut self, shape: CursorShape) -> Result<bool, Error> {
+        let visible = if shape.is_blinking() && self.interval > 0.0 && is_cursor_blinking() {
+            let now = performance()?.now();
+            let start = *self.start.get_or_insert(now);
+            let focused = get_document()?.has_focus()?;
+            !focused || (((now - start) / self.interval) as u64).is_multiple_of(2)
+        } else {
+            self.start = None;
+            true
+        };
+        let changed = self.visible != visible;
+        self.visible = visible;
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blinking_shapes() {
+        let style = Style::default();
+        for (blinking, steady) in [
+            (CursorShape::BlinkingBlock, CursorShape::SteadyBlock),
+            (
+                CursorShape::BlinkingUnderScore,
+                CursorShape::SteadyUnderScore,
+            ),
+            (CursorShape::BlinkingBar, CursorShape::SteadyBar),
+        ] {
+            assert!(blinking.is_blinking());
+            assert!(!steady.is_blinking());
+            assert_eq!(blinking.steady(), steady);
+            assert_eq!(blinking.show(style), steady.show(style));
+            assert_eq!(blinking.hide(style), steady.hide(style));
         }
+        assert_eq!(CursorShape::None.steady(), CursorShape::None);
     }
 }
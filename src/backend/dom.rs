@@ -2,28 +2,157 @@ use std::{
     cell::RefCell,
     io::{Error as IoError, Result as IoResult},
     rc::Rc,
+    time::Duration,
 };
 
 use ratatui::{
     backend::WindowSize,
     buffer::Cell,
-    layout::{Position, Size},
+    layout::{Position, Rect, Size},
     prelude::{backend::ClearType, Backend},
+    style::{Color, Modifier},
 };
 use web_sys::{
     wasm_bindgen::{prelude::Closure, JsCast},
-    window, Document, Element, Window,
+    window, Document, Element, HtmlElement, Window,
 };
 
-use crate::{backend::utils::*, error::Error, widgets::hyperlink::HYPERLINK_MODIFIER, CursorShape};
+use crate::{
+    backend::{
+        control::control_pictures,
+        cursor::{CursorBlink, DEFAULT_BLINK_INTERVAL},
+        fallback::AsciiFallback,
+        multi::BackendType,
+        osc,
+        utils::*,
+    },
+    error::Error,
+    render::{
+        add_event_listener, is_frame_skipped, record_backend_type, record_cursor_position,
+        request_redraw, take_cursor_shape, watch_container, watch_focus,
+    },
+    widgets::hyperlink::HYPERLINK_MODIFIER,
+    CssPosition, CursorShape,
+};
+
+/// Attribute that marks the grid element of the [`DomBackend`].
+pub(crate) const GRID_ATTRIBUTE: &str = "data-ratzilla-grid";
+
+/// Attribute that holds the URL of the cells that are part of a detected URL.
+const URL_ATTRIBUTE: &str = "data-ratzilla-url";
+
+/// CSS of the cell with the [`CursorShape::SteadyBar`] cursor.
+const BAR_CURSOR_CSS: &str = "box-shadow: inset 1px 0 0 currentColor;";
+
+/// Overflow behavior of the grid of the [`DomBackend`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GridOverflow {
+    /// Clips the lines that do not fit into the grid.
+    #[default]
+    Clip,
+    /// Shows scrollbars for the lines that do not fit into the grid.
+    ///
+    /// Mouse coordinates are adjusted by the scroll position so that they
+    /// still map to the cells under the cursor.
+    Scroll,
+}
+
+impl GridOverflow {
+    /// Returns the value of the CSS `overflow` property.
+    fn as_str(&self) -> &'static str {
+        match self {
+            GridOverflow::Clip => "hidden",
+            GridOverflow::Scroll => "auto",
+        }
+    }
+}
+
+/// Focus ring of the grid of the [`DomBackend`].
+///
+/// See [`DomBackendOptions::focusable`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum FocusRing {
+    /// A subtle outline that is only shown when the grid is focused with the
+    /// keyboard.
+    #[default]
+    Subtle,
+    /// The default focus ring of the browser.
+    Native,
+    /// A custom CSS outline (e.g. `"2px solid #4ade80"`) that is only shown
+    /// when the grid is focused with the keyboard.
+    Custom(String),
+    /// No focus ring.
+    ///
+    /// Keyboard users can't tell whether the grid is focused then, so consider
+    /// indicating the focus within the application instead.
+    None,
+}
+
+impl FocusRing {
+    /// Returns the CSS rules of the focus ring for the given selector.
+    fn css(&self, selector: &str) -> Option<String> {
+        let outline = match self {
+            FocusRing::Native => return None,
+            FocusRing::Subtle => "1px solid rgba(128, 128, 128, 0.6)",
+            FocusRing::Custom(outline) => outline,
+            FocusRing::None => "none",
+        };
+        Some(format!(
+            "{selector}:focus {{ outline: none; }} \
+             {selector}:focus-visible {{ outline: {outline}; outline-offset: -1px; }}"
+        ))
+    }
+}
 
 /// Options for the [`DomBackend`].
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct DomBackendOptions {
     /// The element ID.
     grid_id: Option<String>,
     /// The cursor shape.
     cursor_shape: CursorShape,
+    /// Blink interval of the cursor.
+    cursor_blink_interval: Option<Duration>,
+    /// Initial position of the cursor.
+    cursor_position: Option<Position>,
+    /// The CSS `position` of the grid.
+    position: Option<CssPosition>,
+    /// The CSS `z-index` of the grid.
+    z_index: Option<i32>,
+    /// URL of the background image.
+    background_image: Option<String>,
+    /// Opacity of the dark overlay on top of the background image.
+    background_dim: f32,
+    /// Whether font ligatures are enabled.
+    ligatures: bool,
+    /// Whether text antialiasing is disabled.
+    disable_antialiasing: bool,
+    /// Overflow behavior of the grid.
+    overflow: GridOverflow,
+    /// Whether the grid is updated off-screen and swapped in.
+    double_buffering: bool,
+    /// Spacing between the cells in pixels.
+    cell_gap: u16,
+    /// Whether pinch gestures change the font size.
+    pinch_zoom: bool,
+    /// Maximum number of cells updated per frame.
+    cell_budget: Option<usize>,
+    /// Whether grapheme clusters split across cells are joined.
+    join_graphemes: bool,
+    /// Whether URLs in the text are clickable.
+    hyperlinks: bool,
+    /// Whether cells can be selected by dragging the mouse.
+    block_selection: bool,
+    /// Whether the grid can be focused.
+    focusable: bool,
+    /// Focus ring of the grid.
+    focus_ring: FocusRing,
+    /// Substitutions for rendering Unicode symbols as ASCII, if enabled.
+    ascii_fallback: Option<AsciiFallback>,
+    /// Notation for rendering control characters, if enabled.
+    control_chars: bool,
+    /// Margin around the grid in cells.
+    margin: u16,
 }
 
 impl DomBackendOptions {
@@ -32,9 +161,262 @@ impl DomBackendOptions {
         Self {
             grid_id,
             cursor_shape,
+            ..Default::default()
         }
     }
 
+    /// Sets the CSS `position` of the grid.
+    ///
+    /// Use this together with [`DomBackendOptions::z_index`] for placing the terminal
+    /// in front of or behind other content of the page.
+    pub fn position(mut self, position: CssPosition) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Surrounds the grid with a margin of the given number of blank cells.
+    ///
+    /// The margin shows the background of the grid, like the cells without a
+    /// background color. See [`DomBackend::grid_offset`] for mapping the
+    /// coordinates of the mouse events to the grid.
+    pub fn margin(mut self, cells: u16) -> Self {
+        self.margin = cells;
+        self
+    }
+
+    /// Sets the CSS `z-index` of the grid.
+    ///
+    /// Note that the `z-index` only has an effect on positioned elements,
+    /// see [`DomBackendOptions::position`].
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = Some(z_index);
+        self
+    }
+
+    /// Sets the background image of the grid.
+    ///
+    /// The image is shown behind the cells that have no background color
+    /// (i.e. [`Color::Reset`]), which are rendered transparent. The image
+    /// covers the whole grid and is centered.
+    ///
+    /// [`Color::Reset`]: ratatui::style::Color::Reset
+    pub fn background_image(mut self, url: &str) -> Self {
+        self.background_image = Some(url.to_string());
+        self
+    }
+
+    /// Sets the opacity of a dark overlay on top of the background image.
+    ///
+    /// This helps with keeping the text legible on busy images. The value
+    /// is clamped between `0.0` (no overlay, the default) and `1.0` (black).
+    pub fn background_dim(mut self, dim: f32) -> Self {
+        self.background_dim = dim.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Enables or disables font ligatures (e.g. of Fira Code).
+    ///
+    /// Ligatures are disabled by default, since they combine multiple cells
+    /// into a single glyph which may misalign the grid.
+    pub fn ligatures(mut self, enabled: bool) -> Self {
+        self.ligatures = enabled;
+        self
+    }
+
+    /// Disables text antialiasing, for crisp pixel or bitmap fonts.
+    ///
+    /// This sets the `font-smooth` and `-webkit-font-smoothing` CSS properties
+    /// of the grid, which are respected by the browsers on macOS. Other
+    /// platforms control font smoothing on the system level.
+    pub fn disable_antialiasing(mut self) -> Self {
+        self.disable_antialiasing = true;
+        self
+    }
+
+    /// Sets the overflow behavior of the grid.
+    ///
+    /// The lines of the grid never wrap (`white-space: pre`). By default, the
+    /// content that does not fit is clipped so that no scrollbars appear, see
+    /// [`GridOverflow`].
+    pub fn overflow(mut self, overflow: GridOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Enables or disables double buffering.
+    ///
+    /// When enabled, each update is applied to detached copies of the changed
+    /// rows which then replace the shown rows, so that a frame is never shown
+    /// partially updated. This comes at the cost of copying the changed rows
+    /// on every update, which is why it is disabled by default.
+    pub fn double_buffering(mut self, enabled: bool) -> Self {
+        self.double_buffering = enabled;
+        self
+    }
+
+    /// Sets the spacing between the cells in pixels, e.g. for a retro LED look.
+    ///
+    /// The gap is accounted for in the size of the grid, but not in the
+    /// coordinates of the mouse events, which are in pixels. Convert them to
+    /// cells with [`DomBackend::cell_size`], which includes the gap:
+    ///
+    /// ```rust no_run
+    /// use ratzilla::{
+    ///     backend::dom::DomBackendOptions, event::CoordinateRounding, ratatui::Terminal,
+    ///     DomBackend, WebRenderer,
+    /// };
+    ///
+    /// let backend = DomBackend::new_with_options(DomBackendOptions::default().cell_gap(2))?;
+    /// let (cell_size, offset) = (backend.cell_size(), backend.grid_offset());
+    /// let terminal = Terminal::new(backend)?;
+    /// terminal.on_mouse_event(move |event| {
+    ///     let (x, y) = event
+    ///         .relative_to(offset)
+    ///         .grid_coords(cell_size, CoordinateRounding::Floor);
+    /// })?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn cell_gap(mut self, gap: u16) -> Self {
+        self.cell_gap = gap;
+        self
+    }
+
+    /// Enables changing the font size with two-finger pinch gestures.
+    ///
+    /// Instead of zooming the whole page, pinching scales the text of the grid
+    /// (between half and triple the original size) and resizes the grid to fit
+    /// the window.
+    ///
+    /// This is only supported by the DOM backend, since the Canvas and WebGL2
+    /// backends have a fixed cell size. Use [`on_pinch`] for handling the
+    /// gestures yourself there.
+    ///
+    /// [`on_pinch`]: crate::utils::on_pinch
+    pub fn pinch_zoom(mut self, enabled: bool) -> Self {
+        self.pinch_zoom = enabled;
+        self
+    }
+
+    /// Joins the grapheme clusters that are split across cells.
+    ///
+    /// Complex emoji such as ZWJ sequences (e.g. 👨‍👩‍👧) or flags consist of
+    /// multiple code points, which end up in separate cells if a widget writes
+    /// them one by one. With this option, such a cluster is rendered as a single
+    /// glyph in its leading cell and the continuation cells are left blank.
+    pub fn join_graphemes(mut self, enabled: bool) -> Self {
+        self.join_graphemes = enabled;
+        self
+    }
+
+    /// Enables hyperlinks in the grid.
+    ///
+    /// Any URL in the rendered text (starting with `http://` or `https://`) is
+    /// detected and opened in a new tab when clicked.
+    pub fn enable_hyperlinks(mut self) -> Self {
+        self.hyperlinks = true;
+        self
+    }
+
+    /// Makes the grid focusable, e.g. for scoping the keyboard input to the
+    /// terminal when it is embedded in a page.
+    ///
+    /// The grid can then be focused by clicking it or with the Tab key. See
+    /// [`DomBackendOptions::focus_ring`] for styling the focus outline.
+    pub fn focusable(mut self, enabled: bool) -> Self {
+        self.focusable = enabled;
+        self
+    }
+
+    /// Sets the focus ring of a focusable grid.
+    ///
+    /// Browsers draw an outline around the focused grid, which looks out of
+    /// place on a terminal. By default, it is replaced by a subtle outline that
+    /// is only shown for keyboard focus, which keeps the focus visible for
+    /// keyboard users. See [`FocusRing`] for the other options.
+    pub fn focus_ring(mut self, focus_ring: FocusRing) -> Self {
+        self.focus_ring = focus_ring;
+        self
+    }
+
+    /// Renders common Unicode symbols as ASCII.
+    ///
+    /// Box-drawing characters are substituted with `+-|`, arrows with `<>^v`
+    /// and so on, which keeps the output readable on targets that can't render
+    /// these glyphs. See [`AsciiFallback`] for the substitutions.
+    pub fn ascii_fallback(mut self, enabled: bool) -> Self {
+        self.ascii_fallback = enabled.then(AsciiFallback::default);
+        self
+    }
+
+    /// Sets a custom substitution table for rendering Unicode symbols as ASCII.
+    ///
+    /// This enables the ASCII fallback, see [`DomBackendOptions::ascii_fallback`].
+    pub fn ascii_fallback_table(mut self, table: AsciiFallback) -> Self {
+        self.ascii_fallback = Some(table);
+        self
+    }
+
+    /// Renders control characters visibly as Unicode control pictures.
+    ///
+    /// This applies to the cells whose symbols contain control characters,
+    /// see [`ControlChars::Pictures`](crate::backend::control::ControlChars::Pictures).
+    pub fn show_control_chars(mut self, enabled: bool) -> Self {
+        self.control_chars = enabled;
+        self
+    }
+
+    /// Enables rectangular (block) selection of cells with the mouse.
+    ///
+    /// The native text selection of the browser does not match the grid of
+    /// cells, so it is replaced by a terminal-style selection instead: dragging
+    /// the mouse highlights a block of cells in reverse video, which is copied
+    /// to the clipboard when the mouse button is released. The selected text is
+    /// also available via [`DomBackend::get_selected_text`].
+    ///
+    /// Like in native terminals, double-clicking selects the word under the
+    /// mouse and triple-clicking selects the whole line.
+    pub fn block_selection(mut self, enabled: bool) -> Self {
+        self.block_selection = enabled;
+        self
+    }
+
+    /// Sets the maximum number of cells that are updated per frame.
+    ///
+    /// Large changes (e.g. switching screens) are then spread across multiple
+    /// frames, with the remaining cells being updated in the following ones.
+    /// This keeps the frame time bounded at the cost of rendering such changes
+    /// progressively. The following frames are requested from the render loop,
+    /// so this also works with [`WebRenderer::draw_web_on_demand`] and
+    /// [`WebRenderer::draw_web_retained`]. By default, all changed cells are
+    /// updated at once.
+    ///
+    /// [`WebRenderer::draw_web_on_demand`]: crate::WebRenderer::draw_web_on_demand
+    /// [`WebRenderer::draw_web_retained`]: crate::WebRenderer::draw_web_retained
+    pub fn cell_budget(mut self, cells: usize) -> Self {
+        self.cell_budget = Some(cells);
+        self
+    }
+
+    /// Shows the cursor at the given position from the start.
+    ///
+    /// The cursor is hidden until the first frame sets its position otherwise.
+    /// Note that ratatui moves or hides the cursor with every drawn frame,
+    /// depending on whether the frame sets a cursor position. Use
+    /// [`CursorShape::None`] to never show a cursor.
+    pub fn cursor_position(mut self, position: impl Into<Position>) -> Self {
+        self.cursor_position = Some(position.into());
+        self
+    }
+
+    /// Sets the interval of toggling a blinking cursor.
+    ///
+    /// This only applies to the blinking [`CursorShape`]s. Defaults to
+    /// [`DEFAULT_BLINK_INTERVAL`].
+    pub fn cursor_blink_interval(mut self, interval: Duration) -> Self {
+        self.cursor_blink_interval = Some(interval);
+        self
+    }
+
     /// Returns the grid ID.
     ///
     /// - If the grid ID is not set, it returns `"grid"`.
@@ -53,12 +435,55 @@ impl DomBackendOptions {
     }
 }
 
+/// State of the block selection of the [`DomBackend`].
+#[derive(Debug, Default)]
+struct BlockSelection {
+    /// Cell where the selection started.
+    start: Option<Position>,
+    /// Cell where the selection ends, which follows the mouse while dragging.
+    end: Option<Position>,
+    /// Whether the mouse button is held down.
+    dragging: bool,
+    /// Whether the selection was finished and is yet to be copied.
+    finished: bool,
+    /// Pending expansion of the selection to a word or line, after a
+    /// double or triple click.
+    expand: Option<SelectionExpansion>,
+}
+
+/// Expansion of the [`BlockSelection`] on multiple clicks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SelectionExpansion {
+    /// Select the word under the mouse (double click).
+    Word,
+    /// Select the line under the mouse (triple click).
+    Line,
+}
+
+impl BlockSelection {
+    /// Returns the selected area, if any.
+    fn area(&self) -> Option<Rect> {
+        let (start, end) = (self.start?, self.end?);
+        let (x, y) = (start.x.min(end.x), start.y.min(end.y));
+        Some(Rect::new(
+            x,
+            y,
+            start.x.max(end.x) - x + 1,
+            start.y.max(end.y) - y + 1,
+        ))
+    }
+}
+
 /// DOM backend.
 ///
 /// This backend uses the DOM to render the content to the screen.
 ///
 /// In other words, it transforms the [`Cell`]s into `<span>`s which are then
 /// appended to a `<pre>` element.
+///
+/// Cells containing [OSC 8] hyperlinks are rendered as `<a>` elements.
+///
+/// [OSC 8]: https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda
 #[derive(Debug)]
 pub struct DomBackend {
     /// Whether the backend has been initialized.
@@ -81,6 +506,20 @@ pub struct DomBackend {
     options: DomBackendOptions,
     /// Cursor position.
     cursor_position: Option<Position>,
+    /// Position of the last drawn bar cursor.
+    drawn_bar_cursor: Option<Position>,
+    /// Blinking state of the cursor.
+    cursor_blink: CursorBlink,
+    /// Scale of the font size, changed by pinch gestures.
+    font_scale: Rc<std::cell::Cell<f64>>,
+    /// Block selection, updated by the mouse listeners.
+    selection: Rc<RefCell<BlockSelection>>,
+    /// The area that is currently highlighted as selected.
+    highlighted: Option<Rect>,
+    /// Color of the cursor, if set.
+    cursor_color: Option<Color>,
+    /// Whether the cursor color changed since the last flush.
+    cursor_color_changed: bool,
 }
 
 impl DomBackend {
@@ -97,12 +536,49 @@ impl DomBackend {
         ))
     }
 
+    /// Returns the size of a cell in pixels, including the gap between cells.
+    ///
+    /// This can be used for converting the mouse coordinates to cells, see
+    /// [`MouseEvent::grid_coords`].
+    ///
+    /// [`MouseEvent::grid_coords`]: crate::event::MouseEvent::grid_coords
+    pub fn cell_size(&self) -> (f64, f64) {
+        let (width, height) = DOM_CELL_SIZE;
+        let (scale, gap) = (self.font_scale.get(), self.options.cell_gap as f64);
+        (width as f64 * scale + gap, height as f64 * scale + gap)
+    }
+
+    /// Returns the offset of the grid in pixels, i.e. the size of its margin.
+    ///
+    /// This should be subtracted from the mouse coordinates to get the position
+    /// within the grid, see [`MouseEvent::relative_to`].
+    ///
+    /// [`MouseEvent::relative_to`]: crate::event::MouseEvent::relative_to
+    pub fn grid_offset(&self) -> (u32, u32) {
+        let (cell_width, cell_height) = self.cell_size();
+        (
+            (self.options.margin as f64 * cell_width) as u32,
+            (self.options.margin as f64 * cell_height) as u32,
+        )
+    }
+
     /// Set the [`CursorShape`].
     pub fn set_cursor_shape(mut self, shape: CursorShape) -> Self {
         self.options.cursor_shape = shape;
         self
     }
 
+    /// Sets the color of the cursor.
+    ///
+    /// This is the background of the block cursor and the color of the bar and
+    /// underscore cursors. [`Color::Reset`] restores the default cursor, which
+    /// reverses the colors of the cell.
+    pub fn set_cursor_color(&mut self, color: Color) {
+        self.cursor_color = (color != Color::Reset).then_some(color);
+        self.cursor_color_changed = true;
+        request_redraw();
+    }
+
     /// Constructs a new [`DomBackend`] with the given options.
     pub fn new_with_options(options: DomBackendOptions) -> Result<Self, Error> {
         let window = window().ok_or(Error::UnableToRetrieveWindow)?;
@@ -114,37 +590,506 @@ impl DomBackend {
             cells: vec![],
             grid: document.create_element("div")?,
             grid_parent: get_element_by_id_or_body(options.grid_id.as_ref())?,
+            cursor_blink: CursorBlink::new(
+                options
+                    .cursor_blink_interval
+                    .unwrap_or(DEFAULT_BLINK_INTERVAL),
+            ),
             options,
             window,
             document,
             cursor_position: None,
+            drawn_bar_cursor: None,
+            font_scale: Rc::new(std::cell::Cell::new(1.0)),
+            selection: Rc::new(RefCell::new(BlockSelection::default())),
+            highlighted: None,
+            cursor_color: None,
+            cursor_color_changed: false,
         };
-        backend.add_on_resize_listener();
+        watch_container(&backend.grid_parent);
+        backend.add_on_resize_listener()?;
+        if backend.options.pinch_zoom {
+            backend.add_pinch_zoom_listener()?;
+        }
+        if backend.options.hyperlinks {
+            backend.add_hyperlink_listeners()?;
+        }
+        if backend.options.block_selection {
+            backend.add_selection_listeners()?;
+        }
         backend.reset_grid()?;
+        backend.cursor_position = backend.options.cursor_position.filter(|pos| {
+            backend
+                .buffer
+                .get(pos.y as usize)
+                .is_some_and(|line| (pos.x as usize) < line.len())
+        });
+        record_cursor_position(backend.cursor_position);
+        record_backend_type(BackendType::Dom);
         Ok(backend)
     }
 
     /// Add a listener to the window resize event.
-    fn add_on_resize_listener(&mut self) {
+    fn add_on_resize_listener(&mut self) -> Result<(), Error> {
         let initialized = self.initialized.clone();
         let closure = Closure::<dyn FnMut(_)>::new(move |_: web_sys::Event| {
             initialized.replace(false);
         });
-        self.window
-            .set_onresize(Some(closure.as_ref().unchecked_ref()));
-        closure.forget();
+        add_event_listener(&self.window, "resize", &Rc::new(closure))
+    }
+
+    /// Add a listener for pinch gestures that change the font size.
+    fn add_pinch_zoom_listener(&mut self) -> Result<(), Error> {
+        let initialized = self.initialized.clone();
+        let font_scale = self.font_scale.clone();
+        crate::utils::on_pinch(move |factor| {
+            let scale = (font_scale.get() * factor).clamp(0.5, 3.0);
+            if scale != font_scale.get() {
+                font_scale.set(scale);
+                // Re-create the grid with the new cell size
+                initialized.replace(false);
+            }
+        })
+    }
+
+    /// Add the listeners for opening the detected URLs on click.
+    fn add_hyperlink_listeners(&mut self) -> Result<(), Error> {
+        /// Returns the URL of the cell that is the target of the given event.
+        fn target_url(event: &web_sys::Event) -> Option<String> {
+            event
+                .target()?
+                .dyn_into::<Element>()
+                .ok()?
+                .closest(&format!("[{URL_ATTRIBUTE}]"))
+                .ok()??
+                .get_attribute(URL_ATTRIBUTE)
+        }
+
+        let on_click = Closure::<dyn FnMut(_)>::new(move |event: web_sys::Event| {
+            if let Some(url) = target_url(&event) {
+                let _ = crate::utils::open_url(&url, true);
+            }
+        });
+        add_event_listener(&self.grid_parent, "click", &Rc::new(on_click))?;
+
+        let grid_parent = self.grid_parent.clone();
+        let on_mouse_over = Closure::<dyn FnMut(_)>::new(move |event: web_sys::Event| {
+            if let Some(style) = grid_parent.dyn_ref::<HtmlElement>().map(|e| e.style()) {
+                let cursor = if target_url(&event).is_some() {
+                    "pointer"
+                } else {
+                    ""
+                };
+                let _ = style.set_property("cursor", cursor);
+            }
+        });
+        add_event_listener(&self.grid_parent, "mouseover", &Rc::new(on_mouse_over))
+    }
+
+    /// Add the mouse listeners for selecting blocks of cells.
+    fn add_selection_listeners(&mut self) -> Result<(), Error> {
+        let font_scale = self.font_scale.clone();
+        let gap = self.options.cell_gap as f64;
+        // Returns the cell of the grid below the mouse cursor
+        let cell_at = move |event: &web_sys::MouseEvent| {
+            let grid = event
+                .target()?
+                .dyn_into::<Element>()
+                .ok()?
+                .closest(&format!("[{GRID_ATTRIBUTE}]"))
+                .ok()??;
+            // The margin is drawn as a border
+            let rect = grid.get_bounding_client_rect();
+            let left = rect.left() + grid.client_left() as f64;
+            let top = rect.top() + grid.client_top() as f64;
+            let scale = font_scale.get();
+            let x = (event.client_x() as f64 - left + grid.scroll_left() as f64)
+                / (DOM_CELL_SIZE.0 as f64 * scale + gap);
+            let y = (event.client_y() as f64 - top + grid.scroll_top() as f64)
+                / (DOM_CELL_SIZE.1 as f64 * scale + gap);
+            Some(Position::new(x.max(0.0) as u16, y.max(0.0) as u16))
+        };
+
+        let selection = self.selection.clone();
+        let on_mouse = Closure::<dyn FnMut(_)>::new(move |event: web_sys::Event| {
+            let event_type = event.type_();
+            let event = event.unchecked_into::<web_sys::MouseEvent>();
+            let mut selection = selection.borrow_mut();
+            match event_type.as_str() {
+                "mousedown" if event.button() == 0 => {
+                    let Some(cell) = cell_at(&event) else {
+                        return;
+                    };
+                    selection.start = Some(cell);
+                    selection.end = Some(cell);
+                    // The expansion is resolved on the next frame, using the buffer
+                    selection.expand = match event.detail() {
+                        2 => Some(SelectionExpansion::Word),
+                        3 => Some(SelectionExpansion::Line),
+                        _ => None,
+                    };
+                    selection.dragging = selection.expand.is_none();
+                }
+                "mousemove" if selection.dragging => {
+                    if let Some(cell) = cell_at(&event) {
+                        selection.end = Some(cell);
+                    }
+                }
+                "mouseup" if selection.dragging => {
+                    selection.dragging = false;
+                    // A click without dragging clears the selection
+                    if selection.start == selection.end {
+                        selection.start = None;
+                        selection.end = None;
+                    } else {
+                        selection.finished = true;
+                    }
+                }
+                _ => return,
+            }
+            // The selection is drawn with the next frame
+            request_redraw();
+        });
+        let on_mouse = Rc::new(on_mouse);
+        add_event_listener(&self.grid_parent, "mousedown", &on_mouse)?;
+        add_event_listener(&self.grid_parent, "mousemove", &on_mouse)?;
+        add_event_listener(&self.document, "mouseup", &on_mouse)
+    }
+
+    /// Returns the text of the cells that are selected with the mouse.
+    ///
+    /// The selected block of cells is returned line by line, without trailing
+    /// whitespace. Returns `None` if there is no selection.
+    ///
+    /// See [`DomBackendOptions::block_selection`].
+    pub fn get_selected_text(&self) -> Option<String> {
+        let area = self.selection.borrow().area()?;
+        let lines = self
+            .buffer
+            .iter()
+            .skip(area.y as usize)
+            .take(area.height as usize)
+            .map(|line| {
+                line.iter()
+                    .skip(area.x as usize)
+                    .take(area.width as usize)
+                    .map(|cell| osc::visible_text(cell.symbol()))
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        Some(lines.join("\n"))
+    }
+
+    /// Highlights the selected cells and copies a finished selection to the
+    /// clipboard.
+    fn update_selection(&mut self) -> Result<(), Error> {
+        self.expand_selection();
+        let area = self.selection.borrow().area();
+        if area != self.highlighted {
+            let previous = std::mem::replace(&mut self.highlighted, area);
+            let bounds = Rect::new(
+                0,
+                0,
+                self.buffer.first().map_or(0, |line| line.len()) as u16,
+                self.buffer.len() as u16,
+            );
+            for rect in [previous, area].into_iter().flatten() {
+                for position in rect.intersection(bounds).positions() {
+                    let (x, y) = (position.x as usize, position.y as usize);
+                    let cell = &self.buffer[y][x];
+                    self.cells[y * self.buffer[0].len() + x]
+                        .set_attribute("style", &self.get_cell_css(x, y, cell))?;
+                }
+            }
+        }
+        if std::mem::take(&mut self.selection.borrow_mut().finished) {
+            if let Some(text) = self.get_selected_text() {
+                let _ = self.window.navigator().clipboard().write_text(&text);
+            }
+        }
+        Ok(())
+    }
+
+    /// Expands the selection to the word or line under the mouse after a
+    /// double or triple click.
+    fn expand_selection(&mut self) {
+        let mut selection = self.selection.borrow_mut();
+        let (Some(expand), Some(start)) = (selection.expand.take(), selection.start) else {
+            return;
+        };
+        let Some(line) = self.buffer.get(start.y as usize) else {
+            return;
+        };
+        let range = match expand {
+            SelectionExpansion::Word => word_at(line, start.x as usize),
+            SelectionExpansion::Line => Some(0..line.len()),
+        };
+        match range.filter(|range| !range.is_empty()) {
+            Some(range) => {
+                selection.start = Some(Position::new(range.start as u16, start.y));
+                selection.end = Some(Position::new(range.end as u16 - 1, start.y));
+                selection.finished = true;
+            }
+            None => {
+                selection.start = None;
+                selection.end = None;
+            }
+        }
+    }
+
+    /// Returns the CSS style of the cell at the given position.
+    ///
+    /// Selected cells are shown in reverse video.
+    fn get_cell_css(&self, x: usize, y: usize, cell: &Cell) -> String {
+        let position = Position::new(x as u16, y as u16);
+        let selected = self.highlighted.is_some_and(|area| area.contains(position));
+        let mut css = if selected {
+            let mut cell = cell.clone();
+            cell.modifier.toggle(Modifier::REVERSED);
+            get_cell_style_as_css(&cell)
+        } else {
+            get_cell_style_as_css(cell)
+        };
+        if self.bar_cursor_position() == Some(position) {
+            // Unlike a border, the inset shadow doesn't change the size of the cell
+            css.push_str(BAR_CURSOR_CSS);
+        }
+        if let Some(cursor_css) = self.cursor_color_css(position) {
+            css.push_str(&cursor_css);
+        }
+        css
+    }
+
+    /// Returns the CSS of the cursor color for the cell at the given position.
+    ///
+    /// Returns `None` if the cell is not covered by a visible cursor or no
+    /// cursor color is set. [`CursorShape::None`] is never visible.
+    fn cursor_color_css(&self, position: Position) -> Option<String> {
+        let color = get_canvas_color(self.cursor_color?, Color::White);
+        let cursor = self.cursor_position.filter(|_| {
+            self.options.cursor_shape != CursorShape::None && self.cursor_blink.is_visible()
+        })?;
+        let line = self.buffer.get(cursor.y as usize)?;
+        if cursor.y != position.y
+            || !cursor_cells(line, cursor.x as usize).contains(&(position.x as usize))
+        {
+            return None;
+        }
+        match self.options.cursor_shape.steady() {
+            CursorShape::SteadyBlock => Some(format!("background-color: {color};")),
+            CursorShape::SteadyUnderScore => Some(format!("text-decoration-color: {color};")),
+            CursorShape::SteadyBar if cursor == position => {
+                Some(format!("box-shadow: inset 1px 0 0 {color};"))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the position of the bar cursor, if it is shown.
+    fn bar_cursor_position(&self) -> Option<Position> {
+        self.cursor_position.filter(|_| {
+            self.options.cursor_shape.steady() == CursorShape::SteadyBar
+                && self.cursor_blink.is_visible()
+        })
+    }
+
+    /// Updates the cells of the bar cursor if it moved since the last flush.
+    ///
+    /// The bar cursor doesn't change the style of the cells, so the cells
+    /// wouldn't be updated otherwise.
+    fn update_bar_cursor(&mut self) -> Result<(), Error> {
+        let position = self.bar_cursor_position();
+        if position == self.drawn_bar_cursor {
+            return Ok(());
+        }
+        for pos in [self.drawn_bar_cursor, position].into_iter().flatten() {
+            self.restyle_cell(pos.x as usize, pos.y as usize)?;
+        }
+        self.drawn_bar_cursor = position;
+        Ok(())
+    }
+
+    /// Updates the cells of the cursor if its color changed since the last flush.
+    fn update_cursor_color(&mut self) -> Result<(), Error> {
+        if !std::mem::take(&mut self.cursor_color_changed) {
+            return Ok(());
+        }
+        let Some(pos) = self.cursor_position else {
+            return Ok(());
+        };
+        let y = pos.y as usize;
+        let Some(line) = self.buffer.get(y) else {
+            return Ok(());
+        };
+        for x in cursor_cells(line, pos.x as usize) {
+            self.restyle_cell(x, y)?;
+        }
+        Ok(())
+    }
+
+    /// Sets the style of the cell element at the given position.
+    fn restyle_cell(&self, x: usize, y: usize) -> Result<(), Error> {
+        let width = self.buffer[0].len();
+        if let (Some(cell), Some(elem)) = (
+            self.buffer.get(y).and_then(|line| line.get(x)),
+            self.cells.get(y * width + x),
+        ) {
+            elem.set_attribute("style", &self.get_cell_css(x, y, cell))?;
+        }
+        Ok(())
     }
 
     /// Reset the grid and clear the cells.
     fn reset_grid(&mut self) -> Result<(), Error> {
         self.grid = self.document.create_element("div")?;
         self.grid.set_attribute("id", &self.options.grid_id())?;
+        self.grid.set_attribute(GRID_ATTRIBUTE, "")?;
+        set_stacking(&self.grid, self.options.position, self.options.z_index)?;
+        self.set_background_image()?;
+        self.set_ligatures()?;
+        if self.options.disable_antialiasing {
+            disable_text_antialiasing(&self.grid_style()?)?;
+        }
+        self.set_overflow()?;
+        self.set_cell_gap()?;
+        self.set_font_scale()?;
+        self.set_margin()?;
+        if self.options.block_selection {
+            self.grid_style()?.set_property("user-select", "none")?;
+        }
+        if self.options.focusable {
+            self.grid.set_attribute("tabindex", "0")?;
+            self.set_focus_ring()?;
+        }
+        watch_focus(&self.grid)?;
         self.cells.clear();
-        self.buffer = get_sized_buffer();
+        self.highlighted = None;
+        self.drawn_bar_cursor = None;
+        self.buffer = self.sized_buffer();
         self.prev_buffer = self.buffer.clone();
         Ok(())
     }
 
+    /// Adds the style sheet for the focus ring of the grid.
+    fn set_focus_ring(&self) -> Result<(), Error> {
+        let grid_id = self.options.grid_id();
+        let Some(css) = self.options.focus_ring.css(&format!("#{grid_id}")) else {
+            return Ok(());
+        };
+        let style_id = format!("{grid_id}-focus-ring");
+        let style = match self.document.get_element_by_id(&style_id) {
+            Some(style) => style,
+            None => {
+                let style = self.document.create_element("style")?;
+                style.set_id(&style_id);
+                self.document
+                    .head()
+                    .ok_or(Error::UnableToRetrieveComponent("head"))?
+                    .append_child(&style)?;
+                style
+            }
+        };
+        style.set_text_content(Some(&css));
+        Ok(())
+    }
+
+    /// Enables or disables the font ligatures of the grid.
+    fn set_ligatures(&self) -> Result<(), Error> {
+        let value = if self.options.ligatures {
+            "normal"
+        } else {
+            "none"
+        };
+        self.grid_style()?
+            .set_property("font-variant-ligatures", value)?;
+        Ok(())
+    }
+
+    /// Returns a buffer that fits into the window, leaving room for the margin.
+    fn sized_buffer(&self) -> Vec<Vec<Cell>> {
+        let margin = self.options.margin as usize * 2;
+        let mut buffer = get_sized_buffer(self.options.cell_gap, self.font_scale.get());
+        buffer.truncate(buffer.len().saturating_sub(margin).max(1));
+        for line in &mut buffer {
+            line.truncate(line.len().saturating_sub(margin).max(1));
+        }
+        buffer
+    }
+
+    /// Sets the margin around the grid, if configured.
+    fn set_margin(&self) -> Result<(), Error> {
+        if self.options.margin == 0 {
+            return Ok(());
+        }
+        set_margin(&self.grid_style()?, self.grid_offset(), "transparent")
+    }
+
+    /// Sets the white-space and overflow behavior of the grid.
+    fn set_overflow(&self) -> Result<(), Error> {
+        let style = self.grid_style()?;
+        style.set_property("white-space", "pre")?;
+        style.set_property("overflow", self.options.overflow.as_str())?;
+        Ok(())
+    }
+
+    /// Sets the spacing between the cells of the grid, if configured.
+    fn set_cell_gap(&self) -> Result<(), Error> {
+        let gap = self.options.cell_gap;
+        if gap == 0 {
+            return Ok(());
+        }
+        let style = self.grid_style()?;
+        // Each cell is a single character, so the letter spacing is the gap between cells
+        style.set_property("letter-spacing", &format!("{gap}px"))?;
+        style.set_property("display", "flex")?;
+        style.set_property("flex-direction", "column")?;
+        style.set_property("row-gap", &format!("{gap}px"))?;
+        Ok(())
+    }
+
+    /// Sets the font size of the grid for pinch zooming, if enabled.
+    fn set_font_scale(&self) -> Result<(), Error> {
+        if !self.options.pinch_zoom {
+            return Ok(());
+        }
+        let style = self.grid_style()?;
+        // Prevent the browser from zooming the page instead
+        style.set_property("touch-action", "pan-x pan-y")?;
+        style.set_property("font-size", &format!("{}em", self.font_scale.get()))?;
+        Ok(())
+    }
+
+    /// Returns the inline style of the grid.
+    fn grid_style(&self) -> Result<web_sys::CssStyleDeclaration, Error> {
+        Ok(self
+            .grid
+            .dyn_ref::<HtmlElement>()
+            .ok_or(Error::UnableToRetrieveComponent("HtmlElement"))?
+            .style())
+    }
+
+    /// Sets the background image of the grid, if configured.
+    fn set_background_image(&self) -> Result<(), Error> {
+        let Some(url) = &self.options.background_image else {
+            return Ok(());
+        };
+        let style = self.grid_style()?;
+        // The dim overlay is a single color gradient that is drawn on top of the image
+        let dim = self.options.background_dim;
+        style.set_property(
+            "background-image",
+            &format!(
+                "linear-gradient(rgba(0, 0, 0, {dim}), rgba(0, 0, 0, {dim})), url(\"{}\")",
+                url.replace('"', "%22")
+            ),
+        )?;
+        style.set_property("background-size", "cover")?;
+        style.set_property("background-position", "center")?;
+        Ok(())
+    }
+
     /// Pre-render the content to the screen.
     ///
     /// This function is called from [`flush`] once to render the initial
@@ -189,26 +1134,151 @@ impl DomBackend {
             // Append the <pre> to the grid
             self.grid.append_child(&pre)?;
         }
+        if self.options.hyperlinks {
+            for y in 0..self.buffer.len() {
+                self.update_links(y)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Marks the cells of the URLs in the given line with their URL.
+    fn update_links(&self, y: usize) -> Result<(), Error> {
+        let line = &self.buffer[y];
+        let links = detect_urls(line);
+        let mut urls = vec![None; line.len()];
+        for (range, url) in &links {
+            urls[range.clone()].fill(Some(url.as_str()));
+        }
+        for (x, url) in urls.into_iter().enumerate() {
+            // Cells of the Hyperlink widget are already rendered as anchors
+            if line[x].modifier.contains(HYPERLINK_MODIFIER) {
+                continue;
+            }
+            let elem = &self.cells[y * self.buffer[0].len() + x];
+            match url {
+                Some(url) => elem.set_attribute(URL_ATTRIBUTE, url)?,
+                None => elem.remove_attribute(URL_ATTRIBUTE)?,
+            }
+        }
         Ok(())
     }
 
     /// Compare the current buffer to the previous buffer and updates the grid
     /// accordingly.
-    fn update_grid(&mut self) -> Result<(), Error> {
-        for (y, line) in self.buffer.iter().enumerate() {
+    ///
+    /// Returns `false` if the cell budget was exhausted before all the changed
+    /// cells were updated. In that case, the updated cells are recorded in the
+    /// previous buffer so that the remaining ones are updated in the next frame.
+    fn update_grid(&mut self) -> Result<bool, Error> {
+        let mut budget = self.options.cell_budget.unwrap_or(usize::MAX);
+        let mut complete = true;
+        let mut updated = Vec::new();
+        'lines: for (y, line) in self.buffer.iter().enumerate() {
             for (x, cell) in line.iter().enumerate() {
-                if cell.modifier.contains(HYPERLINK_MODIFIER) {
+                if cell.modifier.contains(HYPERLINK_MODIFIER) || cell == &self.prev_buffer[y][x] {
                     continue;
                 }
-                if cell != &self.prev_buffer[y][x] {
-                    let elem = self.cells[y * self.buffer[0].len() + x].clone();
-                    elem.set_inner_html(cell.symbol());
-                    elem.set_attribute("style", &get_cell_style_as_css(cell))?;
+                if budget == 0 {
+                    complete = false;
+                    break 'lines;
                 }
+                budget -= 1;
+                updated.push((x, y));
             }
         }
+        if updated.is_empty() {
+            return Ok(complete);
+        }
+
+        let result = self.update_cells(&updated);
+        if result.is_err() && self.options.double_buffering {
+            // Follow the shown grid again, since not all rows may be swapped in
+            let rows = self.grid.children();
+            self.cells = (0..rows.length())
+                .filter_map(|y| rows.item(y))
+                .flat_map(|row| row_cells(&row))
+                .collect();
+        }
+        result?;
+        if self.options.cell_budget.is_some() {
+            for (x, y) in updated {
+                self.prev_buffer[y][x] = self.buffer[y][x].clone();
+            }
+        }
+        Ok(complete)
+    }
+
+    /// Updates the elements of the given cells.
+    ///
+    /// With double buffering, the rows of the cells are updated off-screen
+    /// and then replace the shown rows.
+    fn update_cells(&mut self, cells: &[(usize, usize)]) -> Result<(), Error> {
+        let width = self.buffer[0].len();
+        let mut rows: Vec<usize> = cells.iter().map(|&(_, y)| y).collect();
+        rows.dedup();
+        let mut live_rows = Vec::new();
+        if self.options.double_buffering {
+            for &y in &rows {
+                live_rows.push(self.detach_row(y, width)?);
+            }
+        }
+        for &(x, y) in cells {
+            let cell = &self.buffer[y][x];
+            let elem = &self.cells[y * width + x];
+            set_cell_content(&self.document, elem, cell)?;
+            elem.set_attribute("style", &self.get_cell_css(x, y, cell))?;
+        }
+        if self.options.hyperlinks {
+            for &y in &rows {
+                self.update_links(y)?;
+            }
+        }
+        for (live_row, row) in live_rows {
+            live_row.replace_with_with_node_1(&row)?;
+        }
         Ok(())
     }
+
+    /// Switches the cells of the given row to a detached copy of the row for
+    /// updating them off-screen.
+    ///
+    /// Only the changed rows are copied, so the cost scales with the size of
+    /// the update instead of the grid. Returns the row that is still shown and
+    /// the copy that is to replace it.
+    fn detach_row(&mut self, y: usize, width: usize) -> Result<(Element, Element), Error> {
+        let live_row = self
+            .grid
+            .children()
+            .item(y as u32)
+            .ok_or(Error::UnableToRetrieveComponent("pre"))?;
+        let row = live_row
+            .clone_node_with_deep(true)?
+            .dyn_into::<Element>()
+            .map_err(|_| Error::UnableToRetrieveComponent("Element"))?;
+        self.cells
+            .splice(y * width..(y + 1) * width, row_cells(&row));
+        Ok((live_row, row))
+    }
+}
+
+/// Returns the cell elements of a row of the grid.
+///
+/// The cells of the [`Hyperlink`] widgets are wrapped in an anchor.
+///
+/// [`Hyperlink`]: crate::widgets::Hyperlink
+fn row_cells(row: &Element) -> Vec<Element> {
+    let mut cells = Vec::new();
+    let children = row.children();
+    for child in (0..children.length()).filter_map(|index| children.item(index)) {
+        if child.tag_name().eq_ignore_ascii_case("a") {
+            let link_cells = child.children();
+            cells.extend((0..link_cells.length()).filter_map(|index| link_cells.item(index)));
+        } else {
+            cells.push(child);
+        }
+    }
+    cells
 }
 
 impl Backend for DomBackend {
@@ -219,6 +1289,11 @@ impl Backend for DomBackend {
     where
         I: Iterator<Item = (u16, u16, &'a Cell)>,
     {
+        // Nothing changed since the previous frame
+        if is_frame_skipped() {
+            return Ok(());
+        }
+
         if !*self.initialized.borrow() {
             // Only runs on resize event.
             if self
@@ -231,7 +1306,19 @@ impl Backend for DomBackend {
             }
         }
 
+        // Switch to the requested cursor shape, hiding the current cursor first
+        if let Some(shape) = take_cursor_shape() {
+            if let Some(pos) = self.cursor_position {
+                let shape = self.options.cursor_shape;
+                style_cursor_cells(&mut self.buffer[pos.y as usize], pos.x as usize, |style| {
+                    shape.hide(style)
+                });
+            }
+            self.options.cursor_shape = shape;
+        }
+
         // Update the cells with new content
+        let mut updated_lines = Vec::new();
         for (x, y, cell) in content {
             let y = y as usize;
             let x = x as usize;
@@ -241,22 +1328,51 @@ impl Backend for DomBackend {
                     std::iter::repeat_with(Cell::default).take(x.saturating_sub(line.len())),
                 );
                 if x < line.len() {
-                    line[x] = cell.clone();
+                    // Keep the sequences in the buffer for rendering OSC 8 hyperlinks
+                    osc::apply_sequences(cell.symbol());
+                    line[x] = match &self.options.ascii_fallback {
+                        Some(fallback) => fallback.apply(cell).unwrap_or_else(|| cell.clone()),
+                        None => cell.clone(),
+                    };
+                    if let Some(cell) = self
+                        .options
+                        .control_chars
+                        .then(|| control_pictures(&line[x]))
+                        .flatten()
+                    {
+                        line[x] = cell;
+                    }
+                    if updated_lines.last() != Some(&y) {
+                        updated_lines.push(y);
+                    }
                 }
             }
         }
-
-        // Draw the cursor if set
-        if let Some(pos) = self.cursor_position {
-            let y = pos.y as usize;
-            let x = pos.x as usize;
-            let line = &mut self.buffer[y];
-            if x < line.len() {
-                let cursor_style = self.options.cursor_shape().show(line[x].style());
-                line[x].set_style(cursor_style);
+        for y in updated_lines {
+            attach_zero_width_chars(&mut self.buffer[y]);
+            if self.options.join_graphemes {
+                join_grapheme_clusters(&mut self.buffer[y]);
             }
         }
 
+        // Draw the cursor if set, unless it has no shape or a blinking cursor
+        // is off at the moment
+        self.cursor_blink.update(self.options.cursor_shape)?;
+        if let Some(pos) = self
+            .cursor_position
+            .filter(|_| self.options.cursor_shape != CursorShape::None)
+        {
+            let shape = self.options.cursor_shape;
+            let visible = self.cursor_blink.is_visible();
+            style_cursor_cells(&mut self.buffer[pos.y as usize], pos.x as usize, |style| {
+                if visible {
+                    shape.show(style)
+                } else {
+                    shape.hide(style)
+                }
+            });
+        }
+
         Ok(())
     }
 
@@ -265,6 +1381,10 @@ impl Backend for DomBackend {
     /// This function is called after the [`DomBackend::draw`] function to
     /// actually render the content to the screen.
     fn flush(&mut self) -> IoResult<()> {
+        if is_frame_skipped() {
+            return Ok(());
+        }
+
         if !*self.initialized.borrow() {
             self.initialized.replace(true);
             self.grid_parent
@@ -275,24 +1395,30 @@ impl Backend for DomBackend {
             self.prev_buffer = self.buffer.clone();
         }
         // Check if the buffer has changed since the last render and update the grid
-        if self.buffer != self.prev_buffer {
-            self.update_grid()?;
+        let complete = self.buffer == self.prev_buffer || self.update_grid()?;
+        if self.options.block_selection {
+            self.update_selection()?;
+        }
+        self.update_bar_cursor()?;
+        self.update_cursor_color()?;
+        // Otherwise, keep the remaining changes for the next frame
+        if complete {
+            self.prev_buffer = self.buffer.clone();
+        } else {
+            request_redraw();
         }
-        self.prev_buffer = self.buffer.clone();
         Ok(())
     }
 
     fn hide_cursor(&mut self) -> IoResult<()> {
         if let Some(pos) = self.cursor_position {
-            let y = pos.y as usize;
-            let x = pos.x as usize;
-            let line = &mut self.buffer[y];
-            if x < line.len() {
-                let style = self.options.cursor_shape.hide(line[x].style());
-                line[x].set_style(style);
-            }
+            let shape = self.options.cursor_shape;
+            style_cursor_cells(&mut self.buffer[pos.y as usize], pos.x as usize, |style| {
+                shape.hide(style)
+            });
         }
         self.cursor_position = None;
+        record_cursor_position(None);
         Ok(())
     }
 
@@ -309,7 +1435,7 @@ impl Backend for DomBackend {
     }
 
     fn clear(&mut self) -> IoResult<()> {
-        self.buffer = get_sized_buffer();
+        self.buffer = self.sized_buffer();
         Ok(())
     }
 
@@ -321,7 +1447,15 @@ impl Backend for DomBackend {
     }
 
     fn window_size(&mut self) -> IoResult<WindowSize> {
-        unimplemented!()
+        let size = self.size()?;
+        let (cell_width, cell_height) = self.cell_size();
+        Ok(WindowSize {
+            columns_rows: size,
+            pixels: Size::new(
+                (size.width as f64 * cell_width) as u16,
+                (size.height as f64 * cell_height) as u16,
+            ),
+        })
     }
 
     fn get_cursor_position(&mut self) -> IoResult<Position> {
@@ -333,16 +1467,18 @@ impl Backend for DomBackend {
 
     fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> IoResult<()> {
         let new_pos = position.into();
-        if let Some(old_pos) = self.cursor_position {
-            let y = old_pos.y as usize;
-            let x = old_pos.x as usize;
-            let line = &mut self.buffer[y];
-            if x < line.len() && old_pos != new_pos {
-                let style = self.options.cursor_shape.hide(line[x].style());
-                line[x].set_style(style);
-            }
+        if let Some(old_pos) = self.cursor_position.filter(|pos| *pos != new_pos) {
+            let shape = self.options.cursor_shape;
+            style_cursor_cells(
+                &mut self.buffer[old_pos.y as usize],
+                old_pos.x as usize,
+                |style| shape.hide(style),
+            );
+            // Keep the cursor visible while it moves
+            self.cursor_blink.reset();
         }
         self.cursor_position = Some(new_pos);
+        record_cursor_position(self.cursor_position);
         Ok(())
     }
 
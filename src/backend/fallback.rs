@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use ratatui::buffer::Cell;
+
+/// The default substitutions of [`AsciiFallback`].
+const DEFAULT_SUBSTITUTIONS: &[(&str, char)] = &[
+    // Horizontal lines
+    ("─━┄┅┈┉╌╍═╴╶╸╺╼╾", '-'),
+    // Vertical lines
+    ("│┃┆┇┊┋╎╏║╵╷╹╻╽╿", '|'),
+    // Corners, junctions and crossings
+    (
+        "┌┍┎┏┐┑┒┓└┕┖┗┘┙┚┛├┝┞┟┠┡┢┣┤┥┦┧┨┩┪┫┬┭┮┯┰┱┲┳┴┵┶┷┸┹┺┻┼┽┾┿╀╁╂╃╄╅╆╇╈╉╊╋\
+         ╒╓╔╕╖╗╘╙╚╛╜╝╞╟╠╡╢╣╤╥╦╧╨╩╪╫╬╭╮╯╰",
+        '+',
+    ),
+    // Diagonals
+    ("╱", '/'),
+    ("╲", '\\'),
+    ("╳", 'X'),
+    // Arrows
+    ("←⇐◀◄⟵", '<'),
+    ("→⇒▶►⟶", '>'),
+    ("↑⇑▲", '^'),
+    ("↓⇓▼", 'v'),
+    // Blocks and shades
+    ("█▉▊▋▌▍▎▐▓▒", '#'),
+    ("░", '.'),
+    ("▀▔", '"'),
+    ("▄▁▂▃▅▆▇", '_'),
+    // Bullets and marks
+    ("•●○◦◯∙·", '*'),
+    ("■□▪▫◆◇", '#'),
+    ("✓✔", 'v'),
+    ("✗✘×", 'x'),
+    ("…", '.'),
+    // Quotes and dashes
+    ("‘’‚′", '\''),
+    ("“”„″", '"'),
+    ("–—―", '-'),
+];
+
+/// A substitution table for rendering Unicode symbols as ASCII.
+///
+/// This is useful for targets that can't render some glyphs (e.g. a font
+/// atlas of the [`WebGl2Backend`] without box-drawing characters), which would
+/// otherwise be rendered as placeholder boxes. The default table maps
+/// box-drawing characters to `+-|`, arrows to `<>^v` and a few other common
+/// symbols to their closest ASCII equivalents.
+///
+/// ```rust
+/// use ratzilla::backend::fallback::AsciiFallback;
+///
+/// let fallback = AsciiFallback::default().with('★', '*');
+/// assert_eq!(fallback.substitute("┌─★─┐"), "+-*-+");
+/// ```
+///
+/// [`WebGl2Backend`]: crate::WebGl2Backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsciiFallback {
+    /// The substitutions of each symbol.
+    table: HashMap<char, char>,
+}
+
+impl Default for AsciiFallback {
+    fn default() -> Self {
+        let table = DEFAULT_SUBSTITUTIONS
+            .iter()
+            .flat_map(|(symbols, ascii)| symbols.chars().map(|symbol| (symbol, *ascii)))
+            .collect();
+        Self { table }
+    }
+}
+
+impl AsciiFallback {
+    /// Constructs a new [`AsciiFallback`] without any substitutions.
+    pub fn empty() -> Self {
+        Self {
+            table: HashMap::new(),
+        }
+    }
+
+    /// Adds a substitution, replacing the existing one of the symbol if any.
+    pub fn with(mut self, symbol: char, ascii: char) -> Self {
+        self.table.insert(symbol, ascii);
+        self
+    }
+
+    /// Removes the substitution of the given symbol.
+    pub fn without(mut self, symbol: char) -> Self {
+        self.table.remove(&symbol);
+        self
+    }
+
+    /// Returns the given text with all the symbols in the table substituted.
+    pub fn substitute(&self, text: &str) -> String {
+        text.chars()
+            .map(|c| self.table.get(&c).copied().unwrap_or(c))
+            .collect()
+    }
+
+    /// Substitutes the symbol of the given cell, if needed.
+    ///
+    /// Returns `None` if the cell doesn't contain any symbol in the table.
+    pub(crate) fn apply(&self, cell: &Cell) -> Option<Cell> {
+        let symbol = cell.symbol();
+        if symbol.is_ascii() || !symbol.chars().any(|c| self.table.contains_key(&c)) {
+            return None;
+        }
+        let mut cell = cell.clone();
+        cell.set_symbol(&self.substitute(symbol));
+        Some(cell)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let fallback = AsciiFallback::default().without('→');
+        let mut cell = Cell::default();
+        cell.set_symbol("╭");
+        let mut expected = cell.clone();
+        expected.set_symbol("+");
+        assert_eq!(fallback.apply(&cell), Some(expected));
+        cell.set_symbol("→");
+        assert_eq!(fallback.apply(&cell), None);
+        cell.set_symbol("a");
+        assert_eq!(fallback.apply(&cell), None);
+        assert_eq!(fallback.substitute("◀ │ ▶"), "< | >");
+    }
+}
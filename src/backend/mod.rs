@@ -47,9 +47,25 @@ pub mod dom;
 pub mod webgl2;
 
 /// Color handling.
-mod color;
+pub(crate) mod color;
+/// OSC escape sequence handling.
+pub(crate) mod osc;
+/// Transparent rendering of the WebGL2 backend.
+pub(crate) mod transparency;
 /// Backend utilities.
 pub(crate) mod utils;
 
 /// Cursor shapes.
 pub mod cursor;
+
+/// ASCII fallback for Unicode symbols.
+pub mod fallback;
+
+/// Rendering of control characters.
+pub mod control;
+
+/// Element stacking.
+pub mod stacking;
+
+/// Runtime backend selection.
+pub mod multi;
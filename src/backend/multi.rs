@@ -0,0 +1,620 @@
+use std::{fmt, io, str::FromStr};
+
+use ratatui::{
+    backend::{Backend, ClearType, WindowSize},
+    buffer::Cell,
+    layout::{Position, Size},
+    Terminal, TerminalOptions,
+};
+use web_sys::UrlSearchParams;
+
+use crate::{
+    backend::{canvas::CanvasBackendOptions, dom::DomBackendOptions, webgl2::WebGl2BackendOptions},
+    error::Error,
+    render::{cell_at, request_redraw},
+    utils::browser_info,
+    CanvasBackend, DomBackend, WebGl2Backend,
+};
+
+/// Type of a backend.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BackendType {
+    /// The [`DomBackend`].
+    #[default]
+    Dom,
+    /// The [`CanvasBackend`].
+    Canvas,
+    /// The [`WebGl2Backend`].
+    WebGl2,
+}
+
+impl BackendType {
+    /// All the backend types.
+    pub const ALL: [BackendType; 3] = [BackendType::Dom, BackendType::Canvas, BackendType::WebGl2];
+
+    /// Returns the name of the backend type, as used in the URL.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackendType::Dom => "dom",
+            BackendType::Canvas => "canvas",
+            BackendType::WebGl2 => "webgl2",
+        }
+    }
+}
+
+impl FromStr for BackendType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BackendType::ALL
+            .into_iter()
+            .find(|backend_type| backend_type.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| Error::UnknownBackend(s.to_string()))
+    }
+}
+
+impl fmt::Display for BackendType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A backend that is selected at runtime.
+///
+/// This allows switching between the backends (e.g. based on the URL or the
+/// capabilities of the browser) while providing a single [`Backend`] type, so
+/// the rest of the application doesn't depend on the selected backend. All the
+/// operations are delegated to the wrapped backend.
+///
+/// If the safe fallback is enabled (see [`MultiBackendBuilder::safe_fallback`]),
+/// the backend is replaced with another one when drawing or flushing fails.
+///
+/// See [`MultiBackendBuilder`] for creating a terminal with this backend.
+pub struct RatzillaBackend {
+    /// The backend in use.
+    backend: ActiveBackend,
+    /// Fallback backends for when the active backend fails, if enabled.
+    fallback: Option<SafeFallback>,
+}
+
+impl RatzillaBackend {
+    /// Returns the type of the backend in use.
+    ///
+    /// This changes when the backend falls back to another one.
+    pub fn backend_type(&self) -> BackendType {
+        match &self.backend {
+            ActiveBackend::Dom(_) => BackendType::Dom,
+            ActiveBackend::Canvas(_) => BackendType::Canvas,
+            ActiveBackend::WebGl2(_) => BackendType::WebGl2,
+        }
+    }
+
+    /// Returns the offset of the grid within the available area, in pixels.
+    ///
+    /// See [`DomBackend::grid_offset`], [`CanvasBackend::grid_offset`] and
+    /// [`WebGl2Backend::grid_offset`].
+    pub fn grid_offset(&self) -> (u32, u32) {
+        match &self.backend {
+            ActiveBackend::Dom(backend) => backend.grid_offset(),
+            ActiveBackend::Canvas(backend) => backend.grid_offset(),
+            ActiveBackend::WebGl2(backend) => backend.grid_offset(),
+        }
+    }
+
+    /// Removes the canvas of the backend from the page.
+    ///
+    /// This is useful when switching to another backend. The DOM backend
+    /// renders into its grid element, which is left as is.
+    pub fn remove_canvas(&self) {
+        match &self.backend {
+            ActiveBackend::Dom(_) => {}
+            ActiveBackend::Canvas(backend) => backend.canvas().remove(),
+            ActiveBackend::WebGl2(backend) => backend.beamterm().canvas().remove(),
+        }
+    }
+
+    /// Switches to the next fallback backend after the active backend failed.
+    ///
+    /// Returns the original error if there is no fallback left. Otherwise, the
+    /// last frame is drawn on the new backend with the next draw, so that the
+    /// application continues where it was.
+    fn fall_back(&mut self, error: io::Error) -> io::Result<()> {
+        let failed = self.backend_type();
+        let Some(fallback) = self.fallback.as_mut() else {
+            return Err(error);
+        };
+        let Some(backend) = fallback.next_backend(failed) else {
+            return Err(error);
+        };
+        fallback.redraw = true;
+        self.remove_canvas();
+        self.backend = backend;
+        web_sys::console::warn_1(
+            &format!(
+                "{failed} backend failed ({error}), falling back to {}",
+                self.backend_type()
+            )
+            .into(),
+        );
+        // Draw the last frame even if the render loop is idle
+        request_redraw();
+        Ok(())
+    }
+
+    /// Draws the last frame and the given content on the fallback backend.
+    ///
+    /// The fallback backend may be smaller, so the cells outside of it are left
+    /// out until the terminal is resized to it.
+    fn redraw_fallback(&mut self, content: &[(u16, u16, &Cell)]) -> io::Result<()> {
+        let size = self.backend.size()?;
+        let last_frame = (0..size.height)
+            .flat_map(|y| (0..size.width).map(move |x| (x, y)))
+            .filter_map(|(x, y)| Some((x, y, cell_at(Position::new(x, y))?)))
+            .collect::<Vec<_>>();
+        let content = content
+            .iter()
+            .copied()
+            .filter(|(x, y, _)| *x < size.width && *y < size.height);
+        self.backend.draw(
+            last_frame
+                .iter()
+                .map(|(x, y, cell)| (*x, *y, cell))
+                .chain(content),
+        )
+    }
+}
+
+impl From<DomBackend> for RatzillaBackend {
+    fn from(backend: DomBackend) -> Self {
+        ActiveBackend::Dom(backend).into()
+    }
+}
+
+impl From<CanvasBackend> for RatzillaBackend {
+    fn from(backend: CanvasBackend) -> Self {
+        ActiveBackend::Canvas(backend).into()
+    }
+}
+
+impl From<WebGl2Backend> for RatzillaBackend {
+    fn from(backend: WebGl2Backend) -> Self {
+        ActiveBackend::WebGl2(Box::new(backend)).into()
+    }
+}
+
+impl From<ActiveBackend> for RatzillaBackend {
+    fn from(backend: ActiveBackend) -> Self {
+        Self {
+            backend,
+            fallback: None,
+        }
+    }
+}
+
+impl Backend for RatzillaBackend {
+    type Error = io::Error;
+
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        if self.fallback.is_none() {
+            return self.backend.draw(content);
+        }
+        // Keep the content for drawing it on a fallback backend
+        let content = content.collect::<Vec<_>>();
+        loop {
+            let redraw = self
+                .fallback
+                .as_mut()
+                .is_some_and(|fallback| std::mem::take(&mut fallback.redraw));
+            let result = if redraw {
+                self.redraw_fallback(&content)
+            } else {
+                self.backend.draw(content.iter().copied())
+            };
+            match result {
+                Ok(()) => return Ok(()),
+                Err(error) => self.fall_back(error)?,
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.backend.flush() {
+            Err(error) if self.fallback.is_some() => self.fall_back(error),
+            result => result,
+        }
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.backend.size()
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.backend.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.backend.show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        self.backend.get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.backend.set_cursor_position(position)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.backend.clear()
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.backend.append_lines(n)
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.backend.window_size()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> Result<(), Self::Error> {
+        self.backend.clear_region(clear_type)
+    }
+}
+
+/// The backend in use by a [`RatzillaBackend`].
+enum ActiveBackend {
+    /// The [`DomBackend`].
+    Dom(DomBackend),
+    /// The [`CanvasBackend`].
+    Canvas(CanvasBackend),
+    /// The [`WebGl2Backend`].
+    ///
+    /// It is boxed since it is much larger than the other backends.
+    WebGl2(Box<WebGl2Backend>),
+}
+
+impl Backend for ActiveBackend {
+    type Error = io::Error;
+
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        match self {
+            ActiveBackend::Dom(backend) => backend.draw(content),
+            ActiveBackend::Canvas(backend) => backend.draw(content),
+            ActiveBackend::WebGl2(backend) => backend.draw(content),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            ActiveBackend::Dom(backend) => backend.flush(),
+            ActiveBackend::Canvas(backend) => backend.flush(),
+            ActiveBackend::WebGl2(backend) => backend.flush(),
+        }
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        match self {
+            ActiveBackend::Dom(backend) => backend.size(),
+            ActiveBackend::Canvas(backend) => backend.size(),
+            ActiveBackend::WebGl2(backend) => backend.size(),
+        }
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        match self {
+            ActiveBackend::Dom(backend) => backend.hide_cursor(),
+            ActiveBackend::Canvas(backend) => backend.hide_cursor(),
+            ActiveBackend::WebGl2(backend) => backend.hide_cursor(),
+        }
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        match self {
+            ActiveBackend::Dom(backend) => backend.show_cursor(),
+            ActiveBackend::Canvas(backend) => backend.show_cursor(),
+            ActiveBackend::WebGl2(backend) => backend.show_cursor(),
+        }
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        match self {
+            ActiveBackend::Dom(backend) => backend.get_cursor_position(),
+            ActiveBackend::Canvas(backend) => backend.get_cursor_position(),
+            ActiveBackend::WebGl2(backend) => backend.get_cursor_position(),
+        }
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        match self {
+            ActiveBackend::Dom(backend) => backend.set_cursor_position(position),
+            ActiveBackend::Canvas(backend) => backend.set_cursor_position(position),
+            ActiveBackend::WebGl2(backend) => backend.set_cursor_position(position),
+        }
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        match self {
+            ActiveBackend::Dom(backend) => backend.clear(),
+            ActiveBackend::Canvas(backend) => backend.clear(),
+            ActiveBackend::WebGl2(backend) => backend.clear(),
+        }
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        match self {
+            ActiveBackend::Dom(backend) => backend.append_lines(n),
+            ActiveBackend::Canvas(backend) => backend.append_lines(n),
+            ActiveBackend::WebGl2(backend) => backend.append_lines(n),
+        }
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        match self {
+            ActiveBackend::Dom(backend) => backend.window_size(),
+            ActiveBackend::Canvas(backend) => backend.window_size(),
+            ActiveBackend::WebGl2(backend) => backend.window_size(),
+        }
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> Result<(), Self::Error> {
+        match clear_type {
+            ClearType::All => self.clear(),
+            _ => Err(io::Error::other("unimplemented")),
+        }
+    }
+}
+
+/// Builder for a terminal with a backend that is selected at runtime.
+///
+/// The backend is selected in the following order:
+///
+/// 1. The `?backend=<type>` URL query parameter (`dom`, `canvas` or `webgl2`).
+/// 2. The fallback backend given to [`MultiBackendBuilder::with_fallback`].
+///
+/// Since WebGL2 is broken on some browsers, the [`CanvasBackend`] is used
+/// instead of the [`WebGl2Backend`] there. If the selected backend can't be
+/// created (e.g. because WebGL2 is not supported), the fallback backend is
+/// created instead.
+///
+/// ```rust no_run
+/// use ratzilla::{
+///     backend::{
+///         canvas::CanvasBackendOptions,
+///         multi::{BackendType, MultiBackendBuilder},
+///     },
+///     WebRenderer,
+/// };
+///
+/// let terminal = MultiBackendBuilder::with_fallback(BackendType::Dom)
+///     .canvas_options(CanvasBackendOptions::new().grid_id("terminal"))
+///     .build_terminal()
+///     .unwrap();
+/// let backend_type = terminal.backend().backend_type();
+/// terminal.draw_web(move |frame| {
+///     frame.render_widget(format!("Rendered with {backend_type}"), frame.area());
+/// });
+/// ```
+#[derive(Debug, Default)]
+pub struct MultiBackendBuilder {
+    /// The backend that is used unless another one is selected in the URL.
+    fallback_backend: BackendType,
+    /// Options of the terminal.
+    terminal_options: TerminalOptions,
+    /// Options of the DOM backend.
+    dom_options: DomBackendOptions,
+    /// Options of the Canvas backend.
+    canvas_options: CanvasBackendOptions,
+    /// Options of the WebGL2 backend.
+    webgl2_options: WebGl2BackendOptions,
+    /// Whether to fall back to another backend if the selected one fails.
+    safe_fallback: bool,
+}
+
+impl MultiBackendBuilder {
+    /// Constructs a new [`MultiBackendBuilder`] with the given fallback backend.
+    ///
+    /// The fallback backend is used if no backend is selected in the URL, or
+    /// if the selected backend can't be created.
+    pub fn with_fallback(fallback_backend: BackendType) -> Self {
+        Self {
+            fallback_backend,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the options of the terminal.
+    pub fn terminal_options(mut self, options: TerminalOptions) -> Self {
+        self.terminal_options = options;
+        self
+    }
+
+    /// Sets the options of the DOM backend.
+    pub fn dom_options(mut self, options: DomBackendOptions) -> Self {
+        self.dom_options = options;
+        self
+    }
+
+    /// Sets the options of the Canvas backend.
+    pub fn canvas_options(mut self, options: CanvasBackendOptions) -> Self {
+        self.canvas_options = options;
+        self
+    }
+
+    /// Sets the options of the WebGL2 backend.
+    pub fn webgl2_options(mut self, options: WebGl2BackendOptions) -> Self {
+        self.webgl2_options = options;
+        self
+    }
+
+    /// Falls back to another backend if the selected backend fails mid-session.
+    ///
+    /// When drawing or flushing fails (e.g. due to a fatal GPU error), the
+    /// terminal continues on the Canvas backend and then the DOM backend, using
+    /// their configured options, without reloading the page. The draw callback
+    /// is kept as is, and the last drawn content is redrawn on the new backend.
+    pub fn safe_fallback(mut self, enabled: bool) -> Self {
+        self.safe_fallback = enabled;
+        self
+    }
+
+    /// Creates the selected backend and a terminal with it.
+    ///
+    /// Use [`RatzillaBackend::backend_type`] for finding out which backend was
+    /// created.
+    pub fn build_terminal(mut self) -> io::Result<Terminal<RatzillaBackend>> {
+        let terminal_options = std::mem::take(&mut self.terminal_options);
+        Terminal::with_options(self.build_backend()?, terminal_options)
+    }
+
+    /// Creates the selected backend.
+    ///
+    /// This is useful for wrapping the backend before creating a terminal
+    /// with it. The terminal options are not used.
+    pub fn build_backend(self) -> io::Result<RatzillaBackend> {
+        let mut backend_type = parse_backend_from_url(self.fallback_backend);
+        // Avoid WebGL2 on browsers that are known to have issues with it
+        if backend_type == BackendType::WebGl2 && browser_info().has_broken_webgl2() {
+            backend_type = BackendType::Canvas;
+        }
+        let Self {
+            fallback_backend,
+            dom_options,
+            canvas_options,
+            webgl2_options,
+            safe_fallback,
+            ..
+        } = self;
+        // Keep the options of the other backends for falling back to them
+        let mut fallback = safe_fallback.then(|| SafeFallback {
+            canvas_options: Some(canvas_options.clone()),
+            dom_options: Some(dom_options.clone()),
+            redraw: false,
+        });
+
+        // The fallback backend is always of another type, so the options
+        // are only taken once
+        let (mut dom_options, mut canvas_options, mut webgl2_options) = (
+            Some(dom_options),
+            Some(canvas_options),
+            Some(webgl2_options),
+        );
+        let mut create = |backend_type| -> Result<ActiveBackend, Error> {
+            Ok(match backend_type {
+                BackendType::Dom => ActiveBackend::Dom(DomBackend::new_with_options(
+                    dom_options.take().unwrap_or_default(),
+                )?),
+                BackendType::Canvas => ActiveBackend::Canvas(CanvasBackend::new_with_options(
+                    canvas_options.take().unwrap_or_default(),
+                )?),
+                BackendType::WebGl2 => ActiveBackend::WebGl2(Box::new(
+                    WebGl2Backend::new_with_options(webgl2_options.take().unwrap_or_default())?,
+                )),
+            })
+        };
+        let backend = match create(backend_type) {
+            Err(error) if backend_type != fallback_backend => {
+                web_sys::console::warn_1(
+                    &format!("{backend_type} backend failed ({error}), falling back to {fallback_backend}")
+                        .into(),
+                );
+                create(fallback_backend)?
+            }
+            result => result?,
+        };
+        let mut backend = RatzillaBackend::from(backend);
+        // There is nothing left to fall back to from the DOM backend
+        if backend.backend_type() != BackendType::Dom {
+            backend.fallback = fallback.take();
+        }
+        Ok(backend)
+    }
+}
+
+/// Options of the backends to fall back to when the active backend fails.
+///
+/// The fallbacks are tried in order, i.e. Canvas and then DOM.
+#[derive(Debug, Default)]
+struct SafeFallback {
+    /// Options of the Canvas backend, if it wasn't tried yet.
+    canvas_options: Option<CanvasBackendOptions>,
+    /// Options of the DOM backend, if it wasn't tried yet.
+    dom_options: Option<DomBackendOptions>,
+    /// Whether the fallback backend still has to be drawn with the last frame.
+    redraw: bool,
+}
+
+impl SafeFallback {
+    /// Creates the next fallback backend for the given failed backend type.
+    fn next_backend(&mut self, failed: BackendType) -> Option<ActiveBackend> {
+        if failed == BackendType::WebGl2 {
+            if let Some(options) = self.canvas_options.take() {
+                match CanvasBackend::new_with_options(options) {
+                    Ok(backend) => return Some(ActiveBackend::Canvas(backend)),
+                    Err(error) => web_sys::console::warn_1(
+                        &format!("Canvas fallback backend failed: {error}").into(),
+                    ),
+                }
+            }
+        }
+        if failed != BackendType::Dom {
+            if let Some(options) = self.dom_options.take() {
+                match DomBackend::new_with_options(options) {
+                    Ok(backend) => return Some(ActiveBackend::Dom(backend)),
+                    Err(error) => web_sys::console::warn_1(
+                        &format!("DOM fallback backend failed: {error}").into(),
+                    ),
+                }
+            }
+        }
+        None
+    }
+}
+
+impl From<BackendType> for MultiBackendBuilder {
+    fn from(backend_type: BackendType) -> Self {
+        MultiBackendBuilder::with_fallback(backend_type)
+    }
+}
+
+/// Returns the backend type of the `?backend=<type>` URL query parameter.
+///
+/// The type is case-insensitive. Returns the given default if the parameter is
+/// missing or invalid.
+pub fn parse_backend_from_url(default: BackendType) -> BackendType {
+    web_sys::window()
+        .and_then(|window| window.location().search().ok())
+        .and_then(|search| UrlSearchParams::new_with_str(&search).ok())
+        .and_then(|params| params.get("backend"))
+        .and_then(|backend| backend.parse().ok())
+        .unwrap_or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_backend_type() {
+        for backend_type in BackendType::ALL {
+            assert_eq!(
+                backend_type.as_str().parse::<BackendType>().ok(),
+                Some(backend_type)
+            );
+        }
+        assert_eq!(
+            "WebGL2".parse::<BackendType>().ok(),
+            Some(BackendType::WebGl2)
+        );
+        let error = "svg".parse::<BackendType>().err().map(|e| e.to_string());
+        let error = error.unwrap_or_default();
+        for backend_type in BackendType::ALL {
+            assert!(error.contains(backend_type.as_str()));
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use ratatui::buffer::Cell;
+
+use crate::utils::set_document_title;
+
+/// OSC command for setting the icon name and window title.
+const SET_ICON_AND_TITLE: &str = "0";
+
+/// OSC command for setting the window title.
+const SET_TITLE: &str = "2";
+
+/// OSC command for hyperlinks.
+const HYPERLINK: &str = "8";
+
+/// An Operating System Command (OSC) escape sequence.
+///
+/// OSC sequences have the form `ESC ] <command> ; <payload> <terminator>`
+/// where the terminator is either `BEL` or `ESC \`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Osc<'a> {
+    /// The command number, e.g. `2` for setting the window title.
+    pub(crate) command: &'a str,
+    /// The arguments of the command.
+    pub(crate) payload: &'a str,
+}
+
+/// Parses the OSC sequence at the start of the given text.
+///
+/// Returns the sequence along with the text that follows it.
+fn parse_osc(text: &str) -> Option<(Osc<'_>, &str)> {
+    let body = text.strip_prefix("\x1b]")?;
+    let end = body.find(['\x07', '\x1b'])?;
+    let rest = &body[end..];
+    let rest = rest
+        .strip_prefix('\x07')
+        .or_else(|| rest.strip_prefix("\x1b\\"))?;
+    let (command, payload) = body[..end].split_once(';').unwrap_or((&body[..end], ""));
+    Some((Osc { command, payload }, rest))
+}
+
+/// Parses a run of consecutive OSC sequences that spans the whole text.
+fn parse_osc_run(mut text: &str) -> Option<Vec<Osc<'_>>> {
+    let mut sequences = Vec::new();
+    while !text.is_empty() {
+        let (osc, rest) = parse_osc(text)?;
+        sequences.push(osc);
+        text = rest;
+    }
+    Some(sequences)
+}
+
+/// Parses the OSC sequences surrounding the visible text of a cell symbol.
+///
+/// Returns the sequences in order of appearance along with the visible text,
+/// which is the text between the leading and the trailing sequences.
+pub(crate) fn parse_symbol(symbol: &str) -> (Vec<Osc<'_>>, &str) {
+    let mut sequences = Vec::new();
+    let mut text = symbol;
+    while let Some((osc, rest)) = parse_osc(text) {
+        sequences.push(osc);
+        text = rest;
+    }
+    for (i, _) in text.match_indices("\x1b]") {
+        if let Some(trailing) = parse_osc_run(&text[i..]) {
+            sequences.extend(trailing);
+            text = &text[..i];
+            break;
+        }
+    }
+    (sequences, text)
+}
+
+/// Returns the visible text of a cell symbol, without any OSC sequences.
+///
+/// If the symbol consists of escape sequences only, a space is returned.
+pub(crate) fn visible_text(symbol: &str) -> &str {
+    if !symbol.contains('\x1b') {
+        return symbol;
+    }
+    match parse_symbol(symbol).1 {
+        "" => " ",
+        text => text,
+    }
+}
+
+/// Returns the URI of the OSC 8 hyperlink in the given cell symbol, if any.
+///
+/// Hyperlinks have the form `ESC ] 8 ; <params> ; <URI> ST <text> ESC ] 8 ; ; ST`
+/// where the closing sequence has an empty URI.
+pub(crate) fn hyperlink(symbol: &str) -> Option<&str> {
+    if !symbol.contains('\x1b') {
+        return None;
+    }
+    parse_symbol(symbol).0.into_iter().find_map(|osc| {
+        let (_, uri) = osc.payload.split_once(';')?;
+        (osc.command == HYPERLINK && !uri.is_empty()).then_some(uri)
+    })
+}
+
+/// Applies the OSC sequences found in the given cell symbol.
+///
+/// Currently, this handles setting the document title (OSC 0 and OSC 2).
+pub(crate) fn apply_sequences(symbol: &str) {
+    if !symbol.contains('\x1b') {
+        return;
+    }
+    for osc in parse_symbol(symbol).0 {
+        if osc.command == SET_ICON_AND_TITLE || osc.command == SET_TITLE {
+            let _ = set_document_title(osc.payload);
+        }
+    }
+}
+
+/// Handles the escape sequences in the symbol of the given cell.
+///
+/// The sequences are applied via [`apply_sequences`] and stripped from the
+/// returned cell so that only the visible text gets rendered.
+pub(crate) fn handle_sequences(cell: &Cell) -> Cell {
+    let mut cell = cell.clone();
+    if cell.symbol().contains('\x1b') {
+        apply_sequences(cell.symbol());
+        let text = visible_text(cell.symbol()).to_string();
+        cell.set_symbol(&text);
+    }
+    cell
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_title() {
+        for symbol in ["\x1b]2;Ratzilla\x07", "\x1b]2;Ratzilla\x1b\\"] {
+            let (sequences, text) = parse_symbol(symbol);
+            assert_eq!(
+                sequences,
+                vec![Osc {
+                    command: "2",
+                    payload: "Ratzilla",
+                }]
+            );
+            assert_eq!(text, "");
+            assert_eq!(visible_text(symbol), " ");
+        }
+    }
+
+    #[test]
+    fn test_parse_surrounding_sequences() {
+        let symbol = "\x1b]8;;https://ratatui.rs\x07ratatui\x1b]8;;\x07";
+        let (sequences, text) = parse_symbol(symbol);
+        assert_eq!(
+            sequences,
+            vec![
+                Osc {
+                    command: "8",
+                    payload: ";https://ratatui.rs",
+                },
+                Osc {
+                    command: "8",
+                    payload: ";",
+                },
+            ]
+        );
+        assert_eq!(text, "ratatui");
+        assert_eq!(hyperlink(symbol), Some("https://ratatui.rs"));
+    }
+
+    #[test]
+    fn test_parse_plain_symbol() {
+        assert_eq!(parse_symbol("a"), (vec![], "a"));
+        assert_eq!(visible_text("a"), "a");
+        assert_eq!(hyperlink("a"), None);
+        // Unterminated sequences are left as is
+        assert_eq!(parse_symbol("\x1b]2;title"), (vec![], "\x1b]2;title"));
+    }
+}
@@ -0,0 +1,33 @@
+/// Supported values of the CSS [`position`] property.
+///
+/// This is used for controlling how the terminal element is stacked
+/// relative to the other content of the page, together with a `z-index`.
+///
+/// [`position`]: https://developer.mozilla.org/en-US/docs/Web/CSS/position
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CssPosition {
+    /// The element is positioned according to the normal flow of the document.
+    #[default]
+    Static,
+    /// The element is offset relative to its position in the normal flow.
+    Relative,
+    /// The element is positioned relative to its closest positioned ancestor.
+    Absolute,
+    /// The element is positioned relative to the viewport.
+    Fixed,
+    /// The element sticks to its scrolling ancestor.
+    Sticky,
+}
+
+impl CssPosition {
+    /// Returns the CSS value of the position.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CssPosition::Static => "static",
+            CssPosition::Relative => "relative",
+            CssPosition::Absolute => "absolute",
+            CssPosition::Fixed => "fixed",
+            CssPosition::Sticky => "sticky",
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use web_sys::{
+    WebGl2RenderingContext as GL, WebGlProgram, WebGlShader, WebGlTexture, WebGlVertexArrayObject,
+};
+
+use crate::error::Error;
+
+/// The color that the unset cells and the padding are rendered with in the
+/// transparent mode of the [`WebGl2Backend`].
+///
+/// The renderer draws every pixel as opaque, so the pixels of this color are
+/// made transparent afterwards. It is close to black, so that the edges of the
+/// glyphs on the transparent cells look like they were drawn on a dark
+/// background.
+///
+/// [`WebGl2Backend`]: crate::WebGl2Backend
+pub(crate) const TRANSPARENT_KEY: u32 = 0x000001;
+
+/// Vertex shader that covers the viewport with a single triangle.
+const VERTEX_SHADER: &str = r#"#version 300 es
+void main() {
+    vec2 position = vec2(float((gl_VertexID & 1) << 2), float((gl_VertexID & 2) << 1)) - 1.0;
+    gl_Position = vec4(position, 0.0, 1.0);
+}
+"#;
+
+/// Fragment shader that replaces the key color with transparent pixels.
+///
+/// The output is premultiplied, as expected by the canvas.
+const FRAGMENT_SHADER: &str = r#"#version 300 es
+precision mediump float;
+
+uniform sampler2D u_frame;
+uniform vec3 u_key;
+
+out vec4 FragColor;
+
+void main() {
+    vec3 color = texelFetch(u_frame, ivec2(gl_FragCoord.xy), 0).rgb;
+    bool is_key = all(lessThan(abs(color - u_key), vec3(0.5 / 255.0)));
+    FragColor = is_key ? vec4(0.0) : vec4(color, 1.0);
+}
+"#;
+
+/// Returns the given color, changed slightly if it is the [`TRANSPARENT_KEY`],
+/// so that it is rendered as opaque.
+pub(crate) fn opaque_color(color: u32) -> u32 {
+    if color == TRANSPARENT_KEY {
+        color ^ 1
+    } else {
+        color
+    }
+}
+
+/// Post-processing pass that makes the pixels of the [`TRANSPARENT_KEY`] color
+/// transparent.
+///
+/// The rendered frame is copied into a texture and drawn back onto the canvas
+/// with the [`FRAGMENT_SHADER`].
+#[derive(Debug)]
+pub(crate) struct TransparencyPass {
+    /// The shader program.
+    program: WebGlProgram,
+    /// Empty vertex array, since the vertices are generated in the shader.
+    vertex_array: WebGlVertexArrayObject,
+    /// Texture that holds the copy of the rendered frame.
+    texture: WebGlTexture,
+}
+
+impl TransparencyPass {
+    /// Creates the resources of the pass in the given context.
+    pub(crate) fn new(gl: &GL) -> Result<Self, Error> {
+        let program = create_program(gl)?;
+        let vertex_array = gl
+            .create_vertex_array()
+            .ok_or(Error::UnableToRetrieveComponent("WebGlVertexArrayObject"))?;
+        let texture = gl
+            .create_texture()
+            .ok_or(Error::UnableToRetrieveComponent("WebGlTexture"))?;
+
+        gl.bind_texture(GL::TEXTURE_2D, Some(&texture));
+        for (parameter, value) in [
+            (GL::TEXTURE_MIN_FILTER, GL::NEAREST),
+            (GL::TEXTURE_MAG_FILTER, GL::NEAREST),
+            (GL::TEXTURE_WRAP_S, GL::CLAMP_TO_EDGE),
+            (GL::TEXTURE_WRAP_T, GL::CLAMP_TO_EDGE),
+        ] {
+            gl.tex_parameteri(GL::TEXTURE_2D, parameter, value as i32);
+        }
+        gl.bind_texture(GL::TEXTURE_2D, None);
+
+        // The sampler uses the first texture unit by default
+        gl.use_program(Some(&program));
+        let [r, g, b] = [16, 8, 0].map(|shift| ((TRANSPARENT_KEY >> shift) & 0xff) as f32 / 255.0);
+        gl.uniform3f(gl.get_uniform_location(&program, "u_key").as_ref(), r, g, b);
+        gl.use_program(None);
+
+        Ok(Self {
+            program,
+            vertex_array,
+            texture,
+        })
+    }
+
+    /// Makes the pixels of the key color in the rendered frame transparent.
+    ///
+    /// This must be called after rendering the frame, with the size of the
+    /// canvas.
+    pub(crate) fn apply(&self, gl: &GL, (width, height): (i32, i32)) {
+        gl.active_texture(GL::TEXTURE0);
+        gl.bind_texture(GL::TEXTURE_2D, Some(&self.texture));
+        gl.copy_tex_image_2d(GL::TEXTURE_2D, 0, GL::RGBA, 0, 0, width, height, 0);
+        gl.use_program(Some(&self.program));
+        gl.bind_vertex_array(Some(&self.vertex_array));
+        gl.draw_arrays(GL::TRIANGLES, 0, 3);
+        gl.bind_vertex_array(None);
+        gl.bind_texture(GL::TEXTURE_2D, None);
+    }
+}
+
+/// Compiles and links the shader program of the pass.
+fn create_program(gl: &GL) -> Result<WebGlProgram, Error> {
+    let vertex_shader = compile_shader(gl, GL::VERTEX_SHADER, VERTEX_SHADER)?;
+    let fragment_shader = compile_shader(gl, GL::FRAGMENT_SHADER, FRAGMENT_SHADER)?;
+    let program = gl
+        .create_program()
+        .ok_or(Error::UnableToRetrieveComponent("WebGlProgram"))?;
+    gl.attach_shader(&program, &vertex_shader);
+    gl.attach_shader(&program, &fragment_shader);
+    gl.link_program(&program);
+    // The shaders are not needed once the program is linked
+    gl.delete_shader(Some(&vertex_shader));
+    gl.delete_shader(Some(&fragment_shader));
+
+    if gl
+        .get_program_parameter(&program, GL::LINK_STATUS)
+        .as_bool()
+        .unwrap_or(false)
+    {
+        Ok(program)
+    } else {
+        let log = gl.get_program_info_log(&program).unwrap_or_default();
+        Err(beamterm_renderer::Error::Shader(log).into())
+    }
+}
+
+/// Compiles a shader of the given type.
+fn compile_shader(gl: &GL, shader_type: u32, source: &str) -> Result<WebGlShader, Error> {
+    let shader = gl
+        .create_shader(shader_type)
+        .ok_or(Error::UnableToRetrieveComponent("WebGlShader"))?;
+    gl.shader_source(&shader, source);
+    gl.compile_shader(&shader);
+
+    if gl
+        .get_shader_parameter(&shader, GL::COMPILE_STATUS)
+        .as_bool()
+        .unwrap_or(false)
+    {
+        Ok(shader)
+    } else {
+        let log = gl.get_shader_info_log(&shader).unwrap_or_default();
+        Err(beamterm_renderer::Error::Shader(log).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opaque_color() {
+        assert_eq!(opaque_color(0x202040), 0x202040);
+        assert_eq!(opaque_color(0x000000), 0x000000);
+        assert_ne!(opaque_color(TRANSPARENT_KEY), TRANSPARENT_KEY);
+    }
+}
@@ -1,22 +1,26 @@
+use std::ops::Range;
+
 use crate::{
-    backend::color::ansi_to_rgb,
+    backend::{color::ansi_to_rgb, osc, stacking::CssPosition},
     error::Error,
     utils::{get_screen_size, get_window_size, is_mobile},
 };
 use compact_str::{format_compact, CompactString};
 use ratatui::{
     buffer::Cell,
-    style::{Color, Modifier},
+    style::{Color, Modifier, Style},
+    text::Span,
 };
+use unicode_segmentation::UnicodeSegmentation;
 use web_sys::{
     wasm_bindgen::{JsCast, JsValue},
-    window, Document, Element, HtmlCanvasElement, Window,
+    window, CssStyleDeclaration, Document, Element, HtmlCanvasElement, HtmlElement, Window,
 };
 
 /// Creates a new `<span>` element with the given cell.
 pub(crate) fn create_span(document: &Document, cell: &Cell) -> Result<Element, Error> {
     let span = document.create_element("span")?;
-    span.set_inner_html(cell.symbol());
+    set_cell_content(document, &span, cell)?;
 
     let style = get_cell_style_as_css(cell);
     span.set_attribute("style", &style)?;
@@ -34,6 +38,44 @@ pub(crate) fn create_anchor(document: &Document, cells: &[Cell]) -> Result<Eleme
     Ok(anchor)
 }
 
+/// Sets the content of the given element to the symbol of the cell.
+///
+/// Cells with an [OSC 8] hyperlink are wrapped in an `<a>` element if the URI
+/// is safe to open (see [`is_safe_url`]), while any other escape sequences are
+/// stripped from the symbol.
+///
+/// [OSC 8]: https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda
+pub(crate) fn set_cell_content(
+    document: &Document,
+    element: &Element,
+    cell: &Cell,
+) -> Result<(), Error> {
+    let text = osc::visible_text(cell.symbol());
+    match osc::hyperlink(cell.symbol()).filter(|url| is_safe_url(url)) {
+        Some(url) => {
+            let anchor = document.create_element("a")?;
+            anchor.set_attribute("href", url)?;
+            anchor.set_attribute("style", "color: inherit;")?;
+            anchor.set_text_content(Some(text));
+            element.set_inner_html("");
+            element.append_child(&anchor)?;
+        }
+        None => element.set_inner_html(text),
+    }
+    Ok(())
+}
+
+/// Returns whether the given URL of a hyperlink is safe to open.
+///
+/// Only the `http`, `https` and `mailto` schemes are allowed, so that e.g.
+/// `javascript:` URLs printed by the application can't run scripts.
+pub(crate) fn is_safe_url(url: &str) -> bool {
+    let url = url.trim_start().as_bytes();
+    ["http:", "https:", "mailto:"].iter().any(|scheme| {
+        url.len() >= scheme.len() && url[..scheme.len()].eq_ignore_ascii_case(scheme.as_bytes())
+    })
+}
+
 /// Converts a cell to a CSS style.
 pub(crate) fn get_cell_style_as_css(cell: &Cell) -> String {
     let mut fg = ansi_to_rgb(cell.fg);
@@ -123,20 +165,33 @@ pub(crate) fn get_raw_screen_size() -> (i32, i32) {
     (s.width().unwrap(), s.height().unwrap())
 }
 
+/// Size of a cell of the DOM grid in pixels, without any gap between cells.
+pub(crate) const DOM_CELL_SIZE: (u16, u16) = (10, 20);
+
 /// Returns a buffer based on the screen size.
-pub(crate) fn get_sized_buffer() -> Vec<Vec<Cell>> {
+///
+/// The `cell_gap` is the spacing between the cells in pixels and the
+/// `font_scale` is the scale of the font size.
+pub(crate) fn get_sized_buffer(cell_gap: u16, font_scale: f64) -> Vec<Vec<Cell>> {
     let size = if is_mobile() {
         get_screen_size()
     } else {
         get_window_size()
     };
-    vec![vec![Cell::default(); size.width as usize]; size.height as usize]
+    let (cell_width, cell_height) = (DOM_CELL_SIZE.0 as f64, DOM_CELL_SIZE.1 as f64);
+    let width = size.width as f64 * cell_width / (cell_width * font_scale + cell_gap as f64);
+    let height = size.height as f64 * cell_height / (cell_height * font_scale + cell_gap as f64);
+    vec![vec![Cell::default(); width as usize]; height as usize]
 }
 
-/// Returns a buffer based on the canvas size.
-pub(crate) fn get_sized_buffer_from_canvas(canvas: &HtmlCanvasElement) -> Vec<Vec<Cell>> {
-    let width = canvas.client_width() as u16 / 10_u16;
-    let height = canvas.client_height() as u16 / 19_u16;
+/// Returns a buffer based on the canvas size and the given cell size.
+pub(crate) fn get_sized_buffer_from_canvas(
+    canvas: &HtmlCanvasElement,
+    cell_width: f64,
+    cell_height: f64,
+) -> Vec<Vec<Cell>> {
+    let width = (canvas.client_width() as f64 / cell_width) as u16;
+    let height = (canvas.client_height() as f64 / cell_height) as u16;
     vec![vec![Cell::default(); width as usize]; height as usize]
 }
 
@@ -172,6 +227,11 @@ pub(crate) fn performance() -> Result<web_sys::Performance, Error> {
         .ok_or(Error::UnableToRetrieveComponent("Performance"))?)
 }
 
+/// Returns the number of device pixels per CSS pixel of the display.
+pub(crate) fn device_pixel_ratio() -> Result<f64, Error> {
+    Ok(get_window()?.device_pixel_ratio())
+}
+
 /// Creates a new canvas element in the specified parent element with the
 /// given width and height.
 pub(crate) fn create_canvas_in_element(
@@ -194,6 +254,87 @@ pub(crate) fn create_canvas_in_element(
     Ok(canvas)
 }
 
+/// Sets the CSS `position` and `z-index` of the given element.
+///
+/// Properties that are not given are left untouched.
+pub(crate) fn set_stacking(
+    element: &Element,
+    position: Option<CssPosition>,
+    z_index: Option<i32>,
+) -> Result<(), Error> {
+    let style = element
+        .dyn_ref::<HtmlElement>()
+        .ok_or(Error::UnableToRetrieveComponent("HtmlElement"))?
+        .style();
+    if let Some(position) = position {
+        style.set_property("position", position.as_str())?;
+    }
+    if let Some(z_index) = z_index {
+        style.set_property("z-index", &z_index.to_string())?;
+    }
+    Ok(())
+}
+
+/// Returns the largest size with the given aspect ratio that fits into `size`.
+///
+/// The `ratio` is given as `(width, height)`, e.g. `(4, 3)`.
+pub(crate) fn fit_aspect_ratio(size: (u32, u32), ratio: (u32, u32)) -> (u32, u32) {
+    let (width, height) = (size.0 as u64, size.1 as u64);
+    let (ratio_width, ratio_height) = (ratio.0 as u64, ratio.1 as u64);
+    if ratio_width == 0 || ratio_height == 0 {
+        return size;
+    }
+    if width * ratio_height > height * ratio_width {
+        ((height * ratio_width / ratio_height) as u32, size.1)
+    } else {
+        (size.0, (width * ratio_height / ratio_width) as u32)
+    }
+}
+
+/// Centers the canvas of the given size within the container size by adding
+/// margins around it.
+///
+/// Returns the offset of the canvas within the container, in pixels.
+pub(crate) fn letterbox_canvas(
+    canvas: &HtmlCanvasElement,
+    container_size: (u32, u32),
+    size: (u32, u32),
+) -> Result<(u32, u32), Error> {
+    let offset = (
+        container_size.0.saturating_sub(size.0) / 2,
+        container_size.1.saturating_sub(size.1) / 2,
+    );
+    let style = canvas.style();
+    style.set_property("display", "block")?;
+    style.set_property("margin-left", &format!("{}px", offset.0))?;
+    style.set_property("margin-top", &format!("{}px", offset.1))?;
+    Ok(offset)
+}
+
+/// Surrounds the element with the given style with a margin in pixels.
+///
+/// The margin is drawn as a border of the given color, so the offsets of the
+/// mouse events within the element stay relative to the grid.
+pub(crate) fn set_margin(
+    style: &CssStyleDeclaration,
+    margin: (u32, u32),
+    color: &str,
+) -> Result<(), Error> {
+    style.set_property("box-sizing", "content-box")?;
+    style.set_property("border-style", "solid")?;
+    style.set_property("border-color", color)?;
+    style.set_property("border-width", &format!("{}px {}px", margin.1, margin.0))?;
+    Ok(())
+}
+
+/// Disables the antialiasing of text with the given style.
+pub(crate) fn disable_text_antialiasing(style: &CssStyleDeclaration) -> Result<(), Error> {
+    style.set_property("font-smooth", "never")?;
+    style.set_property("-webkit-font-smoothing", "none")?;
+    style.set_property("-moz-osx-font-smoothing", "unset")?;
+    Ok(())
+}
+
 /// Checks if the given cell contains a braille character.
 fn contains_braille(cell: &Cell) -> bool {
     cell.symbol()
@@ -201,3 +342,316 @@ fn contains_braille(cell: &Cell) -> bool {
         .next()
         .is_some_and(|c| ('\u{2800}'..='\u{28FF}').contains(&c))
 }
+
+/// Checks if the given symbol is an emoji.
+///
+/// This covers the pictographic blocks (e.g. `🔴`) and symbols that request
+/// emoji presentation via the variation selector `U+FE0F` (e.g. `❤️`).
+pub(crate) fn is_emoji(symbol: &str) -> bool {
+    symbol
+        .chars()
+        .any(|c| ('\u{1F000}'..='\u{1FAFF}').contains(&c) || c == '\u{FE0F}')
+}
+
+/// Joins the grapheme clusters that are split across the cells of a line.
+///
+/// Complex emoji (e.g. ZWJ sequences or flags made of regional indicators)
+/// consist of multiple code points, which end up in separate cells if a
+/// widget writes them one by one. The whole cluster is moved into its leading
+/// cell and the continuation cells are blanked, so the cluster is rendered as
+/// a single glyph. The continuation cell of a wide fragment is skipped over,
+/// while any other blank cell ends the cluster.
+pub(crate) fn join_grapheme_clusters(line: &mut [Cell]) {
+    let mut lead: Option<usize> = None;
+    let mut continuation = None;
+    for x in 0..line.len() {
+        let symbol = line[x].symbol();
+        if symbol == " " {
+            if continuation != Some(x) {
+                lead = None;
+            }
+            continue;
+        }
+        continuation = (Span::raw(symbol).width() > 1).then_some(x + 1);
+        if let Some(lead) = lead.filter(|_| !symbol.is_ascii() && !symbol.contains('\x1b')) {
+            let cluster = format!("{}{symbol}", line[lead].symbol());
+            if !cluster.contains('\x1b') && cluster.graphemes(true).count() == 1 {
+                line[lead].set_symbol(&cluster);
+                line[x].set_symbol(" ");
+                continue;
+            }
+        }
+        lead = Some(x);
+    }
+}
+
+/// Attaches the zero-width characters in the given line to the preceding cell.
+///
+/// Combining marks (e.g. `U+0301` for an acute accent) and other zero-width
+/// characters that end up in a cell of their own would otherwise be rendered
+/// with zero width, shifting the subsequent columns. They are appended to the
+/// symbol of the cell before them (skipping over the continuation cell of a
+/// wide character) and their own cell is blanked.
+pub(crate) fn attach_zero_width_chars(line: &mut [Cell]) {
+    for x in 1..line.len() {
+        let symbol = line[x].symbol();
+        if symbol.is_ascii() || symbol.contains('\x1b') || Span::raw(symbol).width() != 0 {
+            continue;
+        }
+        let base =
+            if x >= 2 && line[x - 1].symbol() == " " && Span::raw(line[x - 2].symbol()).width() > 1
+            {
+                x - 2
+            } else {
+                x - 1
+            };
+        if line[base].symbol().contains('\x1b') {
+            continue;
+        }
+        let symbol = format!("{}{symbol}", line[base].symbol());
+        line[base].set_symbol(&symbol);
+        line[x].set_symbol(" ");
+    }
+}
+
+/// Returns the range of cells that the cursor at the given column covers.
+///
+/// Wide characters (e.g. CJK) occupy two cells, so the cursor covers both of
+/// them when it is placed on either the character or its continuation cell.
+/// Otherwise, it covers the cell at the column. The range is empty if the
+/// column is outside of the line.
+pub(crate) fn cursor_cells(line: &[Cell], x: usize) -> Range<usize> {
+    let is_wide = |x: usize| Span::raw(line[x].symbol()).width() > 1;
+    if x >= line.len() {
+        x..x
+    } else if is_wide(x) {
+        x..(x + 2).min(line.len())
+    } else if x > 0 && is_wide(x - 1) {
+        x - 1..x + 1
+    } else {
+        x..x + 1
+    }
+}
+
+/// Transforms the style of the cells that the cursor at the given column
+/// covers, see [`cursor_cells`].
+pub(crate) fn style_cursor_cells(line: &mut [Cell], x: usize, transform: impl Fn(Style) -> Style) {
+    let range = cursor_cells(line, x);
+    for cell in &mut line[range] {
+        cell.set_style(transform(cell.style()));
+    }
+}
+
+/// Returns the range of cells of the word at the given column of a line.
+///
+/// Words consist of alphanumeric characters and underscores, as in most
+/// terminals. Any other character forms a word of its own, except for
+/// whitespace, for which `None` is returned.
+pub(crate) fn word_at(line: &[Cell], x: usize) -> Option<Range<usize>> {
+    let is_word = |cell: &Cell| {
+        osc::visible_text(cell.symbol())
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_')
+    };
+    let cell = line.get(x)?;
+    if osc::visible_text(cell.symbol()).trim().is_empty() {
+        return None;
+    }
+    if !is_word(cell) {
+        return Some(x..x + 1);
+    }
+    let start = line[..x]
+        .iter()
+        .rposition(|cell| !is_word(cell))
+        .map_or(0, |i| i + 1);
+    let end = line[x..]
+        .iter()
+        .position(|cell| !is_word(cell))
+        .map_or(line.len(), |i| x + i);
+    Some(start..end)
+}
+
+/// Detects the URLs in the given line of cells.
+///
+/// Returns the range of cells of each URL along with the URL itself. URLs
+/// start with `http://` or `https://` and end at the next whitespace, with
+/// trailing punctuation (e.g. the period at the end of a sentence) excluded.
+pub(crate) fn detect_urls(line: &[Cell]) -> Vec<(Range<usize>, String)> {
+    let mut urls = Vec::new();
+    let mut x = 0;
+    while x < line.len() {
+        let start = x;
+        while x < line.len() && !line[x].symbol().trim().is_empty() {
+            x += 1;
+        }
+        if x == start {
+            x += 1;
+            continue;
+        }
+        let mut word = String::new();
+        let mut offsets = Vec::with_capacity(x - start);
+        for cell in &line[start..x] {
+            offsets.push(word.len());
+            word.push_str(cell.symbol());
+        }
+        let Some(scheme) = ["https://", "http://"]
+            .into_iter()
+            .filter_map(|scheme| word.find(scheme))
+            .min()
+        else {
+            continue;
+        };
+        let url = word[scheme..]
+            .trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '}', '\'', '"', '>']);
+        if !url.trim_end_matches('/').ends_with(':') {
+            let first = offsets.partition_point(|offset| *offset < scheme);
+            let last = offsets.partition_point(|offset| *offset < scheme + url.len());
+            urls.push((start + first..start + last, url.to_string()));
+        }
+    }
+    urls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::cursor::CursorShape;
+
+    #[test]
+    fn test_is_safe_url() {
+        assert!(is_safe_url("https://ratatui.rs"));
+        assert!(is_safe_url("HTTP://ratatui.rs"));
+        assert!(is_safe_url("mailto:orhun@archlinux.org"));
+        assert!(!is_safe_url("javascript:alert(1)"));
+        assert!(!is_safe_url(" JavaScript:alert(1)"));
+        assert!(!is_safe_url("java\tscript:alert(1)"));
+        assert!(!is_safe_url("data:text/html,<script>alert(1)</script>"));
+        assert!(!is_safe_url("//example.com"));
+    }
+
+    #[test]
+    fn test_fit_aspect_ratio() {
+        assert_eq!(fit_aspect_ratio((1920, 1080), (4, 3)), (1440, 1080));
+        assert_eq!(fit_aspect_ratio((800, 1000), (4, 3)), (800, 600));
+        assert_eq!(fit_aspect_ratio((800, 600), (4, 3)), (800, 600));
+        assert_eq!(fit_aspect_ratio((800, 600), (0, 3)), (800, 600));
+    }
+
+    #[test]
+    fn test_join_grapheme_clusters() {
+        let mut line = [
+            "👨", " ", "\u{200d}", "👩", " ", "\u{200d}", "👧", " ", "a", "🇺", "🇸", "🇺", " ", "🇸",
+        ]
+        .map(|symbol| {
+            let mut cell = Cell::default();
+            cell.set_symbol(symbol);
+            cell
+        });
+        join_grapheme_clusters(&mut line);
+        assert_eq!(
+            line.iter().map(Cell::symbol).collect::<Vec<_>>(),
+            [
+                "👨\u{200d}👩\u{200d}👧",
+                " ",
+                " ",
+                " ",
+                " ",
+                " ",
+                " ",
+                " ",
+                "a",
+                "🇺🇸",
+                " ",
+                "🇺",
+                " ",
+                "🇸"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_attach_zero_width_chars() {
+        let mut line = ["e", "\u{301}", "x", "中", " ", "\u{308}", "a"].map(|symbol| {
+            let mut cell = Cell::default();
+            cell.set_symbol(symbol);
+            cell
+        });
+        attach_zero_width_chars(&mut line);
+        assert_eq!(
+            line.iter().map(Cell::symbol).collect::<Vec<_>>(),
+            ["e\u{301}", " ", "x", "中\u{308}", " ", " ", "a"]
+        );
+    }
+
+    #[test]
+    fn test_word_at() {
+        let line = "let foo_bar = 42;"
+            .chars()
+            .map(|c| {
+                let mut cell = Cell::default();
+                cell.set_symbol(&c.to_string());
+                cell
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(word_at(&line, 6), Some(4..11));
+        assert_eq!(word_at(&line, 0), Some(0..3));
+        assert_eq!(word_at(&line, 15), Some(14..16));
+        assert_eq!(word_at(&line, 12), Some(12..13));
+        assert_eq!(word_at(&line, 3), None);
+        assert_eq!(word_at(&line, 100), None);
+    }
+
+    #[test]
+    fn test_cursor_cells() {
+        let line = ["a", "漢", " ", "b"]
+            .into_iter()
+            .map(|symbol| {
+                let mut cell = Cell::default();
+                cell.set_symbol(symbol);
+                cell
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(cursor_cells(&line, 0), 0..1);
+        assert_eq!(cursor_cells(&line, 1), 1..3);
+        assert_eq!(cursor_cells(&line, 2), 1..3);
+        assert_eq!(cursor_cells(&line, 3), 3..4);
+        assert_eq!(cursor_cells(&line, 4), 4..4);
+    }
+
+    #[test]
+    fn test_style_cursor_cells_without_shape() {
+        let mut line = ["a", "漢", " "].map(|symbol| {
+            let mut cell = Cell::default();
+            cell.set_symbol(symbol)
+                .set_style(Style::default().underlined());
+            cell
+        });
+        let expected = line.clone();
+        for x in 0..line.len() {
+            style_cursor_cells(&mut line, x, |style| CursorShape::None.show(style));
+            assert_eq!(line, expected);
+            style_cursor_cells(&mut line, x, |style| CursorShape::None.hide(style));
+            assert_eq!(line, expected);
+        }
+    }
+
+    #[test]
+    fn test_detect_urls() {
+        let line = "see https://ratatui.rs. or (http://a.b/c) https:// x"
+            .chars()
+            .map(|c| {
+                let mut cell = Cell::default();
+                cell.set_symbol(&c.to_string());
+                cell
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            detect_urls(&line),
+            vec![
+                (4..22, "https://ratatui.rs".to_string()),
+                (28..40, "http://a.b/c".to_string()),
+            ]
+        );
+    }
+}
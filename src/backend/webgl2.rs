@@ -1,8 +1,21 @@
 use crate::{
-    backend::{color::to_rgb, utils::*},
+    backend::{
+        color::{contrast_ratio, to_rgb, MIN_CURSOR_CONTRAST},
+        control::control_pictures,
+        cursor::{CursorBlink, DEFAULT_BLINK_INTERVAL},
+        fallback::AsciiFallback,
+        multi::BackendType,
+        osc,
+        transparency::{opaque_color, TransparencyPass, TRANSPARENT_KEY},
+        utils::*,
+    },
     error::Error,
-    widgets::hyperlink::HYPERLINK_MODIFIER,
-    CursorShape,
+    render::{
+        add_event_listener, is_frame_skipped, record_backend_type, record_cursor_position,
+        request_redraw, take_cursor_shape, watch_container, watch_focus,
+    },
+    widgets::{graphics::GraphicsLayer, hyperlink::HYPERLINK_MODIFIER},
+    CssPosition, CursorShape,
 };
 pub use beamterm_renderer::SelectionMode;
 use beamterm_renderer::{mouse::*, select, CellData, GlyphEffect, Terminal as Beamterm, Terminal};
@@ -14,14 +27,22 @@ use ratatui::{
     layout::{Position, Size},
     prelude::Backend,
     style::{Color, Modifier},
+    text::Span,
 };
 use std::{
+    borrow::Cow,
     cell::RefCell,
+    collections::VecDeque,
     io::{Error as IoError, Result as IoResult},
     mem::swap,
     rc::Rc,
+    time::Duration,
+};
+use web_sys::{
+    js_sys::{Date, Object, Reflect},
+    wasm_bindgen::{prelude::Closure, JsCast},
+    window, Element, HtmlCanvasElement,
 };
-use web_sys::{wasm_bindgen::JsCast, window, Element};
 
 /// Re-export beamterm's atlas data type. Used by [`WebGl2BackendOptions::font_atlas`].
 pub use beamterm_renderer::FontAtlasData;
@@ -30,6 +51,9 @@ pub use beamterm_renderer::FontAtlasData;
 const SYNC_TERMINAL_BUFFER_MARK: &str = "sync-terminal-buffer";
 const WEBGL_RENDER_MARK: &str = "webgl-render";
 
+/// Number of frames that [`WebGl2Backend::last_frame_timings`] averages over.
+const FRAME_TIMINGS_SAMPLES: usize = 60;
+
 /// Options for the [`WebGl2Backend`].
 #[derive(Default, Debug)]
 pub struct WebGl2BackendOptions {
@@ -42,11 +66,17 @@ pub struct WebGl2BackendOptions {
     /// Fallback glyph to use for characters not in the font atlas.
     fallback_glyph: Option<CompactString>,
     /// Override the default font atlas.
-    font_atlas: Option<FontAtlasData>,
+    ///
+    /// It is kept for re-creating the renderer when the WebGL context is lost.
+    font_atlas: Option<Rc<FontAtlasData>>,
     /// The canvas padding color.
     canvas_padding_color: Option<Color>,
     /// The cursor shape.
     cursor_shape: CursorShape,
+    /// Blink interval of the cursor.
+    cursor_blink_interval: Option<Duration>,
+    /// Initial position of the cursor.
+    cursor_position: Option<Position>,
     /// Hyperlink click callback.
     hyperlink_callback: Option<HyperlinkCallback>,
     /// Mouse selection mode (enables text selection with mouse).
@@ -55,6 +85,26 @@ pub struct WebGl2BackendOptions {
     measure_performance: bool,
     /// Enable console debugging and introspection API.
     console_debug_api: bool,
+    /// Time that the canvas size has to be stable for before resizing the grid.
+    resize_debounce: Duration,
+    /// Aspect ratio of the canvas.
+    aspect_ratio: Option<(u32, u32)>,
+    /// Whether the graphics layer is enabled.
+    graphics_layer: bool,
+    /// Cursor colors for cells whose colors have too little contrast.
+    cursor_fallback_colors: Option<(Color, Color)>,
+    /// The CSS `position` of the canvas.
+    position: Option<CssPosition>,
+    /// The CSS `z-index` of the canvas.
+    z_index: Option<i32>,
+    /// Whether the page content shows through the unset cells.
+    transparent: bool,
+    /// Substitutions for rendering Unicode symbols as ASCII, if enabled.
+    ascii_fallback: Option<AsciiFallback>,
+    /// Notation for rendering control characters, if enabled.
+    control_chars: bool,
+    /// Margin around the grid in cells.
+    margin: u16,
 }
 
 impl WebGl2BackendOptions {
@@ -82,6 +132,68 @@ impl WebGl2BackendOptions {
         self
     }
 
+    /// Keeps the canvas at the given aspect ratio, e.g. `(4, 3)`.
+    ///
+    /// The canvas is sized to the largest size with this aspect ratio that fits
+    /// into the available area and centered in it, leaving bars on the sides that
+    /// show the background of the parent element. Like the size of the canvas,
+    /// this is computed once from the parent element when the backend is created.
+    ///
+    /// The mouse coordinates are not adjusted, subtract
+    /// [`WebGl2Backend::grid_offset`] from them to get the position within the grid.
+    ///
+    /// This is not supported by the DOM backend, since its grid follows the size
+    /// of the window.
+    pub fn aspect_ratio(mut self, ratio: (u32, u32)) -> Self {
+        self.aspect_ratio = Some(ratio);
+        self
+    }
+
+    /// Surrounds the grid with a margin of the given number of blank cells.
+    ///
+    /// The margin is filled with the canvas padding color and is part of the
+    /// size of the canvas. See [`WebGl2Backend::grid_offset`] for mapping the
+    /// coordinates of the mouse events to the grid.
+    pub fn margin(mut self, cells: u16) -> Self {
+        self.margin = cells;
+        self
+    }
+
+    /// Sets the foreground and background color of the block cursor for cells
+    /// with too little contrast.
+    ///
+    /// The block cursor reverses the colors of the cell below it, which makes it
+    /// nearly invisible if the foreground and background colors are similar.
+    /// In that case, these colors are used instead. Defaults to black on white.
+    pub fn cursor_fallback_colors(mut self, fg: Color, bg: Color) -> Self {
+        self.cursor_fallback_colors = Some((fg, bg));
+        self
+    }
+
+    /// Enables the graphics layer for drawing the [`Graphics`] widget.
+    ///
+    /// The graphics layer is a separate canvas on top of the text grid, which
+    /// allows drawing shapes with pixel precision instead of the sub-cell
+    /// resolution of Braille or block characters.
+    ///
+    /// [`Graphics`]: crate::widgets::Graphics
+    pub fn graphics_layer(mut self, enabled: bool) -> Self {
+        self.graphics_layer = enabled;
+        self
+    }
+
+    /// Sets the time that the canvas size has to be stable for before the
+    /// terminal grid is resized.
+    ///
+    /// Resizing the grid is expensive for large terminals, so intermediate sizes
+    /// (e.g. while dragging the window border) are coalesced and the grid is only
+    /// resized once the size settles. By default, the grid is resized as soon as
+    /// the size did not change between two frames.
+    pub fn resize_debounce(mut self, debounce: Duration) -> Self {
+        self.resize_debounce = debounce;
+        self
+    }
+
     /// Sets the fallback glyph to use for characters not in the font atlas.
     ///
     /// If not set, defaults to a space character (` `).
@@ -90,23 +202,104 @@ impl WebGl2BackendOptions {
         self
     }
 
+    /// Sets the CSS `position` of the canvas.
+    ///
+    /// Use this together with [`WebGl2BackendOptions::z_index`] for placing the terminal
+    /// in front of or behind other content of the page.
+    pub fn position(mut self, position: CssPosition) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Sets the CSS `z-index` of the canvas.
+    ///
+    /// Note that the `z-index` only has an effect on positioned elements,
+    /// see [`WebGl2BackendOptions::position`].
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = Some(z_index);
+        self
+    }
+
+    /// Renders common Unicode symbols as ASCII.
+    ///
+    /// Box-drawing characters are substituted with `+-|`, arrows with `<>^v`
+    /// and so on, which keeps the output readable on targets that can't render
+    /// these glyphs, e.g. if they are missing from the font atlas. See
+    /// [`AsciiFallback`] for the substitutions.
+    pub fn ascii_fallback(mut self, enabled: bool) -> Self {
+        self.ascii_fallback = enabled.then(AsciiFallback::default);
+        self
+    }
+
+    /// Sets a custom substitution table for rendering Unicode symbols as ASCII.
+    ///
+    /// This enables the ASCII fallback, see [`WebGl2BackendOptions::ascii_fallback`].
+    pub fn ascii_fallback_table(mut self, table: AsciiFallback) -> Self {
+        self.ascii_fallback = Some(table);
+        self
+    }
+
+    /// Renders control characters visibly as Unicode control pictures.
+    ///
+    /// This applies to the cells whose symbols contain control characters,
+    /// see [`ControlChars::Pictures`](crate::backend::control::ControlChars::Pictures).
+    pub fn show_control_chars(mut self, enabled: bool) -> Self {
+        self.control_chars = enabled;
+        self
+    }
+
     /// Sets the canvas padding color.
     ///
-    /// The padding area is the space not covered by the terminal grid.
+    /// The padding area is the space not covered by the terminal grid. This is
+    /// also the background color of the cells without a background color.
     pub fn canvas_padding_color(mut self, color: Color) -> Self {
         self.canvas_padding_color = Some(color);
         self
     }
 
+    /// Renders the cells without a background color as transparent.
+    ///
+    /// This allows overlaying the terminal on top of the page content, e.g. a
+    /// background image. The canvas is created with an alpha-enabled context,
+    /// and the unset cells as well as the padding are cleared after rendering
+    /// each frame. The edges of the glyphs on the transparent cells are blended
+    /// with black, so this works best on dark pages.
+    ///
+    /// This overrides [`WebGl2BackendOptions::canvas_padding_color`].
+    pub fn transparent(mut self, enabled: bool) -> Self {
+        self.transparent = enabled;
+        self
+    }
+
     /// Sets the cursor shape to use when cursor is visible.
     pub fn cursor_shape(mut self, shape: CursorShape) -> Self {
         self.cursor_shape = shape;
         self
     }
 
+    /// Shows the cursor at the given position from the start.
+    ///
+    /// The cursor is hidden until the first frame sets its position otherwise.
+    /// Note that ratatui moves or hides the cursor with every drawn frame,
+    /// depending on whether the frame sets a cursor position. Use
+    /// [`CursorShape::None`] to never show a cursor.
+    pub fn cursor_position(mut self, position: impl Into<Position>) -> Self {
+        self.cursor_position = Some(position.into());
+        self
+    }
+
+    /// Sets the interval of toggling a blinking cursor.
+    ///
+    /// This only applies to the blinking [`CursorShape`]s. Defaults to
+    /// [`DEFAULT_BLINK_INTERVAL`].
+    pub fn cursor_blink_interval(mut self, interval: Duration) -> Self {
+        self.cursor_blink_interval = Some(interval);
+        self
+    }
+
     /// Sets a custom font atlas to use for rendering.
     pub fn font_atlas(mut self, atlas: FontAtlasData) -> Self {
-        self.font_atlas = Some(atlas);
+        self.font_atlas = Some(Rc::new(atlas));
         self
     }
 
@@ -132,7 +325,12 @@ impl WebGl2BackendOptions {
 
     /// Enables hyperlinks in the canvas.
     ///
+    /// Any URL in the rendered text (starting with `http://` or `https://`) and the
+    /// text of the [`Hyperlink`] widget is opened in a new tab when clicked.
+    ///
     /// Sets up a default mouse handler using [`WebGl2BackendOptions::on_hyperlink_click`].
+    ///
+    /// [`Hyperlink`]: crate::widgets::Hyperlink
     pub fn enable_hyperlinks(self) -> Self {
         self.on_hyperlink_click(|url| {
             if let Some(w) = window() {
@@ -153,11 +351,22 @@ impl WebGl2BackendOptions {
 
     /// Gets the canvas padding color, defaulting to black if not set.
     fn get_canvas_padding_color(&self) -> u32 {
+        if self.transparent {
+            return TRANSPARENT_KEY;
+        }
         self.canvas_padding_color
             .map(|c| to_rgb(c, 0x000000))
             .unwrap_or(0x000000)
     }
 
+    /// Gets the CSS color of the margin around the grid.
+    fn get_margin_color(&self) -> String {
+        if self.transparent {
+            return "transparent".to_string();
+        }
+        format!("#{:06x}", self.get_canvas_padding_color())
+    }
+
     /// Enables debug API during terminal creation.
     ///
     /// The debug api is accessible from the browser console under `window.__beamterm_debug`.
@@ -167,6 +376,46 @@ impl WebGl2BackendOptions {
     }
 }
 
+/// Information about a font atlas that is available for the [`WebGl2Backend`].
+///
+/// See [`available_atlases`].
+#[derive(Debug)]
+pub struct AtlasInfo {
+    /// Name of the font.
+    pub font_name: String,
+    /// Size of the font, in pixels.
+    pub font_size: f32,
+    /// Size of a single cell, in pixels.
+    pub cell_size: (u32, u32),
+    /// Number of glyphs in the atlas, counting each font style separately.
+    pub glyph_count: usize,
+    /// The atlas data, which can be passed to [`WebGl2BackendOptions::font_atlas`].
+    pub data: FontAtlasData,
+}
+
+impl From<FontAtlasData> for AtlasInfo {
+    fn from(data: FontAtlasData) -> Self {
+        let (cell_width, cell_height) = data.cell_size;
+        Self {
+            font_name: data.font_name.to_string(),
+            font_size: data.font_size,
+            cell_size: (cell_width as u32, cell_height as u32),
+            glyph_count: data.glyphs.len(),
+            data,
+        }
+    }
+}
+
+/// Returns the font atlases that are built into the [`WebGl2Backend`].
+///
+/// The first atlas is the one that is used by default. Characters that are not
+/// covered by the chosen atlas are rendered with the fallback glyph (see
+/// [`WebGl2BackendOptions::fallback_glyph`]), so applications can use this for
+/// picking an atlas by size and coverage, or for warning about missing glyphs.
+pub fn available_atlases() -> Vec<AtlasInfo> {
+    vec![FontAtlasData::default().into()]
+}
+
 /// WebGl2 backend for high-performance terminal rendering.
 ///
 /// This backend renders the terminal buffer onto an HTML canvas element using [WebGL2]
@@ -225,6 +474,9 @@ impl WebGl2BackendOptions {
 /// avg('upload-cells-to-gpu')
 /// avg('sync-terminal-buffer')
 /// ```
+///
+/// The same durations can be read in the application (e.g. for showing them in
+/// a performance overlay) via [`WebGl2Backend::last_frame_timings`].
 pub struct WebGl2Backend {
     /// WebGl2 terminal renderer.
     beamterm: Beamterm,
@@ -232,8 +484,32 @@ pub struct WebGl2Backend {
     options: WebGl2BackendOptions,
     /// Cursor position.
     cursor_position: Option<Position>,
+    /// Blinking state of the cursor.
+    cursor_blink: CursorBlink,
+    /// Color of the cursor, if set.
+    cursor_color: Option<Color>,
+    /// The cells that were last uploaded to the renderer, `None` if unknown.
+    buffer: Vec<Option<Cell>>,
+    /// The cells that changed in the current draw call.
+    changed_cells: BitVec,
+    /// The rows that changed in the current draw call.
+    dirty_rows: BitVec,
+    /// Offset of the letterboxed canvas.
+    letterbox_offset: (u32, u32),
+    /// Graphics layer, if enabled.
+    graphics_layer: Option<GraphicsLayer>,
+    /// Pass for clearing the unset cells, if transparent.
+    transparency: Option<TransparencyPass>,
+    /// Pending canvas size along with the time it was first seen, in milliseconds.
+    pending_resize: Option<((u32, u32), f64)>,
+    /// Whether a redraw is scheduled for applying the pending resize.
+    resize_scheduled: Rc<std::cell::Cell<bool>>,
     /// Performance measurement.
     performance: Option<web_sys::Performance>,
+    /// Duration of the last buffer synchronization, in milliseconds.
+    sync_duration: f64,
+    /// Timings of the last frames, if measured.
+    frame_timings: VecDeque<FrameTimings>,
     /// Hyperlink tracking.
     hyperlink_cells: Option<Rc<RefCell<BitVec>>>,
     /// Mouse handler for hyperlink clicks.
@@ -242,6 +518,33 @@ pub struct WebGl2Backend {
     cursor_over_hyperlink: Option<Rc<RefCell<bool>>>,
     /// Hyperlink click callback.
     _hyperlink_callback: Option<HyperlinkCallback>,
+    /// State of the WebGL context (shared with the context event listeners).
+    context_state: Rc<std::cell::Cell<ContextState>>,
+    /// Whether the background color changed since the last flush.
+    background_changed: bool,
+}
+
+/// Durations of the rendering stages of the [`WebGl2Backend`], in milliseconds.
+///
+/// See [`WebGl2Backend::last_frame_timings`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct FrameTimings {
+    /// Synchronizing Ratatui's cell data with beamterm (`sync-terminal-buffer`).
+    pub sync_terminal_buffer: f64,
+    /// Uploading the cells to the GPU and executing the draw call
+    /// (`webgl-render`).
+    pub webgl_render: f64,
+}
+
+/// State of the WebGL context of the [`WebGl2Backend`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContextState {
+    /// The context is available.
+    Available,
+    /// The context is lost, e.g. due to a GPU reset.
+    Lost,
+    /// The context is restored, but the renderer is not re-created yet.
+    Restored,
 }
 
 impl WebGl2Backend {
@@ -269,8 +572,18 @@ impl WebGl2Backend {
 
         // Parent element of canvas (uses <body> unless specified)
         let parent = get_element_by_id_or_body(options.grid_id.as_ref())?;
-
-        let beamterm = Self::init_beamterm(&mut options, &parent)?;
+        watch_container(&parent);
+
+        let (beamterm, letterbox_offset) = Self::init_beamterm(&mut options, &parent)?;
+        let transparency = options
+            .transparent
+            .then(|| TransparencyPass::new(beamterm.gl()))
+            .transpose()?;
+        watch_focus(beamterm.canvas())?;
+        let graphics_layer = options
+            .graphics_layer
+            .then(|| GraphicsLayer::new(&parent))
+            .transpose()?;
 
         let hyperlink_cells = if options.hyperlink_callback.is_some() {
             let indices = BitVec::repeat(false, beamterm.cell_count());
@@ -307,34 +620,251 @@ impl WebGl2Backend {
             None
         };
 
+        let context_state = Rc::new(std::cell::Cell::new(ContextState::Available));
+        Self::add_context_listeners(beamterm.canvas(), &context_state)?;
+        let (columns, rows) = beamterm.terminal_size();
+        let cursor_position = options
+            .cursor_position
+            .filter(|pos| pos.x < columns && pos.y < rows);
+        record_cursor_position(cursor_position);
+        record_backend_type(BackendType::WebGl2);
+
         Ok(Self {
+            buffer: vec![None; beamterm.cell_count()],
+            changed_cells: BitVec::repeat(false, beamterm.cell_count()),
+            dirty_rows: BitVec::repeat(false, beamterm.terminal_size().1 as usize),
+            context_state,
             beamterm,
-            cursor_position: None,
+            cursor_position,
+            cursor_blink: CursorBlink::new(
+                options
+                    .cursor_blink_interval
+                    .unwrap_or(DEFAULT_BLINK_INTERVAL),
+            ),
+            cursor_color: None,
+            pending_resize: None,
+            resize_scheduled: Rc::new(std::cell::Cell::new(false)),
+            letterbox_offset,
+            graphics_layer,
+            transparency,
             options,
             hyperlink_cells,
             hyperlink_mouse_handler,
             performance,
+            sync_duration: 0.0,
+            frame_timings: VecDeque::with_capacity(FRAME_TIMINGS_SAMPLES),
             cursor_over_hyperlink,
             _hyperlink_callback: hyperlink_callback,
+            background_changed: false,
         })
     }
 
+    /// Adds listeners for the loss and restoration of the WebGL context.
+    fn add_context_listeners(
+        canvas: &HtmlCanvasElement,
+        context_state: &Rc<std::cell::Cell<ContextState>>,
+    ) -> Result<(), Error> {
+        let on_lost = Closure::<dyn FnMut(_)>::new({
+            let context_state = context_state.clone();
+            move |event: web_sys::Event| {
+                // Signals that the context should be restored
+                event.prevent_default();
+                context_state.set(ContextState::Lost);
+            }
+        });
+        add_event_listener(canvas, "webglcontextlost", &Rc::new(on_lost))?;
+        let on_restored = Closure::<dyn FnMut(_)>::new({
+            let context_state = context_state.clone();
+            move |_: web_sys::Event| {
+                context_state.set(ContextState::Restored);
+                request_redraw();
+            }
+        });
+        add_event_listener(canvas, "webglcontextrestored", &Rc::new(on_restored))
+    }
+
+    /// Re-creates the renderer after the WebGL context was restored.
+    ///
+    /// The content is re-uploaded from the cells that were last drawn.
+    fn restore_context(&mut self) -> Result<(), Error> {
+        let canvas = self.beamterm.canvas().clone();
+        self.beamterm = Self::build_beamterm(&self.options, canvas)?;
+        // The resources of the pass are gone along with the old context
+        self.transparency = self
+            .options
+            .transparent
+            .then(|| TransparencyPass::new(self.beamterm.gl()))
+            .transpose()?;
+
+        if let (Some(callback), Some(hyperlink_cells), Some(cursor_state)) = (
+            &self._hyperlink_callback,
+            &self.hyperlink_cells,
+            &self.cursor_over_hyperlink,
+        ) {
+            self.hyperlink_mouse_handler = Some(Self::create_hyperlink_mouse_handler(
+                &self.beamterm,
+                hyperlink_cells.clone(),
+                callback.callback.clone(),
+                cursor_state.clone(),
+            )?);
+        }
+
+        self.upload_buffer()
+    }
+
+    /// Re-uploads the cells that were last drawn, e.g. for applying a new
+    /// background color to the cells without one.
+    fn upload_buffer(&mut self) -> Result<(), Error> {
+        let columns = self.beamterm.terminal_size().0;
+        let width = columns.max(1) as usize;
+        let background = self.options.get_canvas_padding_color();
+        let cells = self.buffer.iter().enumerate().flat_map(|(index, cell)| {
+            cell.iter().flat_map(move |cell| {
+                positioned_cell_data(
+                    (index % width) as u16,
+                    (index / width) as u16,
+                    cell,
+                    columns,
+                    background,
+                )
+            })
+        });
+        self.beamterm.update_cells_by_position(cells)?;
+        Ok(())
+    }
+
+    /// Applies the changed background color.
+    ///
+    /// The padding color can only be set when the renderer is built, so the
+    /// renderer is only re-created if the canvas has a padding around the
+    /// grid. Otherwise, the cells are re-uploaded with the new color.
+    fn apply_background_color(&mut self) -> Result<(), Error> {
+        let (columns, rows) = self.beamterm.terminal_size();
+        let (cell_width, cell_height) = self.beamterm.cell_size();
+        let grid_size = (columns as i32 * cell_width, rows as i32 * cell_height);
+        if self.beamterm.canvas_size() == grid_size {
+            self.upload_buffer()
+        } else {
+            self.restore_context()
+        }
+    }
+
+    /// Sets the background color of the canvas.
+    ///
+    /// This is used for the padding area that is not covered by the terminal
+    /// grid and for the cells without a background color. The color is applied
+    /// on the next flush.
+    pub fn set_background_color(&mut self, color: Color) {
+        self.options.canvas_padding_color = Some(color);
+        self.background_changed = true;
+        // Keep the margin in the background color
+        self.beamterm
+            .canvas()
+            .style()
+            .set_property("border-color", &self.options.get_margin_color())
+            .unwrap_or_default();
+        request_redraw();
+    }
+
     /// Returns the options objects used to create this backend.
     pub fn options(&self) -> &WebGl2BackendOptions {
         &self.options
     }
 
+    /// Returns the offset of the canvas within the available area, in pixels.
+    ///
+    /// This is non-zero when the canvas is letterboxed (see
+    /// [`WebGl2BackendOptions::aspect_ratio`]) and should be subtracted from the mouse
+    /// coordinates to get the position within the canvas.
+    pub fn letterbox_offset(&self) -> (u32, u32) {
+        self.letterbox_offset
+    }
+
+    /// Returns the offset of the grid within the available area, in pixels.
+    ///
+    /// This is the letterbox offset plus the margin around the grid, and
+    /// should be subtracted from the mouse coordinates to get the position
+    /// within the grid, see [`MouseEvent::relative_to`].
+    ///
+    /// [`MouseEvent::relative_to`]: crate::event::MouseEvent::relative_to
+    pub fn grid_offset(&self) -> (u32, u32) {
+        let (cell_width, cell_height) = self.beamterm.cell_size();
+        let margin = self.options.margin as u32;
+        (
+            self.letterbox_offset.0 + margin * cell_width as u32,
+            self.letterbox_offset.1 + margin * cell_height as u32,
+        )
+    }
+
+    /// Returns the durations of the rendering stages, averaged over the last
+    /// 60 frames so that they don't jitter.
+    ///
+    /// Returns `None` if [`WebGl2BackendOptions::measure_performance`] is not
+    /// enabled or no frame was rendered yet.
+    pub fn last_frame_timings(&self) -> Option<FrameTimings> {
+        if self.frame_timings.is_empty() {
+            return None;
+        }
+        let count = self.frame_timings.len() as f64;
+        let (sync, render) =
+            self.frame_timings
+                .iter()
+                .fold((0.0, 0.0), |(sync, render), timings| {
+                    (
+                        sync + timings.sync_terminal_buffer,
+                        render + timings.webgl_render,
+                    )
+                });
+        Some(FrameTimings {
+            sync_terminal_buffer: sync / count,
+            webgl_render: render / count,
+        })
+    }
+
     /// Returns the [`CursorShape`].
     pub fn cursor_shape(&self) -> &CursorShape {
         &self.options.cursor_shape
     }
 
+    /// Returns the underlying [beamterm] renderer.
+    ///
+    /// This is an escape hatch for using beamterm features that are not exposed
+    /// by Ratzilla, e.g. its selection API.
+    ///
+    /// **This is an advanced API.** It is tied to the beamterm version that is
+    /// used by Ratzilla and may change with any release.
+    ///
+    /// [beamterm]: https://github.com/junkdog/beamterm
+    pub fn beamterm(&self) -> &Beamterm {
+        &self.beamterm
+    }
+
+    /// Returns the underlying [beamterm] renderer mutably.
+    ///
+    /// See [`WebGl2Backend::beamterm`]. Note that changing the state of the
+    /// renderer (e.g. resizing it) may break the assumptions of this backend.
+    ///
+    /// [beamterm]: https://github.com/junkdog/beamterm
+    pub fn beamterm_mut(&mut self) -> &mut Beamterm {
+        &mut self.beamterm
+    }
+
     /// Set the [`CursorShape`].
     pub fn set_cursor_shape(mut self, shape: CursorShape) -> Self {
         self.options.cursor_shape = shape;
         self
     }
 
+    /// Sets the background color of the block cursor.
+    ///
+    /// [`Color::Reset`] restores the default cursor, which reverses the colors
+    /// of the cell. The underscore and bar cursors are drawn with the text
+    /// color of the cell regardless.
+    pub fn set_cursor_color(&mut self, color: Color) {
+        self.cursor_color = (color != Color::Reset).then_some(color);
+        request_redraw();
+    }
+
     /// Sets the canvas viewport and projection, reconfigures the terminal grid.
     pub fn resize_canvas(&mut self) -> Result<(), Error> {
         let size_px = self.beamterm.canvas_size();
@@ -342,6 +872,15 @@ impl WebGl2Backend {
         // resize the terminal grid and viewport
         self.beamterm.resize(size_px.0, size_px.1)?;
 
+        // the content of the resized grid is unknown until the next draw call
+        self.buffer.clear();
+        self.buffer.resize(self.beamterm.cell_count(), None);
+        self.changed_cells.clear();
+        self.changed_cells.resize(self.beamterm.cell_count(), false);
+        self.dirty_rows.clear();
+        self.dirty_rows
+            .resize(self.beamterm.terminal_size().1 as usize, false);
+
         // Update mouse handler dimensions if it exists
         if let Some(mouse_handler) = &mut self.hyperlink_mouse_handler {
             let (cols, rows) = self.beamterm.terminal_size();
@@ -368,104 +907,325 @@ impl WebGl2Backend {
     }
 
     /// Checks if the canvas size matches the display size and resizes it if necessary.
+    ///
+    /// The resize is coalesced: it only happens once the display size has been
+    /// stable for at least one frame and the configured
+    /// [`WebGl2BackendOptions::resize_debounce`].
     fn check_canvas_resize(&mut self) -> Result<(), Error> {
         let canvas = self.beamterm.canvas();
-        let display_width = canvas.client_width() as u32;
-        let display_height = canvas.client_height() as u32;
-
-        let buffer_width = canvas.width();
-        let buffer_height = canvas.height();
+        let display_size = (canvas.client_width() as u32, canvas.client_height() as u32);
+        let buffer_size = (canvas.width(), canvas.height());
 
-        if display_width != buffer_width || display_height != buffer_height {
-            canvas.set_width(display_width);
-            canvas.set_height(display_height);
+        if display_size == buffer_size {
+            self.pending_resize = None;
+            return Ok(());
+        }
 
-            self.resize_canvas()?;
+        let now = Date::now();
+        let debounce = self.options.resize_debounce.as_secs_f64() * 1000.0;
+        match self.pending_resize {
+            Some((size, since)) if size == display_size => {
+                if now - since >= debounce {
+                    self.pending_resize = None;
+                    canvas.set_width(display_size.0);
+                    canvas.set_height(display_size.1);
+
+                    self.resize_canvas()?;
+                } else {
+                    self.schedule_resize(since + debounce - now)?;
+                }
+            }
+            _ => {
+                self.pending_resize = Some((display_size, now));
+                self.schedule_resize(debounce)?;
+            }
         }
 
         Ok(())
     }
 
+    /// Requests a redraw after the given delay in milliseconds, so that the
+    /// pending resize is applied even if no other frame is drawn (e.g. in the
+    /// on-demand mode).
+    ///
+    /// Only one redraw is scheduled at a time.
+    fn schedule_resize(&self, delay: f64) -> Result<(), Error> {
+        if self.resize_scheduled.replace(true) {
+            return Ok(());
+        }
+        let resize_scheduled = self.resize_scheduled.clone();
+        let on_timeout = Closure::once_into_js(move || {
+            resize_scheduled.set(false);
+            request_redraw();
+        });
+        get_window()?.set_timeout_with_callback_and_timeout_and_arguments_0(
+            on_timeout.unchecked_ref(),
+            delay.ceil() as i32,
+        )?;
+        Ok(())
+    }
+
     /// Updates the terminal grid with new cell content.
+    ///
+    /// The content only contains the cells that changed since the previous
+    /// frame (as computed by Ratatui's buffer diff). The symbols that can't be
+    /// rendered as is are substituted (see [`WebGl2BackendOptions::ascii_fallback`]
+    /// and [`WebGl2BackendOptions::show_control_chars`]), and the cells that are
+    /// identical to the last uploaded ones are skipped. The rest is stored in the
+    /// buffer and uploaded from there by position, so unchanged cells are never
+    /// re-sent to the GPU.
+    ///
+    /// The rows with changed cells are tracked in a bitmap, so the per-row
+    /// work after the upload (e.g. detecting URLs) only touches those rows.
     fn update_grid<'a, I>(&mut self, content: I) -> Result<(), Error>
     where
         I: Iterator<Item = (u16, u16, &'a Cell)>,
     {
         // If enabled, measures the time taken to synchronize the terminal buffer.
-        self.measure_begin(SYNC_TERMINAL_BUFFER_MARK);
-
-        // If hyperlink support is enabled, we need to track which cells are hyperlinks,
-        // before passing the content to the beamterm renderer.
-        if let Some(hyperlink_cells) = self.hyperlink_cells.as_mut() {
-            let w = self.beamterm.terminal_size().0 as usize;
+        let sync_start = self.measure_begin(SYNC_TERMINAL_BUFFER_MARK);
+
+        let columns = self.beamterm.terminal_size().0;
+        let width = columns as usize;
+        let background = self.options.get_canvas_padding_color();
+        let fallback = self.options.ascii_fallback.as_ref();
+        let control_chars = self.options.control_chars;
+        let mut hyperlink_cells = self
+            .hyperlink_cells
+            .as_ref()
+            .map(|cells| cells.borrow_mut());
+        self.changed_cells.fill(false);
+        self.dirty_rows.fill(false);
+        for (x, y, cell) in content {
+            let cell = match fallback.and_then(|fallback| fallback.apply(cell)) {
+                Some(cell) => Cow::Owned(cell),
+                None => Cow::Borrowed(cell),
+            };
+            let cell = match control_chars.then(|| control_pictures(&cell)).flatten() {
+                Some(cell) => Cow::Owned(cell),
+                None => cell,
+            };
+            // Skip the cells that Ratatui re-reports without any changes
+            let index = y as usize * width + x as usize;
+            let Some(prev) = self.buffer.get_mut(index) else {
+                continue;
+            };
+            if prev.as_ref() == Some(&*cell) {
+                continue;
+            }
 
-            let mut hyperlink_cells = hyperlink_cells.borrow_mut();
+            // Apply any escape sequences (e.g. setting the title) embedded in the cells
+            osc::apply_sequences(cell.symbol());
 
             // Mark any cells that have the hyperlink modifier set (don't blink!).
             // At this stage, we don't care about the actual cell content,
             // as we can extract it on demand.
-            let cells = content.inspect(|(x, y, c)| {
-                let idx = *y as usize * w + *x as usize;
-                let is_hyperlink = c.modifier.contains(HYPERLINK_MODIFIER);
-                hyperlink_cells.set(idx, is_hyperlink);
-            });
-            let cells = cells.map(|(x, y, cell)| (x, y, cell_data(cell)));
-
-            self.beamterm.update_cells_by_position(cells)
-        } else {
-            let cells = content.map(|(x, y, cell)| (x, y, cell_data(cell)));
-            self.beamterm.update_cells_by_position(cells)
+            if let Some(hyperlink_cells) = hyperlink_cells.as_mut() {
+                hyperlink_cells.set(index, cell.modifier.contains(HYPERLINK_MODIFIER));
+            }
+
+            let is_wide = Span::raw(cell.symbol()).width() > 1;
+            *prev = Some(cell.into_owned());
+            // The continuation cell of a wide character is overwritten on upload
+            if is_wide {
+                if let Some(next) = self.buffer.get_mut(index + 1) {
+                    *next = None;
+                }
+            }
+            self.changed_cells.set(index, true);
+            if let Some(mut dirty) = self.dirty_rows.get_mut(y as usize) {
+                *dirty = true;
+            }
+        }
+        drop(hyperlink_cells);
+
+        let buffer = &self.buffer;
+        let cells = self.changed_cells.iter_ones().flat_map(|index| {
+            buffer[index].iter().flat_map(move |cell| {
+                positioned_cell_data(
+                    (index % width) as u16,
+                    (index / width) as u16,
+                    cell,
+                    columns,
+                    background,
+                )
+            })
+        });
+        self.beamterm.update_cells_by_position(cells)?;
+
+        // Mark the URLs in the updated lines as hyperlinks as well
+        if let Some(hyperlink_cells) = &self.hyperlink_cells {
+            let mut hyperlink_cells = hyperlink_cells.borrow_mut();
+            for y in self.dirty_rows.iter_ones() {
+                let start = y * width;
+                let Some(cells) = self.buffer.get(start..start + width) else {
+                    continue;
+                };
+                let line = cells
+                    .iter()
+                    .map(|cell| cell.clone().unwrap_or_default())
+                    .collect::<Vec<_>>();
+                // Unchanged cells might no longer be part of a URL
+                for (x, cell) in line.iter().enumerate() {
+                    hyperlink_cells.set(start + x, cell.modifier.contains(HYPERLINK_MODIFIER));
+                }
+                for (range, _) in detect_urls(&line) {
+                    hyperlink_cells[start + range.start..start + range.end].fill(true);
+                }
+            }
         }
-        .map_err(Error::from)?;
 
-        self.measure_end(SYNC_TERMINAL_BUFFER_MARK);
+        self.sync_duration = self.measure_end(SYNC_TERMINAL_BUFFER_MARK, sync_start);
 
         Ok(())
     }
 
-    /// Toggles the cursor visibility based on its current position.
+    /// Returns the cursor position if the cursor is visible at the moment.
     ///
-    /// If there is no cursor position, it does nothing.
-    fn toggle_cursor(&mut self) {
-        if let Some(pos) = self.cursor_position {
-            self.draw_cursor(pos);
-        }
+    /// A blinking cursor is not visible while it is off.
+    fn visible_cursor(&self) -> Option<Position> {
+        self.cursor_position
+            .filter(|_| self.cursor_blink.is_visible())
     }
 
     /// Draws the cursor at the specified position.
-    fn draw_cursor(&mut self, pos: Position) {
-        if let Some(c) = self
-            .beamterm
-            .grid()
-            .borrow_mut()
-            .cell_data_mut(pos.x, pos.y)
-        {
-            match self.options.cursor_shape {
+    ///
+    /// The cells below the cursor are uploaded with the colors or the style of
+    /// the cursor. Use [`Self::restore_cursor`] to upload them from the buffer
+    /// again.
+    ///
+    /// The cursor covers both cells of a wide character.
+    fn draw_cursor(&mut self, pos: Position) -> Result<(), Error> {
+        let columns = self.cursor_columns(pos);
+        let background = self.options.get_canvas_padding_color();
+        let shape = self.options.cursor_shape.steady();
+        let default_cell = Cell::default();
+        let cell = self
+            .buffer
+            .get(self.buffer_index(Position::new(columns.start, pos.y)))
+            .and_then(Option::as_ref)
+            .unwrap_or(&default_cell);
+        let (fg, bg) = resolve_fg_bg_colors(cell, background);
+        let cursor = self.cursor_colors(fg, bg);
+        let style = into_glyph_bits(cell.modifier);
+        let symbol = osc::visible_text(cell.symbol());
+        let cells = columns.enumerate().filter_map(|(i, x)| {
+            // The continuation cell of a wide character is cleared
+            let symbol = if i == 0 { symbol } else { " " };
+            let data = match shape {
                 CursorShape::SteadyBlock => {
-                    c.flip_colors();
+                    let (fg, bg) = block_cursor_cell_colors(i, cursor);
+                    CellData::new_with_style_bits(symbol, style, fg, bg)
                 }
-                CursorShape::SteadyUnderScore => {
+                // There is no glyph effect for a bar, the underline is the closest
+                CursorShape::SteadyUnderScore | CursorShape::SteadyBar => {
                     // if the overall style is underlined, remove it, otherwise add it
-                    c.style(c.get_style() ^ (GlyphEffect::Underline as u16));
+                    let style = style ^ GlyphEffect::Underline as u16;
+                    CellData::new_with_style_bits(symbol, style, fg, bg)
                 }
-            }
+                _ => return None,
+            };
+            Some((x, pos.y, data))
+        });
+        self.beamterm.update_cells_by_position(cells)?;
+        Ok(())
+    }
+
+    /// Restores the cells below the cursor at the specified position.
+    ///
+    /// The cells are uploaded from the buffer again.
+    fn restore_cursor(&mut self, pos: Position) -> Result<(), Error> {
+        let x = self.cursor_columns(pos).start;
+        let columns = self.beamterm.terminal_size().0;
+        let background = self.options.get_canvas_padding_color();
+        let default_cell = Cell::default();
+        let cell = self
+            .buffer
+            .get(self.buffer_index(Position::new(x, pos.y)))
+            .and_then(Option::as_ref)
+            .unwrap_or(&default_cell);
+        let cells = positioned_cell_data(x, pos.y, cell, columns, background);
+        self.beamterm.update_cells_by_position(cells)?;
+        Ok(())
+    }
+
+    /// Returns the index of the cell at the given position in the buffer.
+    fn buffer_index(&self, pos: Position) -> usize {
+        let width = self.beamterm.terminal_size().0 as usize;
+        pos.y as usize * width + pos.x as usize
+    }
+
+    /// Returns the columns that the cursor at the given position covers.
+    ///
+    /// See [`cursor_cells`] for details.
+    fn cursor_columns(&self, pos: Position) -> std::ops::Range<u16> {
+        let (columns, _) = self.beamterm.terminal_size();
+        let start = pos.y as usize * columns as usize;
+        let end = start + columns as usize;
+        match self.buffer.get(start..end) {
+            Some(line) => cursor_columns(line, pos.x),
+            None => pos.x..pos.x + 1,
         }
     }
 
-    /// Measures the beginning of a performance mark.
-    fn measure_begin(&self, label: &str) {
-        if let Some(performance) = &self.performance {
-            performance.mark(label).unwrap_or_default();
+    /// Returns the colors of the block cursor for a cell with the given
+    /// colors.
+    ///
+    /// The cursor color is used as the background if it is set. Otherwise, the
+    /// cell colors are reversed, or the fallback cursor colors are used if the
+    /// cell colors have too little contrast for the block cursor to be visible.
+    fn cursor_colors(&self, fg: u32, bg: u32) -> (u32, u32) {
+        if let Some(color) = self.cursor_color {
+            return (bg, to_rgb(color, 0xffffff));
         }
+        if contrast_ratio(fg, bg) >= MIN_CURSOR_CONTRAST {
+            return (bg, fg);
+        }
+        let (fallback_fg, fallback_bg) = self
+            .options
+            .cursor_fallback_colors
+            .unwrap_or((Color::Black, Color::White));
+        (to_rgb(fallback_fg, 0x000000), to_rgb(fallback_bg, 0xffffff))
+    }
+
+    /// Measures the beginning of a performance mark.
+    ///
+    /// Returns the start time in milliseconds, if measuring.
+    fn measure_begin(&self, label: &str) -> Option<f64> {
+        let performance = self.performance.as_ref()?;
+        performance.mark(label).unwrap_or_default();
+        Some(performance.now())
     }
 
     /// Measures the end of a performance mark.
-    fn measure_end(&self, label: &str) {
-        if let Some(performance) = &self.performance {
-            performance
-                .measure_with_start_mark(label, label)
-                .unwrap_or_default();
+    ///
+    /// Returns the duration since the given start time in milliseconds.
+    fn measure_end(&self, label: &str, start: Option<f64>) -> f64 {
+        match (&self.performance, start) {
+            (Some(performance), Some(start)) => {
+                let duration = performance.now() - start;
+                performance
+                    .measure_with_start_mark(label, label)
+                    .unwrap_or_default();
+                duration
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Stores the timings of the rendered frame for
+    /// [`WebGl2Backend::last_frame_timings`].
+    fn record_frame_timings(&mut self, webgl_render: f64) {
+        if self.performance.is_none() {
+            return;
         }
+        if self.frame_timings.len() == FRAME_TIMINGS_SAMPLES {
+            self.frame_timings.pop_front();
+        }
+        self.frame_timings.push_back(FrameTimings {
+            // Frames without cell changes don't synchronize the buffer
+            sync_terminal_buffer: std::mem::take(&mut self.sync_duration),
+            webgl_render,
+        });
     }
 
     /// Updates the canvas cursor style efficiently.
@@ -583,20 +1343,83 @@ impl WebGl2Backend {
     }
 
     /// Initializes the beamterm renderer with the given options and parent element.
+    ///
+    /// Returns the renderer along with the offset of the (letterboxed) canvas.
     fn init_beamterm(
         options: &mut WebGl2BackendOptions,
         parent: &Element,
-    ) -> Result<Terminal, Error> {
+    ) -> Result<(Terminal, (u32, u32)), Error> {
         let (width, height) = options
             .size
             .unwrap_or_else(|| (parent.client_width() as u32, parent.client_height() as u32));
+        let (canvas_width, canvas_height) = options.aspect_ratio.map_or((width, height), |ratio| {
+            fit_aspect_ratio((width, height), ratio)
+        });
+
+        let canvas = create_canvas_in_element(parent, canvas_width, canvas_height)?;
+        // Remove the canvas again if the renderer can't be set up, so that
+        // another backend can be created in its place
+        let init = || -> Result<_, Error> {
+            let letterbox_offset = if options.aspect_ratio.is_some() {
+                letterbox_canvas(&canvas, (width, height), (canvas_width, canvas_height))?
+            } else {
+                (0, 0)
+            };
+            set_stacking(&canvas, options.position, options.z_index)?;
+
+            let mut beamterm = Self::build_beamterm(options, canvas.clone())?;
+            if options.margin > 0 {
+                // The cell size is only known once the font atlas is loaded, so
+                // the canvas is shrunk to make room for the margin afterwards
+                let (cell_width, cell_height) = beamterm.cell_size();
+                let margin = (
+                    options.margin as u32 * cell_width as u32,
+                    options.margin as u32 * cell_height as u32,
+                );
+                let size = (
+                    canvas_width.saturating_sub(margin.0 * 2),
+                    canvas_height.saturating_sub(margin.1 * 2),
+                );
+                canvas.set_width(size.0);
+                canvas.set_height(size.1);
+                let style = canvas.style();
+                style.set_property("width", &format!("{}px", size.0))?;
+                style.set_property("height", &format!("{}px", size.1))?;
+                set_margin(&style, margin, &options.get_margin_color())?;
+                beamterm.resize(size.0 as i32, size.1 as i32)?;
+            }
 
-        let canvas = create_canvas_in_element(parent, width, height)?;
+            Ok((beamterm, letterbox_offset))
+        };
+        init().inspect_err(|_| canvas.remove())
+    }
 
+    /// Builds the beamterm renderer for the given canvas.
+    fn build_beamterm(
+        options: &WebGl2BackendOptions,
+        canvas: HtmlCanvasElement,
+    ) -> Result<Terminal, Error> {
+        // The renderer takes the atlas by value, so it is rebuilt from the one
+        // in the options every time the renderer is created
+        let font_atlas = match &options.font_atlas {
+            Some(atlas) => FontAtlasData::from_binary(&atlas.to_binary())
+                .map_err(|e| beamterm_renderer::Error::Data(e.message))?,
+            None => FontAtlasData::default(),
+        };
+        if options.transparent {
+            // The attributes of the first call are used for the lifetime of the
+            // context, so the renderer picks up the alpha-enabled context.
+            // Antialiasing is disabled for copying the frame as is.
+            let attributes = Object::new();
+            Reflect::set(&attributes, &"alpha".into(), &true.into())?;
+            Reflect::set(&attributes, &"premultipliedAlpha".into(), &true.into())?;
+            Reflect::set(&attributes, &"antialias".into(), &false.into())?;
+            canvas.get_context_with_context_options("webgl2", &attributes)?;
+        }
         let beamterm = Beamterm::builder(canvas)
             .canvas_padding_color(options.get_canvas_padding_color())
             .fallback_glyph(options.fallback_glyph.as_ref().unwrap_or(&" ".into()))
-            .font_atlas(options.font_atlas.take().unwrap_or_default());
+            .font_atlas(font_atlas);
 
         let beamterm = if let Some(mode) = options.mouse_selection_mode {
             beamterm.default_mouse_input_handler(mode, true)
@@ -622,6 +1445,11 @@ impl Backend for WebGl2Backend {
     where
         I: Iterator<Item = (u16, u16, &'a Cell)>,
     {
+        // Nothing changed since the previous frame
+        if is_frame_skipped() {
+            return Ok(());
+        }
+
         // we only update when we have new cell data or if the mouse selection
         // handler is enabled (otherwise, we fail to update the visualized selection).
         if content.size_hint().1 != Some(0) || self.options.mouse_selection_mode.is_some() {
@@ -636,22 +1464,62 @@ impl Backend for WebGl2Backend {
     /// This function is called after the [`WebGl2Backend::draw`] function to
     /// actually render the content to the screen.
     fn flush(&mut self) -> IoResult<()> {
+        if is_frame_skipped() {
+            return Ok(());
+        }
+
+        match self.context_state.get() {
+            // Nothing can be rendered until the context is restored
+            ContextState::Lost => return Ok(()),
+            ContextState::Restored => {
+                self.restore_context()?;
+                self.context_state.set(ContextState::Available);
+            }
+            ContextState::Available if self.background_changed => self.apply_background_color()?,
+            ContextState::Available => {}
+        }
+        self.background_changed = false;
+
         self.check_canvas_resize()?;
 
-        self.measure_begin(WEBGL_RENDER_MARK);
+        // The cursor is only drawn while rendering, so it can be switched at any time
+        if let Some(shape) = take_cursor_shape() {
+            self.options.cursor_shape = shape;
+        }
+        self.cursor_blink.update(self.options.cursor_shape)?;
+
+        let render_start = self.measure_begin(WEBGL_RENDER_MARK);
 
         // Flushes GPU buffers and render existing content to the canvas
-        self.toggle_cursor(); // show cursor before rendering
+        let cursor = self.visible_cursor();
+        if let Some(pos) = cursor {
+            self.draw_cursor(pos)?; // show cursor before rendering
+        }
         self.beamterm.render_frame().map_err(Error::from)?;
-        self.toggle_cursor(); // restore cell to previous state
+        if let Some(transparency) = &self.transparency {
+            transparency.apply(self.beamterm.gl(), self.beamterm.canvas_size());
+        }
+        if let Some(pos) = cursor {
+            self.restore_cursor(pos)?; // restore cells to previous state
+        }
+
+        if let Some(graphics_layer) = &self.graphics_layer {
+            let (cell_width, cell_height) = self.beamterm.cell_size();
+            graphics_layer.draw(
+                self.beamterm.canvas(),
+                (cell_width as f64, cell_height as f64),
+            )?;
+        }
 
-        self.measure_end(WEBGL_RENDER_MARK);
+        let render_duration = self.measure_end(WEBGL_RENDER_MARK, render_start);
+        self.record_frame_timings(render_duration);
 
         Ok(())
     }
 
     fn hide_cursor(&mut self) -> IoResult<()> {
         self.cursor_position = None;
+        record_cursor_position(None);
         Ok(())
     }
 
@@ -660,12 +1528,14 @@ impl Backend for WebGl2Backend {
     }
 
     fn clear(&mut self) -> IoResult<()> {
-        let cells = [CellData::new_with_style_bits(" ", 0, 0xffffff, 0x000000)]
+        let background = self.options.get_canvas_padding_color();
+        let cells = [CellData::new_with_style_bits(" ", 0, 0xffffff, background)]
             .into_iter()
             .cycle()
             .take(self.beamterm.cell_count());
 
         self.beamterm.update_cells(cells).map_err(Error::from)?;
+        self.buffer.fill(Some(Cell::default()));
 
         if let Some(hyperlink_cells) = &mut self.hyperlink_cells {
             hyperlink_cells.borrow_mut().clear();
@@ -697,7 +1567,13 @@ impl Backend for WebGl2Backend {
     }
 
     fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> IoResult<()> {
-        self.cursor_position = Some(position.into());
+        let position = position.into();
+        if self.cursor_position != Some(position) {
+            // Keep the cursor visible while it moves
+            self.cursor_blink.reset();
+        }
+        self.cursor_position = Some(position);
+        record_cursor_position(self.cursor_position);
         Ok(())
     }
 
@@ -790,9 +1666,19 @@ fn find_hyperlink_bounds(
 }
 
 /// Resolves foreground and background colors for a [`Cell`].
-fn resolve_fg_bg_colors(cell: &Cell) -> (u32, u32) {
+///
+/// The given background color is used for cells without a background color.
+fn resolve_fg_bg_colors(cell: &Cell, background: u32) -> (u32, u32) {
     let mut fg = to_rgb(cell.fg, 0xffffff);
-    let mut bg = to_rgb(cell.bg, 0x000000);
+    let mut bg = to_rgb(cell.bg, background);
+
+    // Only the cells without a background color are transparent
+    if background == TRANSPARENT_KEY {
+        fg = opaque_color(fg);
+        if cell.bg != Color::Reset {
+            bg = opaque_color(bg);
+        }
+    }
 
     if cell.modifier.contains(Modifier::REVERSED) {
         swap(&mut fg, &mut bg);
@@ -802,9 +1688,34 @@ fn resolve_fg_bg_colors(cell: &Cell) -> (u32, u32) {
 }
 
 /// Converts a [`Cell`] into a [`CellData`] for the beamterm renderer.
-fn cell_data(cell: &Cell) -> CellData<'_> {
-    let (fg, bg) = resolve_fg_bg_colors(cell);
-    CellData::new_with_style_bits(cell.symbol(), into_glyph_bits(cell.modifier), fg, bg)
+fn cell_data(cell: &Cell, background: u32) -> CellData<'_> {
+    let (fg, bg) = resolve_fg_bg_colors(cell, background);
+    CellData::new_with_style_bits(
+        osc::visible_text(cell.symbol()),
+        into_glyph_bits(cell.modifier),
+        fg,
+        bg,
+    )
+}
+
+/// Converts a [`Cell`] at the given position into the [`CellData`] for the
+/// beamterm renderer.
+///
+/// Wide characters (e.g. CJK) occupy two cells, but Ratatui never reports the
+/// continuation cell after them. It is cleared with the background color of the
+/// wide character here, so that it doesn't show stale content.
+fn positioned_cell_data(
+    x: u16,
+    y: u16,
+    cell: &Cell,
+    columns: u16,
+    background: u32,
+) -> impl Iterator<Item = (u16, u16, CellData<'_>)> {
+    let continuation = (Span::raw(cell.symbol()).width() > 1 && x + 1 < columns).then(|| {
+        let (_, bg) = resolve_fg_bg_colors(cell, background);
+        (x + 1, y, CellData::new_with_style_bits(" ", 0, bg, bg))
+    });
+    std::iter::once((x, y, cell_data(cell, background))).chain(continuation)
 }
 
 /// Extracts glyph styling bits from cell modifiers.
@@ -866,6 +1777,38 @@ impl std::fmt::Debug for HyperlinkCallback {
     }
 }
 
+/// Returns the columns of a line that the cursor at the given column covers.
+///
+/// The cells that aren't drawn yet count as narrow. See [`cursor_cells`] for
+/// details.
+fn cursor_columns(line: &[Option<Cell>], x: u16) -> std::ops::Range<u16> {
+    let is_wide = |x: u16| {
+        line.get(x as usize)
+            .and_then(Option::as_ref)
+            .is_some_and(|cell| Span::raw(cell.symbol()).width() > 1)
+    };
+    if is_wide(x) {
+        x..(x + 2).min(line.len() as u16)
+    } else if x > 0 && is_wide(x - 1) {
+        x - 1..x + 1
+    } else {
+        x..x + 1
+    }
+}
+
+/// Returns the colors of a cell that the block cursor covers.
+///
+/// The `index` is the position of the cell within the cursor. The continuation
+/// cell of a wide character (i.e. index 1) is cleared, so it is filled with the
+/// background of the cursor instead of reversing its colors.
+fn block_cursor_cell_colors(index: usize, cursor: (u32, u32)) -> (u32, u32) {
+    if index == 0 {
+        cursor
+    } else {
+        (cursor.1, cursor.1)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -894,4 +1837,52 @@ mod tests {
         .map(|(effect, modifier)| (effect as u16, into_glyph_bits(modifier)))
         .for_each(|(expected, actual)| assert_eq!(expected, actual));
     }
+
+    #[test]
+    fn test_reset_background_color() {
+        let mut cell = Cell::default();
+        assert_eq!(resolve_fg_bg_colors(&cell, 0x202040), (0xffffff, 0x202040));
+        cell.set_bg(Color::Rgb(1, 2, 3));
+        assert_eq!(resolve_fg_bg_colors(&cell, 0x202040), (0xffffff, 0x010203));
+        cell.set_bg(Color::Reset).set_style(Modifier::REVERSED);
+        assert_eq!(resolve_fg_bg_colors(&cell, 0x202040), (0x202040, 0xffffff));
+    }
+
+    #[test]
+    fn test_transparent_background_color() {
+        let mut cell = Cell::default();
+        assert_eq!(
+            resolve_fg_bg_colors(&cell, TRANSPARENT_KEY),
+            (0xffffff, TRANSPARENT_KEY)
+        );
+        // The colors that are set stay opaque
+        cell.set_bg(Color::Rgb(0, 0, 1));
+        assert_ne!(
+            resolve_fg_bg_colors(&cell, TRANSPARENT_KEY).1,
+            TRANSPARENT_KEY
+        );
+    }
+
+    #[test]
+    fn test_cursor_columns() {
+        let line = ["a", "漢", " ", "b"].map(|symbol| {
+            let mut cell = Cell::default();
+            cell.set_symbol(symbol);
+            Some(cell)
+        });
+        assert_eq!(cursor_columns(&line, 0), 0..1);
+        assert_eq!(cursor_columns(&line, 1), 1..3);
+        assert_eq!(cursor_columns(&line, 2), 1..3);
+        assert_eq!(cursor_columns(&line, 3), 3..4);
+        assert_eq!(cursor_columns(&[None, None], 1), 1..2);
+    }
+
+    #[test]
+    fn test_block_cursor_cell_colors() {
+        // The cursor reverses the colors of the wide character
+        let cursor = (0x000000, 0xffffff);
+        assert_eq!(block_cursor_cell_colors(0, cursor), cursor);
+        // The continuation cell is filled with the background of the cursor
+        assert_eq!(block_cursor_cell_colors(1, cursor), (0xffffff, 0xffffff));
+    }
 }
@@ -1,5 +1,7 @@
 use web_sys::wasm_bindgen;
 
+use crate::backend::multi::BackendType;
+
 /// Custom error implementation.
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -43,6 +45,26 @@ pub enum Error {
     /// Failed to retrieve a HTML/js component, such as `Performance`.
     #[error("Failed to retrieve component: {0}")]
     UnableToRetrieveComponent(&'static str),
+
+    /// Unknown backend type.
+    ///
+    /// This error occurs when parsing a [`BackendType`] from an invalid name.
+    ///
+    /// [`BackendType`]: crate::backend::multi::BackendType
+    #[error(
+        "Unknown backend: '{0}' (valid options are: {valid})",
+        valid = BackendType::ALL.map(|backend_type| backend_type.as_str()).join(", ")
+    )]
+    UnknownBackend(String),
+
+    /// Mouse events are not supported.
+    ///
+    /// This error occurs when handling the mouse events with the
+    /// [`WebGl2Backend`] in use.
+    ///
+    /// [`WebGl2Backend`]: crate::WebGl2Backend
+    #[error("Mouse events are not supported by the WebGL2 backend")]
+    MouseEventsNotSupported,
 }
 
 /// Convert [`wasm_bindgen::JsValue`] to [`Error`].
@@ -1,3 +1,47 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashSet, VecDeque},
+    fmt,
+    rc::Rc,
+};
+
+use ratatui::layout::{Position, Rect};
+use web_sys::wasm_bindgen::JsCast;
+
+use crate::backend::{dom::GRID_ATTRIBUTE, utils::performance};
+
+thread_local! {
+    /// Whether the unidentified keys are logged to the console.
+    static KEY_DEBUG: Cell<bool> = const { Cell::new(false) };
+    /// The `key` and `code` of the unidentified keys that were logged.
+    static LOGGED_KEYS: RefCell<HashSet<(String, String)>> = RefCell::new(HashSet::new());
+}
+
+/// Enables or disables logging the unidentified keys.
+///
+/// See [`crate::utils::debug_unidentified_keys`].
+pub(crate) fn set_key_debug(enabled: bool) {
+    KEY_DEBUG.set(enabled);
+}
+
+/// Records the given unidentified key.
+///
+/// Returns `true` if the key wasn't recorded before.
+fn record_unidentified_key(key: &str, code: &str) -> bool {
+    LOGGED_KEYS.with_borrow_mut(|keys| keys.insert((key.to_string(), code.to_string())))
+}
+
+/// Logs the `key` and `code` of the given event to the console, once per
+/// distinct key, if enabled.
+fn log_unidentified_key(event: &web_sys::KeyboardEvent) {
+    let (key, code) = (event.key(), event.code());
+    if KEY_DEBUG.get() && record_unidentified_key(&key, &code) {
+        web_sys::console::warn_1(
+            &format!("ratzilla: unidentified key (key: {key:?}, code: {code:?})").into(),
+        );
+    }
+}
+
 /// A key event.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct KeyEvent {
@@ -9,6 +53,53 @@ pub struct KeyEvent {
     pub alt: bool,
     /// Whether the shift key is pressed.
     pub shift: bool,
+    /// Whether the meta key is pressed.
+    ///
+    /// This is the command key (⌘) on macOS and the Windows key on Windows.
+    pub meta: bool,
+    /// The kind of the event.
+    pub kind: KeyEventKind,
+    /// The state of the lock keys and the location of the key.
+    pub state: KeyEventState,
+}
+
+/// Additional state of a [`KeyEvent`].
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct KeyEventState {
+    /// Whether caps lock is on.
+    pub caps_lock: bool,
+    /// Whether num lock is on.
+    pub num_lock: bool,
+    /// Whether the key is on the numeric keypad.
+    pub keypad: bool,
+}
+
+/// Get the [`KeyEventState`] of a [`web_sys::KeyboardEvent`].
+impl From<&web_sys::KeyboardEvent> for KeyEventState {
+    fn from(event: &web_sys::KeyboardEvent) -> Self {
+        KeyEventState {
+            caps_lock: event.get_modifier_state("CapsLock"),
+            num_lock: event.get_modifier_state("NumLock"),
+            keypad: event.location() == web_sys::KeyboardEvent::DOM_KEY_LOCATION_NUMPAD,
+        }
+    }
+}
+
+/// The kind of a [`KeyEvent`].
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum KeyEventKind {
+    /// The key was pressed.
+    #[default]
+    Press,
+    /// The key is held down and the press is repeated.
+    Repeat,
+    /// The key was released.
+    ///
+    /// Release events are only reported to the handlers that opt in, see
+    /// [`on_key_event_kinds`].
+    ///
+    /// [`on_key_event_kinds`]: crate::WebRenderer::on_key_event_kinds
+    Release,
 }
 
 /// A mouse movement event.
@@ -30,17 +121,804 @@ pub struct MouseEvent {
     pub shift: bool,
 }
 
+/// A text input from a virtual (on-screen) keyboard.
+///
+/// See [`WebRenderer::on_text_input`].
+///
+/// [`WebRenderer::on_text_input`]: crate::WebRenderer::on_text_input
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TextInput {
+    /// Inserts the text at the cursor.
+    Insert(String),
+    /// Inserts a line break.
+    Enter,
+    /// Deletes the character before the cursor.
+    Backspace,
+    /// Deletes the character after the cursor.
+    Delete,
+}
+
+impl TextInput {
+    /// Converts the type of a [`web_sys::InputEvent`] with its data.
+    ///
+    /// Returns `None` for input types that are not supported.
+    pub(crate) fn from_input_type(input_type: &str, data: Option<String>) -> Option<Self> {
+        match input_type {
+            "insertText" | "insertReplacementText" => data.map(TextInput::Insert),
+            "insertLineBreak" | "insertParagraph" => Some(TextInput::Enter),
+            "deleteContentBackward" => Some(TextInput::Backspace),
+            "deleteContentForward" => Some(TextInput::Delete),
+            _ => None,
+        }
+    }
+}
+
+/// A paste event.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PasteEvent {
+    /// The pasted content as plain text.
+    ///
+    /// If the clipboard only contains HTML, this is the text content of the
+    /// markup (see [`html_to_text`]). Line endings are normalized to `\n`.
+    pub text: String,
+    /// The pasted content as HTML, if the source provided rich content.
+    pub html: Option<String>,
+}
+
+/// Rounding mode for converting pixel coordinates to grid coordinates.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum CoordinateRounding {
+    /// Rounds down to the cell that contains the position.
+    #[default]
+    Floor,
+    /// Rounds to the nearest cell boundary.
+    ///
+    /// A position past the midpoint of a cell maps to the next cell, which is
+    /// useful for selecting the boundaries between cells (e.g. for selections
+    /// or column-resize handles).
+    Nearest,
+}
+
+/// Converts pixel coordinates to grid (cell) coordinates.
+///
+/// The `cell_size` is the width and height of a single cell in pixels.
+pub fn pixels_to_grid_coords(
+    x: f64,
+    y: f64,
+    cell_size: (f64, f64),
+    rounding: CoordinateRounding,
+) -> (u16, u16) {
+    let (col, row) = (x / cell_size.0, y / cell_size.1);
+    let (col, row) = match rounding {
+        CoordinateRounding::Floor => (col.floor(), row.floor()),
+        CoordinateRounding::Nearest => (col.round(), row.round()),
+    };
+    (col.max(0.0) as u16, row.max(0.0) as u16)
+}
+
+impl MouseEvent {
+    /// Returns the mouse event with the given offset in pixels subtracted
+    /// from its position.
+    ///
+    /// This makes the position relative to the grid of a backend, e.g. with
+    /// the offset of [`DomBackend::grid_offset`].
+    ///
+    /// [`DomBackend::grid_offset`]: crate::DomBackend::grid_offset
+    pub fn relative_to(mut self, offset: (u32, u32)) -> Self {
+        self.x = self.x.saturating_sub(offset.0);
+        self.y = self.y.saturating_sub(offset.1);
+        self
+    }
+
+    /// Converts the position of the mouse event to grid coordinates.
+    ///
+    /// See [`pixels_to_grid_coords`] for details.
+    pub fn grid_coords(&self, cell_size: (f64, f64), rounding: CoordinateRounding) -> (u16, u16) {
+        pixels_to_grid_coords(self.x as f64, self.y as f64, cell_size, rounding)
+    }
+}
+
+/// A mouse event handler of a [`MouseRegions`].
+type MouseHandler = Box<dyn FnMut(MouseEvent)>;
+
+/// Dispatches mouse events to the handlers of the regions they occur in.
+///
+/// Instead of matching the coordinates in a single [`on_mouse_event`] callback,
+/// regions of the grid (e.g. buttons) can be registered along with their own
+/// handlers. Each event is dispatched to the topmost region that contains it,
+/// which is the one that was registered last. Events outside of all regions go
+/// to the optional fallback handler.
+///
+/// This works the same on all backends, since the dispatching is done based
+/// on the grid coordinates of the events.
+///
+/// [`on_mouse_event`]: crate::WebRenderer::on_mouse_event
+#[derive(Default)]
+pub struct MouseRegions {
+    /// The registered regions and their handlers, from bottom to top.
+    regions: Vec<(Rect, MouseHandler)>,
+    /// Handler for the events outside of all regions.
+    fallback: Option<MouseHandler>,
+}
+
+impl fmt::Debug for MouseRegions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MouseRegions")
+            .field(
+                "regions",
+                &self
+                    .regions
+                    .iter()
+                    .map(|(area, _)| area)
+                    .collect::<Vec<_>>(),
+            )
+            .field("fallback", &self.fallback.is_some())
+            .finish()
+    }
+}
+
+impl MouseRegions {
+    /// Constructs a new, empty [`MouseRegions`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a region with the given handler.
+    ///
+    /// The region is placed on top of the previously registered regions.
+    pub fn register<F>(&mut self, area: Rect, handler: F)
+    where
+        F: FnMut(MouseEvent) + 'static,
+    {
+        self.regions.push((area, Box::new(handler)));
+    }
+
+    /// Sets the handler for the events outside of all regions.
+    pub fn set_fallback<F>(&mut self, handler: F)
+    where
+        F: FnMut(MouseEvent) + 'static,
+    {
+        self.fallback = Some(Box::new(handler));
+    }
+
+    /// Removes all regions, e.g. before registering the regions of a new frame.
+    ///
+    /// The fallback handler is kept.
+    pub fn clear(&mut self) {
+        self.regions.clear();
+    }
+
+    /// Dispatches the given event to the handler of the topmost matching region.
+    ///
+    /// The `cell_size` is used for converting the position of the event to grid
+    /// coordinates, see [`MouseEvent::grid_coords`]. Returns `true` if the event
+    /// was handled by a region or by the fallback handler.
+    pub fn dispatch(&mut self, event: MouseEvent, cell_size: (f64, f64)) -> bool {
+        let (x, y) = event.grid_coords(cell_size, CoordinateRounding::Floor);
+        let position = Position::new(x, y);
+        let handler = self
+            .regions
+            .iter_mut()
+            .rev()
+            .find(|(area, _)| area.contains(position))
+            .map(|(_, handler)| handler)
+            .or(self.fallback.as_mut());
+        match handler {
+            Some(handler) => {
+                handler(event);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Maps key events to application-defined actions.
+///
+/// Instead of matching the key codes in a single [`on_key_event`] callback,
+/// the key bindings can be declared in one place and the actions looked up
+/// for each event:
+///
+/// ```rust
+/// use ratzilla::event::{KeyCode, KeyEvent, Keymap};
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Action {
+///     Quit,
+///     Search,
+///     Ignore,
+/// }
+///
+/// let mut keymap = Keymap::new();
+/// keymap.bind(KeyCode::Char('q'), Action::Quit);
+/// keymap.bind(
+///     KeyEvent {
+///         ctrl: true,
+///         ..KeyCode::Char('k').into()
+///     },
+///     Action::Search,
+/// );
+/// keymap.set_fallback(Action::Ignore);
+///
+/// assert_eq!(keymap.get(&KeyCode::Char('q').into()), Some(&Action::Quit));
+/// assert_eq!(keymap.get(&KeyCode::Char('k').into()), Some(&Action::Ignore));
+/// ```
+///
+/// The modifiers of a binding have to match exactly, except for the shift key
+/// of character keys, since it is already reflected by the character itself
+/// (e.g. `Shift+q` is reported as `Q`).
+///
+/// [`on_key_event`]: crate::WebRenderer::on_key_event
+#[derive(Debug, Clone)]
+pub struct Keymap<A> {
+    /// The key bindings and their actions.
+    bindings: Vec<(KeyEvent, A)>,
+    /// Action for the keys without a binding.
+    fallback: Option<A>,
+}
+
+impl<A> Default for Keymap<A> {
+    fn default() -> Self {
+        Self {
+            bindings: Vec::new(),
+            fallback: None,
+        }
+    }
+}
+
+impl<A> Keymap<A> {
+    /// Constructs a new, empty [`Keymap`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Binds the given key (along with its modifiers) to an action.
+    ///
+    /// Replaces the previous action of the key, if any.
+    pub fn bind<K>(&mut self, key: K, action: A)
+    where
+        K: Into<KeyEvent>,
+    {
+        let key = key.into();
+        self.bindings
+            .retain(|(binding, _)| !binding_matches(binding, &key));
+        self.bindings.push((key, action));
+    }
+
+    /// Sets the action for the keys without a binding.
+    pub fn set_fallback(&mut self, action: A) {
+        self.fallback = Some(action);
+    }
+
+    /// Returns the action of the given key event.
+    ///
+    /// Returns the fallback action if no binding matches.
+    pub fn get(&self, event: &KeyEvent) -> Option<&A> {
+        self.bindings
+            .iter()
+            .find(|(binding, _)| binding_matches(binding, event))
+            .map(|(_, action)| action)
+            .or(self.fallback.as_ref())
+    }
+}
+
+/// Returns `true` if the event matches the key binding.
+///
+/// See [`Keymap`] for the rules of matching the modifiers.
+fn binding_matches(binding: &KeyEvent, event: &KeyEvent) -> bool {
+    binding.code == event.code
+        && binding.ctrl == event.ctrl
+        && binding.alt == event.alt
+        && binding.meta == event.meta
+        && (binding.shift == event.shift || matches!(event.code, KeyCode::Char(_)))
+}
+
+/// The result of feeding a key event to a [`ChordDetector`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chord<'a, A> {
+    /// The key completes the chord with the given action.
+    Matched(&'a A),
+    /// The key is part of a chord that is not complete yet.
+    Pending,
+    /// The key is not part of a chord and can be handled as usual.
+    Unmatched,
+}
+
+/// Detects key chords, i.e. sequences of keys like `gg` or `Ctrl+X Ctrl+S`.
+///
+/// The key events are fed to the detector, which tracks the keys of the chord
+/// in progress and reports the action once a chord is complete. The keys of a
+/// chord have to be pressed within the timeout of each other, otherwise the
+/// chord starts over.
+///
+/// ```rust no_run
+/// use ratzilla::event::{Chord, ChordDetector, KeyCode, KeyEvent};
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Action {
+///     Top,
+///     Save,
+/// }
+///
+/// let ctrl = |c| KeyEvent {
+///     ctrl: true,
+///     ..KeyCode::Char(c).into()
+/// };
+/// let mut chords = ChordDetector::new();
+/// chords.bind([KeyCode::Char('g'), KeyCode::Char('g')], Action::Top);
+/// chords.bind([ctrl('x'), ctrl('s')], Action::Save);
+///
+/// // In the key event callback:
+/// let event = KeyEvent::from(KeyCode::Char('g'));
+/// match chords.feed(&event) {
+///     Chord::Matched(action) => println!("{action:?}"),
+///     Chord::Pending => {}
+///     Chord::Unmatched => println!("{:?}", event.code),
+/// }
+/// ```
+///
+/// The keys are matched like the bindings of a [`Keymap`]. A chord that is a
+/// prefix of another one (e.g. `g` and `gg`) is matched as soon as it is
+/// complete, so the longer chord is never matched. If a key doesn't continue
+/// the chord in progress, the previous keys are discarded and the key is
+/// matched on its own.
+#[derive(Debug, Clone)]
+pub struct ChordDetector<A> {
+    /// The key sequences of the chords and their actions.
+    chords: Vec<(Vec<KeyEvent>, A)>,
+    /// The keys of the chord in progress.
+    pending: Vec<KeyEvent>,
+    /// The time of the last key in milliseconds.
+    last_key: Option<f64>,
+    /// The maximum time between the keys of a chord in milliseconds.
+    timeout: f64,
+}
+
+impl<A> Default for ChordDetector<A> {
+    fn default() -> Self {
+        Self {
+            chords: Vec::new(),
+            pending: Vec::new(),
+            last_key: None,
+            timeout: 1000.0,
+        }
+    }
+}
+
+impl<A> ChordDetector<A> {
+    /// Constructs a new [`ChordDetector`] without any chords.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum time between the keys of a chord.
+    ///
+    /// Defaults to one second.
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = timeout.as_secs_f64() * 1000.0;
+        self
+    }
+
+    /// Binds the given sequence of keys (along with their modifiers) to an
+    /// action.
+    ///
+    /// Replaces the previous action of the sequence, if any. Empty sequences
+    /// are ignored.
+    pub fn bind<I, K>(&mut self, keys: I, action: A)
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<KeyEvent>,
+    {
+        let keys: Vec<KeyEvent> = keys.into_iter().map(Into::into).collect();
+        if keys.is_empty() {
+            return;
+        }
+        self.chords.retain(|(chord, _)| {
+            chord.len() != keys.len()
+                || !chord.iter().zip(&keys).all(|(a, b)| binding_matches(a, b))
+        });
+        self.chords.push((keys, action));
+    }
+
+    /// Feeds a key event to the detector.
+    ///
+    /// Release events and unidentified keys (e.g. the modifier keys) don't
+    /// affect the chord in progress.
+    pub fn feed(&mut self, event: &KeyEvent) -> Chord<'_, A> {
+        let now = performance()
+            .map(|performance| performance.now())
+            .unwrap_or_default();
+        self.feed_at(event, now)
+    }
+
+    /// Returns `true` if a chord is in progress.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Discards the chord in progress, e.g. when pressing `Esc`.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.last_key = None;
+    }
+
+    /// Feeds a key event that occurred at the given time in milliseconds.
+    fn feed_at(&mut self, event: &KeyEvent, now: f64) -> Chord<'_, A> {
+        if event.kind == KeyEventKind::Release || event.code == KeyCode::Unidentified {
+            return if self.is_pending() {
+                Chord::Pending
+            } else {
+                Chord::Unmatched
+            };
+        }
+        if self.last_key.is_some_and(|last| now - last > self.timeout) {
+            self.pending.clear();
+        }
+        self.last_key = Some(now);
+        self.pending.push(event.clone());
+        loop {
+            if let Some(index) = self.completed_chord() {
+                self.pending.clear();
+                return Chord::Matched(&self.chords[index].1);
+            }
+            if self.chords.iter().any(|(chord, _)| self.continues(chord)) {
+                return Chord::Pending;
+            }
+            // Start over with the last key if it doesn't continue the chord
+            if self.pending.len() == 1 {
+                self.pending.clear();
+                return Chord::Unmatched;
+            }
+            self.pending.drain(..self.pending.len() - 1);
+        }
+    }
+
+    /// Returns the index of the chord that the pending keys complete.
+    fn completed_chord(&self) -> Option<usize> {
+        self.chords
+            .iter()
+            .position(|(chord, _)| chord.len() == self.pending.len() && self.continues(chord))
+    }
+
+    /// Returns `true` if the pending keys are the start of the given chord.
+    fn continues(&self, chord: &[KeyEvent]) -> bool {
+        chord.len() >= self.pending.len()
+            && chord
+                .iter()
+                .zip(&self.pending)
+                .all(|(binding, event)| binding_matches(binding, event))
+    }
+}
+
+/// Identifier of a key or paste handler.
+///
+/// See [`WebRenderer::add_key_handler`] and [`WebRenderer::on_paste_event`].
+///
+/// [`WebRenderer::add_key_handler`]: crate::WebRenderer::add_key_handler
+/// [`WebRenderer::on_paste_event`]: crate::WebRenderer::on_paste_event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HandlerId(pub(crate) u64);
+
+/// How an [`EventQueue`] handles new events when it is full.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Drops the oldest queued event to make room for the new one.
+    #[default]
+    DropOldest,
+    /// Drops the new event.
+    DropNewest,
+    /// Drops the new event if it repeats the last queued one (e.g. a held
+    /// key), otherwise drops the oldest queued event.
+    CoalesceRepeats,
+}
+
+/// A bounded queue of input events.
+///
+/// Instead of handling the events in the event callbacks, they can be queued
+/// and processed in the render callback. If the application can't keep up
+/// (e.g. when a key is held down during heavy per-frame work), the queue is
+/// capped at its capacity and the overflowing events are handled according to
+/// the [`OverflowPolicy`], which keeps the memory usage and the input lag
+/// bounded.
+///
+/// The queue is a cheaply cloneable handle, so the event callback and the
+/// render callback can share it. See [`WebRenderer::key_event_queue`].
+///
+/// [`WebRenderer::key_event_queue`]: crate::WebRenderer::key_event_queue
+#[derive(Debug)]
+pub struct EventQueue<T> {
+    /// The queued events.
+    events: Rc<RefCell<VecDeque<T>>>,
+    /// Maximum number of queued events.
+    capacity: usize,
+    /// How new events are handled when the queue is full.
+    policy: OverflowPolicy,
+}
+
+impl<T> Clone for EventQueue<T> {
+    fn clone(&self) -> Self {
+        Self {
+            events: self.events.clone(),
+            capacity: self.capacity,
+            policy: self.policy,
+        }
+    }
+}
+
+impl<T: PartialEq> EventQueue<T> {
+    /// Constructs a new [`EventQueue`] with the given capacity and overflow policy.
+    ///
+    /// The capacity is at least one event.
+    pub fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        Self {
+            events: Rc::new(RefCell::new(VecDeque::new())),
+            capacity: capacity.max(1),
+            policy,
+        }
+    }
+
+    /// Queues the given event.
+    ///
+    /// Returns `false` if the new event was dropped due to the overflow policy.
+    pub fn push(&self, event: T) -> bool {
+        let mut events = self.events.borrow_mut();
+        if events.len() >= self.capacity {
+            match self.policy {
+                OverflowPolicy::DropNewest => return false,
+                OverflowPolicy::CoalesceRepeats if events.back() == Some(&event) => return false,
+                OverflowPolicy::DropOldest | OverflowPolicy::CoalesceRepeats => {
+                    events.pop_front();
+                }
+            }
+        }
+        events.push_back(event);
+        true
+    }
+
+    /// Removes and returns the oldest queued event.
+    pub fn pop(&self) -> Option<T> {
+        self.events.borrow_mut().pop_front()
+    }
+
+    /// Removes and returns all queued events, from oldest to newest.
+    pub fn drain(&self) -> Vec<T> {
+        self.events.borrow_mut().drain(..).collect()
+    }
+
+    /// Returns the number of queued events.
+    pub fn len(&self) -> usize {
+        self.events.borrow().len()
+    }
+
+    /// Returns `true` if there are no queued events.
+    pub fn is_empty(&self) -> bool {
+        self.events.borrow().is_empty()
+    }
+}
+
+/// `deltaMode` of wheel events with deltas in lines.
+const DOM_DELTA_LINE: u32 = 1;
+
+/// `deltaMode` of wheel events with deltas in pages.
+const DOM_DELTA_PAGE: u32 = 2;
+
+/// Number of pixels that count as one line of scrolling.
+pub(crate) const PIXELS_PER_LINE: f64 = 20.0;
+
+/// Number of lines that count as one page of scrolling.
+const LINES_PER_PAGE: f64 = 24.0;
+
+/// Converts the delta of a wheel event to lines.
+///
+/// Depending on the device, browsers report the deltas in pixels, lines or
+/// pages, as indicated by the [`deltaMode`] of the event.
+///
+/// [`deltaMode`]: https://developer.mozilla.org/en-US/docs/Web/API/WheelEvent/deltaMode
+pub(crate) fn normalize_wheel_delta(delta: f64, delta_mode: u32) -> f64 {
+    match delta_mode {
+        DOM_DELTA_LINE => delta,
+        DOM_DELTA_PAGE => delta * LINES_PER_PAGE,
+        _ => delta / PIXELS_PER_LINE,
+    }
+}
+
+/// Converts the delta of a wheel event to whole pixels.
+fn wheel_delta_pixels(delta: f64, delta_mode: u32) -> i32 {
+    (normalize_wheel_delta(delta, delta_mode) * PIXELS_PER_LINE).round() as i32
+}
+
+/// A scroll offset with momentum.
+///
+/// Instead of jumping by the scrolled lines, the offset accelerates and then
+/// coasts to a stop, so flicking through a long list with a trackpad or a touch
+/// screen feels natural. Each scrolled delta adds to the velocity, which decays
+/// over time while [`ScrollInertia::update`] is called on every frame. The total
+/// distance travelled matches the scrolled lines.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use ratzilla::event::ScrollInertia;
+///
+/// let mut scroll = ScrollInertia::new().bounds(0.0, 100.0);
+///
+/// // In the scroll handler, e.g. `ratzilla::utils::on_scroll`:
+/// scroll.scroll(3.0);
+///
+/// // In the render callback:
+/// let offset = scroll.update(Duration::from_millis(16));
+/// assert!(offset > 0.0 && offset < 3.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScrollInertia {
+    /// The current offset, in lines.
+    offset: f64,
+    /// The current velocity, in lines per second.
+    velocity: f64,
+    /// Fraction of the velocity that remains after one second.
+    friction: f64,
+    /// Minimum and maximum offset.
+    bounds: Option<(f64, f64)>,
+}
+
+impl Default for ScrollInertia {
+    fn default() -> Self {
+        Self {
+            offset: 0.0,
+            velocity: 0.0,
+            friction: 0.01,
+            bounds: None,
+        }
+    }
+}
+
+impl ScrollInertia {
+    /// Velocity below which the scrolling stops, in lines per second.
+    const MIN_VELOCITY: f64 = 0.1;
+
+    /// Constructs a new [`ScrollInertia`] at offset zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the fraction of the velocity that remains after one second.
+    ///
+    /// Lower values stop the scrolling sooner. Defaults to `0.01`.
+    pub fn friction(mut self, friction: f64) -> Self {
+        self.friction = friction.clamp(f64::EPSILON, 1.0 - f64::EPSILON);
+        self
+    }
+
+    /// Sets the minimum and maximum offset, e.g. `(0.0, items - height)`.
+    pub fn bounds(mut self, min: f64, max: f64) -> Self {
+        self.set_bounds(min, max);
+        self
+    }
+
+    /// Changes the minimum and maximum offset, e.g. when the content changes.
+    pub fn set_bounds(&mut self, min: f64, max: f64) {
+        self.bounds = Some((min, max.max(min)));
+        self.clamp();
+    }
+
+    /// Scrolls by the given number of lines.
+    ///
+    /// Positive values scroll down.
+    pub fn scroll(&mut self, delta: f64) {
+        // A delta adds the velocity that travels exactly the delta until it stops
+        self.velocity += delta * self.decay_rate();
+    }
+
+    /// Advances the momentum by the given time and returns the new offset.
+    pub fn update(&mut self, elapsed: std::time::Duration) -> f64 {
+        let rate = self.decay_rate();
+        let decay = (-rate * elapsed.as_secs_f64()).exp();
+        self.offset += self.velocity * (1.0 - decay) / rate;
+        self.velocity *= decay;
+        if self.velocity.abs() < Self::MIN_VELOCITY {
+            self.velocity = 0.0;
+        }
+        self.clamp();
+        self.offset
+    }
+
+    /// Returns the current offset, in lines.
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+
+    /// Jumps to the given offset and stops the scrolling.
+    pub fn set_offset(&mut self, offset: f64) {
+        self.offset = offset;
+        self.velocity = 0.0;
+        self.clamp();
+    }
+
+    /// Returns `true` if the offset is still changing.
+    pub fn is_moving(&self) -> bool {
+        self.velocity != 0.0
+    }
+
+    /// Returns the exponential decay rate of the velocity, per second.
+    fn decay_rate(&self) -> f64 {
+        -self.friction.ln()
+    }
+
+    /// Keeps the offset within the bounds, stopping at the edges.
+    fn clamp(&mut self) {
+        if let Some((min, max)) = self.bounds {
+            if self.offset < min || self.offset > max {
+                self.offset = self.offset.clamp(min, max);
+                self.velocity = 0.0;
+            }
+        }
+    }
+}
+
 /// Convert a [`web_sys::KeyboardEvent`] to a [`KeyEvent`].
+///
+/// The kind of the event is derived from its type (`keydown` or `keyup`) and
+/// the `repeat` property.
 impl From<web_sys::KeyboardEvent> for KeyEvent {
     fn from(event: web_sys::KeyboardEvent) -> Self {
         let ctrl = event.ctrl_key();
         let alt = event.alt_key();
         let shift = event.shift_key();
+        let meta = event.meta_key();
+        let state = KeyEventState::from(&event);
+        let kind = if event.type_() == "keyup" {
+            KeyEventKind::Release
+        } else if event.repeat() {
+            KeyEventKind::Repeat
+        } else {
+            KeyEventKind::Press
+        };
         KeyEvent {
             code: event.into(),
             ctrl,
             alt,
             shift,
+            meta,
+            kind,
+            state,
+        }
+    }
+}
+
+impl KeyEvent {
+    /// Returns `true` if the key is held down and the press is repeated.
+    ///
+    /// This can be used for ignoring the auto-repeat of held keys, e.g. for
+    /// menu navigation.
+    pub fn is_repeat(&self) -> bool {
+        self.kind == KeyEventKind::Repeat
+    }
+
+    /// Returns `true` if this is the combo for submitting text on the given
+    /// platform, i.e. `Cmd+Enter` on Apple platforms and `Ctrl+Enter` elsewhere.
+    pub(crate) fn is_submit_combo_on(&self, apple: bool) -> bool {
+        let modifier = if apple { self.meta } else { self.ctrl };
+        self.code == KeyCode::Enter && modifier && !self.alt
+    }
+
+    /// Returns `true` if this is the find-in-page shortcut on the given
+    /// platform, i.e. `Cmd+F` on Apple platforms and `Ctrl+F` elsewhere.
+    pub(crate) fn is_find_combo_on(&self, apple: bool) -> bool {
+        let modifier = if apple { self.meta } else { self.ctrl };
+        matches!(self.code, KeyCode::Char('f' | 'F')) && modifier && !self.alt
+    }
+}
+
+/// Convert a [`KeyCode`] to a key press [`KeyEvent`] without any modifiers.
+impl From<KeyCode> for KeyEvent {
+    fn from(code: KeyCode) -> Self {
+        KeyEvent {
+            code,
+            ctrl: false,
+            alt: false,
+            shift: false,
+            meta: false,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::default(),
         }
     }
 }
@@ -82,6 +960,34 @@ pub enum KeyCode {
     Unidentified,
 }
 
+impl KeyCode {
+    /// Returns the value of the [`key`] property of a keyboard event with this key code.
+    ///
+    /// This is the inverse of the conversion from [`web_sys::KeyboardEvent`].
+    ///
+    /// [`key`]: https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key
+    pub(crate) fn to_key(&self) -> String {
+        match self {
+            KeyCode::Char(char) => char.to_string(),
+            KeyCode::F(n) => format!("F{n}"),
+            KeyCode::Backspace => "Backspace".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Left => "ArrowLeft".to_string(),
+            KeyCode::Right => "ArrowRight".to_string(),
+            KeyCode::Up => "ArrowUp".to_string(),
+            KeyCode::Down => "ArrowDown".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::Delete => "Delete".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::PageUp => "PageUp".to_string(),
+            KeyCode::PageDown => "PageDown".to_string(),
+            KeyCode::Esc => "Escape".to_string(),
+            KeyCode::Unidentified => "Unidentified".to_string(),
+        }
+    }
+}
+
 /// Convert a [`web_sys::KeyboardEvent`] to a [`KeyCode`].
 impl From<web_sys::KeyboardEvent> for KeyCode {
     fn from(event: web_sys::KeyboardEvent) -> Self {
@@ -91,10 +997,11 @@ impl From<web_sys::KeyboardEvent> for KeyCode {
             if let Some(char) = char {
                 return KeyCode::Char(char);
             } else {
+                log_unidentified_key(&event);
                 return KeyCode::Unidentified;
             }
         }
-        match key.as_str() {
+        let code = match key.as_str() {
             "F1" => KeyCode::F(1),
             "F2" => KeyCode::F(2),
             "F3" => KeyCode::F(3),
@@ -121,7 +1028,11 @@ impl From<web_sys::KeyboardEvent> for KeyCode {
             "PageDown" => KeyCode::PageDown,
             "Escape" => KeyCode::Esc,
             _ => KeyCode::Unidentified,
+        };
+        if code == KeyCode::Unidentified {
+            log_unidentified_key(&event);
         }
+        code
     }
 }
 
@@ -151,6 +1062,20 @@ pub enum MouseEventKind {
     Pressed,
     /// Mouse button released
     Released,
+    /// Mouse wheel scrolled
+    ///
+    /// The deltas are given in pixels, regardless of whether the browser
+    /// reports them in pixels, lines or pages. Positive values scroll right
+    /// and down.
+    ///
+    /// For scrolling by lines, including touch screens, see
+    /// [`on_scroll`](crate::utils::on_scroll).
+    Wheel {
+        /// The horizontal scroll amount in pixels.
+        delta_x: i32,
+        /// The vertical scroll amount in pixels.
+        delta_y: i32,
+    },
     /// Unidentified mouse event
     Unidentified,
 }
@@ -161,17 +1086,34 @@ impl From<web_sys::MouseEvent> for MouseEvent {
         let ctrl = event.ctrl_key();
         let alt = event.alt_key();
         let shift = event.shift_key();
-        let event_type = event.type_().into();
+        let event_type = match event.dyn_ref::<web_sys::WheelEvent>() {
+            Some(wheel) => MouseEventKind::Wheel {
+                delta_x: wheel_delta_pixels(wheel.delta_x(), wheel.delta_mode()),
+                delta_y: wheel_delta_pixels(wheel.delta_y(), wheel.delta_mode()),
+            },
+            None => event.type_().into(),
+        };
+        // Account for the scroll position of a scrollable grid
+        let (scroll_x, scroll_y) = event
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+            .and_then(|target| target.closest(&format!("[{GRID_ATTRIBUTE}]")).ok())
+            .flatten()
+            .map(|grid| (grid.scroll_left(), grid.scroll_top()))
+            .unwrap_or_default();
         MouseEvent {
             // Button is only valid if it is a mousedown or mouseup event.
-            button: if event_type == MouseEventKind::Moved {
+            button: if matches!(
+                event_type,
+                MouseEventKind::Moved | MouseEventKind::Wheel { .. }
+            ) {
                 MouseButton::Unidentified
             } else {
                 event.button().into()
             },
             event: event_type,
-            x: event.client_x() as u32,
-            y: event.client_y() as u32,
+            x: (event.client_x() + scroll_x) as u32,
+            y: (event.client_y() + scroll_y) as u32,
             ctrl,
             alt,
             shift,
@@ -179,6 +1121,74 @@ impl From<web_sys::MouseEvent> for MouseEvent {
     }
 }
 
+/// Convert a [`web_sys::ClipboardEvent`] to a [`PasteEvent`].
+impl From<web_sys::ClipboardEvent> for PasteEvent {
+    fn from(event: web_sys::ClipboardEvent) -> Self {
+        let data = event.clipboard_data();
+        let get_data = |format| {
+            data.as_ref()
+                .and_then(|data| data.get_data(format).ok())
+                .filter(|data| !data.is_empty())
+        };
+        let html = get_data("text/html");
+        let text = get_data("text/plain")
+            .or_else(|| html.as_deref().map(html_to_text))
+            .unwrap_or_default();
+        PasteEvent {
+            text: text.replace("\r\n", "\n").replace('\r', "\n"),
+            html,
+        }
+    }
+}
+
+/// Converts HTML markup to plain text.
+///
+/// Tags are removed, line breaks are inserted for `<br>` and the end of block
+/// elements, and the common character entities are decoded. This is not a full
+/// HTML parser, but it is enough to recover readable text from pasted content.
+pub fn html_to_text(html: &str) -> String {
+    const BLOCK_TAGS: [&str; 7] = ["/p", "/div", "/li", "/tr", "/h", "/pre", "/blockquote"];
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = rest[start + 1..start + end].trim().to_ascii_lowercase();
+        let name = tag.split_whitespace().next().unwrap_or_default();
+        if name.trim_end_matches('/') == "br"
+            || BLOCK_TAGS.iter().any(|block| name.starts_with(block))
+        {
+            text.push('\n');
+        }
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+    text.trim_end_matches('\n')
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Convert a [`MouseButton`] to the button number of a [`web_sys::MouseEvent`].
+impl From<&MouseButton> for i16 {
+    fn from(button: &MouseButton) -> Self {
+        match button {
+            MouseButton::Left => 0,
+            MouseButton::Middle => 1,
+            MouseButton::Right => 2,
+            MouseButton::Back => 3,
+            MouseButton::Forward => 4,
+            MouseButton::Unidentified => -1,
+        }
+    }
+}
+
 /// Convert a [`web_sys::MouseEvent`] to a [`MouseButton`].
 impl From<i16> for MouseButton {
     fn from(button: i16) -> Self {
@@ -193,6 +1203,21 @@ impl From<i16> for MouseButton {
     }
 }
 
+impl MouseEventKind {
+    /// Returns the type of the [`web_sys::MouseEvent`] with this kind.
+    ///
+    /// Returns `None` for [`MouseEventKind::Unidentified`].
+    pub(crate) fn event_type(&self) -> Option<&'static str> {
+        match self {
+            MouseEventKind::Moved => Some("mousemove"),
+            MouseEventKind::Pressed => Some("mousedown"),
+            MouseEventKind::Released => Some("mouseup"),
+            MouseEventKind::Wheel { .. } => Some("wheel"),
+            MouseEventKind::Unidentified => None,
+        }
+    }
+}
+
 /// Convert a [`web_sys::MouseEvent`] to a [`MouseEventKind`].
 impl From<String> for MouseEventKind {
     fn from(event: String) -> Self {
@@ -205,3 +1230,237 @@ impl From<String> for MouseEventKind {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_chord_detector() {
+        let ctrl = |c| KeyEvent {
+            ctrl: true,
+            ..KeyCode::Char(c).into()
+        };
+        let mut chords = ChordDetector::new();
+        chords.bind([KeyCode::Char('g'), KeyCode::Char('g')], "top");
+        chords.bind([ctrl('x'), ctrl('s')], "save");
+        let g = KeyEvent::from(KeyCode::Char('g'));
+
+        assert_eq!(chords.feed_at(&g, 0.0), Chord::Pending);
+        assert_eq!(chords.feed_at(&g, 100.0), Chord::Matched(&"top"));
+        assert!(!chords.is_pending());
+
+        // The modifier keys don't interrupt the chord
+        assert_eq!(chords.feed_at(&ctrl('x'), 200.0), Chord::Pending);
+        let control = KeyEvent::from(KeyCode::Unidentified);
+        assert_eq!(chords.feed_at(&control, 250.0), Chord::Pending);
+        assert_eq!(chords.feed_at(&ctrl('s'), 300.0), Chord::Matched(&"save"));
+
+        // Starts over after the timeout
+        assert_eq!(chords.feed_at(&g, 400.0), Chord::Pending);
+        assert_eq!(chords.feed_at(&g, 2000.0), Chord::Pending);
+        assert_eq!(chords.feed_at(&g, 2100.0), Chord::Matched(&"top"));
+
+        // A key that breaks the chord is matched on its own
+        assert_eq!(chords.feed_at(&g, 2200.0), Chord::Pending);
+        assert_eq!(
+            chords.feed_at(&KeyCode::Char('j').into(), 2300.0),
+            Chord::Unmatched
+        );
+        assert_eq!(chords.feed_at(&g, 2400.0), Chord::Pending);
+        assert_eq!(chords.feed_at(&ctrl('x'), 2500.0), Chord::Pending);
+        assert_eq!(chords.feed_at(&ctrl('s'), 2600.0), Chord::Matched(&"save"));
+    }
+
+    #[test]
+    fn test_record_unidentified_key() {
+        assert!(record_unidentified_key("AudioVolumeUp", "AudioVolumeUp"));
+        assert!(!record_unidentified_key("AudioVolumeUp", "AudioVolumeUp"));
+        assert!(record_unidentified_key("Clear", "Numpad5"));
+    }
+
+    #[test]
+    fn test_key_code_to_key() {
+        assert_eq!(KeyCode::Char('a').to_key(), "a");
+        assert_eq!(KeyCode::F(5).to_key(), "F5");
+        assert_eq!(KeyCode::Esc.to_key(), "Escape");
+    }
+
+    #[test]
+    fn test_mouse_regions() {
+        use std::{cell::RefCell, rc::Rc};
+
+        let clicks = Rc::new(RefCell::new(Vec::new()));
+        let mut regions = MouseRegions::new();
+        for (name, area) in [
+            ("first", Rect::new(0, 0, 5, 1)),
+            ("second", Rect::new(10, 0, 5, 1)),
+            ("overlay", Rect::new(3, 0, 4, 1)),
+        ] {
+            let clicks = clicks.clone();
+            regions.register(area, move |_| clicks.borrow_mut().push(name));
+        }
+
+        let event = |x| MouseEvent {
+            button: MouseButton::Left,
+            event: MouseEventKind::Pressed,
+            x,
+            y: 0,
+            ctrl: false,
+            alt: false,
+            shift: false,
+        };
+        let cell_size = (10.0, 20.0);
+        assert!(regions.dispatch(event(5), cell_size));
+        assert!(regions.dispatch(event(105), cell_size));
+        assert!(regions.dispatch(event(45), cell_size));
+        assert!(!regions.dispatch(event(85), cell_size));
+        assert_eq!(*clicks.borrow(), vec!["first", "second", "overlay"]);
+    }
+
+    #[test]
+    fn test_pixels_to_grid_coords() {
+        let cell_size = (10.0, 20.0);
+        assert_eq!(
+            pixels_to_grid_coords(15.1, 39.0, cell_size, CoordinateRounding::Floor),
+            (1, 1)
+        );
+        assert_eq!(
+            pixels_to_grid_coords(15.1, 39.0, cell_size, CoordinateRounding::Nearest),
+            (2, 2)
+        );
+        assert_eq!(
+            pixels_to_grid_coords(-5.0, 0.0, cell_size, CoordinateRounding::Floor),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        assert_eq!(html_to_text("plain"), "plain");
+        assert_eq!(
+            html_to_text("<p class=\"x\">Hello <b>world</b></p><p>a &amp; b</p>"),
+            "Hello world\na & b"
+        );
+        assert_eq!(html_to_text("one<br>two<br/>three"), "one\ntwo\nthree");
+        assert_eq!(html_to_text("1 &lt; 2"), "1 < 2");
+    }
+
+    #[test]
+    fn test_keymap() {
+        let mut keymap = Keymap::new();
+        keymap.bind(KeyCode::Char('q'), "quit");
+        keymap.bind(
+            KeyEvent {
+                ctrl: true,
+                ..KeyCode::Char('k').into()
+            },
+            "search",
+        );
+        keymap.bind(
+            KeyEvent {
+                shift: true,
+                ..KeyCode::Tab.into()
+            },
+            "previous",
+        );
+        assert_eq!(keymap.get(&KeyCode::Char('q').into()), Some(&"quit"));
+        assert_eq!(keymap.get(&KeyCode::Char('k').into()), None);
+        let ctrl_k = KeyEvent {
+            ctrl: true,
+            ..KeyCode::Char('k').into()
+        };
+        assert_eq!(keymap.get(&ctrl_k), Some(&"search"));
+        let shift_k = KeyEvent {
+            ctrl: true,
+            shift: true,
+            ..KeyCode::Char('k').into()
+        };
+        assert_eq!(keymap.get(&shift_k), Some(&"search"));
+        assert_eq!(keymap.get(&KeyCode::Tab.into()), None);
+
+        keymap.bind(KeyCode::Char('q'), "exit");
+        keymap.set_fallback("ignore");
+        assert_eq!(keymap.get(&KeyCode::Char('q').into()), Some(&"exit"));
+        assert_eq!(keymap.get(&KeyCode::Esc.into()), Some(&"ignore"));
+    }
+
+    #[test]
+    fn test_normalize_wheel_delta() {
+        assert_eq!(normalize_wheel_delta(60.0, 0), 3.0);
+        assert_eq!(normalize_wheel_delta(-3.0, DOM_DELTA_LINE), -3.0);
+        assert_eq!(normalize_wheel_delta(1.0, DOM_DELTA_PAGE), LINES_PER_PAGE);
+        assert_eq!(wheel_delta_pixels(-3.0, DOM_DELTA_LINE), -60);
+        assert_eq!(wheel_delta_pixels(4.4, 0), 4);
+    }
+
+    #[test]
+    fn test_scroll_inertia() {
+        let mut scroll = ScrollInertia::new().bounds(0.0, 10.0);
+        scroll.scroll(3.0);
+        let offset = scroll.update(Duration::from_millis(100));
+        assert!(offset > 0.0 && offset < 3.0);
+        assert!(scroll.is_moving());
+        let offset = scroll.update(Duration::from_secs(10));
+        assert!((offset - 3.0).abs() < 0.1);
+        assert!(!scroll.is_moving());
+
+        // Stops at the bounds
+        scroll.scroll(100.0);
+        assert_eq!(scroll.update(Duration::from_secs(10)), 10.0);
+        scroll.scroll(-100.0);
+        assert_eq!(scroll.update(Duration::from_secs(10)), 0.0);
+    }
+
+    #[test]
+    fn test_is_submit_combo() {
+        let enter = KeyEvent::from(KeyCode::Enter);
+        let ctrl_enter = KeyEvent {
+            ctrl: true,
+            ..enter.clone()
+        };
+        let cmd_enter = KeyEvent {
+            meta: true,
+            ..enter.clone()
+        };
+        assert!(!enter.is_submit_combo_on(false));
+        assert!(!enter.is_submit_combo_on(true));
+        assert!(ctrl_enter.is_submit_combo_on(false));
+        assert!(!ctrl_enter.is_submit_combo_on(true));
+        assert!(cmd_enter.is_submit_combo_on(true));
+        assert!(!cmd_enter.is_submit_combo_on(false));
+    }
+
+    #[test]
+    fn test_is_find_combo() {
+        let ctrl_f = KeyEvent {
+            ctrl: true,
+            ..KeyEvent::from(KeyCode::Char('f'))
+        };
+        assert!(ctrl_f.is_find_combo_on(false));
+        assert!(!ctrl_f.is_find_combo_on(true));
+        assert!(!KeyEvent::from(KeyCode::Char('f')).is_find_combo_on(false));
+    }
+
+    #[test]
+    fn test_event_queue() {
+        let queue = EventQueue::new(2, OverflowPolicy::DropOldest);
+        assert!(queue.push(1) && queue.push(2) && queue.push(3));
+        assert_eq!(queue.drain(), vec![2, 3]);
+        assert!(queue.is_empty());
+
+        let queue = EventQueue::new(2, OverflowPolicy::DropNewest);
+        assert!(queue.push(1) && queue.push(2));
+        assert!(!queue.push(3));
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.len(), 1);
+
+        let queue = EventQueue::new(2, OverflowPolicy::CoalesceRepeats);
+        assert!(queue.push(1) && queue.push(2));
+        assert!(!queue.push(2));
+        assert!(queue.push(3));
+        assert_eq!(queue.drain(), vec![2, 3]);
+    }
+}
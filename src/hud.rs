@@ -0,0 +1,158 @@
+use std::{cell::RefCell, fmt::Write, rc::Rc};
+
+use web_sys::{
+    js_sys::Reflect,
+    wasm_bindgen::{prelude::*, JsCast},
+    HtmlElement, Performance,
+};
+
+use crate::{
+    backend::utils::{get_document, performance},
+    error::Error,
+    event::{KeyCode, KeyEvent},
+    render::add_event_listener,
+};
+
+/// ID of the performance HUD element.
+const HUD_ID: &str = "ratzilla-perf-hud";
+
+/// Interval between the updates of the HUD, in milliseconds.
+const UPDATE_INTERVAL: f64 = 250.0;
+
+/// Performance measures of the [`WebGl2Backend`] along with their labels.
+///
+/// [`WebGl2Backend`]: crate::WebGl2Backend
+const MEASURES: [(&str, &str); 2] = [("sync-terminal-buffer", "sync"), ("webgl-render", "render")];
+
+/// Performance overlay that shows the frame rate and the backend timings.
+struct PerfHud {
+    /// The overlay element.
+    element: HtmlElement,
+    /// Performance API for timing the frames.
+    performance: Performance,
+    /// The key that toggles the overlay.
+    toggle_key: KeyCode,
+    /// Whether the overlay is visible.
+    visible: bool,
+    /// Number of frames since the last update.
+    frames: u32,
+    /// Time of the last update, in milliseconds.
+    last_update: f64,
+}
+
+impl PerfHud {
+    /// Toggles the visibility of the overlay.
+    fn toggle(&mut self) {
+        self.visible = !self.visible;
+        self.frames = 0;
+        self.last_update = self.performance.now();
+        let _ = self
+            .element
+            .style()
+            .set_property("display", if self.visible { "block" } else { "none" });
+    }
+
+    /// Records a frame and updates the overlay once per [`UPDATE_INTERVAL`].
+    fn record_frame(&mut self) {
+        if !self.visible {
+            return;
+        }
+        self.frames += 1;
+        let now = self.performance.now();
+        let elapsed = now - self.last_update;
+        if elapsed < UPDATE_INTERVAL {
+            return;
+        }
+
+        let mut text = format!("FPS: {:.1}", self.frames as f64 * 1000.0 / elapsed);
+        for (name, label) in MEASURES {
+            if let Some(duration) = self.last_measure(name) {
+                let _ = write!(text, " | {label}: {duration:.2}ms");
+            }
+        }
+        self.element.set_text_content(Some(&text));
+        self.frames = 0;
+        self.last_update = now;
+    }
+
+    /// Returns the duration of the last performance measure with the given name.
+    fn last_measure(&self, name: &str) -> Option<f64> {
+        let entries = self
+            .performance
+            .get_entries_by_name_with_entry_type(name, "measure");
+        let entry = entries.at(-1);
+        Reflect::get(&entry, &JsValue::from_str("duration"))
+            .ok()?
+            .as_f64()
+    }
+}
+
+thread_local! {
+    static PERF_HUD: RefCell<Option<PerfHud>> = const { RefCell::new(None) };
+}
+
+/// Enables the performance HUD, toggled with the given key.
+///
+/// See [`WebRenderer::enable_perf_hud`].
+///
+/// [`WebRenderer::enable_perf_hud`]: crate::WebRenderer::enable_perf_hud
+pub(crate) fn enable(toggle_key: KeyCode) -> Result<(), Error> {
+    // Replace the existing overlay, if any, which already has a key listener
+    let listening = PERF_HUD.with(|hud| hud.borrow().is_some());
+    disable();
+    let document = get_document()?;
+    let element = document
+        .create_element("div")?
+        .dyn_into::<HtmlElement>()
+        .map_err(|_| Error::UnableToRetrieveComponent("HtmlElement"))?;
+    element.set_id(HUD_ID);
+    element.set_attribute(
+        "style",
+        "display: none; position: fixed; top: 0; right: 0; \
+         padding: 4px 8px; background: rgba(0, 0, 0, 0.75); color: #4ade80; \
+         font: 12px monospace; pointer-events: none; z-index: 2147483647;",
+    )?;
+    document
+        .body()
+        .ok_or(Error::UnableToRetrieveBody)?
+        .append_child(&element)?;
+
+    let performance = performance()?;
+    PERF_HUD.with(|hud| {
+        *hud.borrow_mut() = Some(PerfHud {
+            element,
+            last_update: performance.now(),
+            performance,
+            toggle_key,
+            visible: false,
+            frames: 0,
+        })
+    });
+
+    if listening {
+        return Ok(());
+    }
+    let closure = Closure::<dyn FnMut(_)>::new(move |event: web_sys::Event| {
+        let event: KeyEvent = event.unchecked_into::<web_sys::KeyboardEvent>().into();
+        PERF_HUD.with(|hud| {
+            if let Some(hud) = hud.borrow_mut().as_mut() {
+                if event.code == hud.toggle_key {
+                    hud.toggle();
+                }
+            }
+        });
+    });
+    add_event_listener(&document, "keydown", &Rc::new(closure))
+}
+
+/// Records a frame for the performance HUD, if it is enabled.
+pub(crate) fn record_frame() {
+    PERF_HUD.with(|hud| hud.borrow_mut().as_mut().map(PerfHud::record_frame));
+}
+
+/// Removes the performance HUD.
+pub(crate) fn disable() {
+    if let Some(hud) = PERF_HUD.with(|hud| hud.take()) {
+        hud.element.remove();
+    }
+}
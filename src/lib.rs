@@ -16,9 +16,21 @@ pub mod widgets;
 /// Backend.
 pub mod backend;
 
+/// Animation helpers.
+pub mod animation;
+
 /// Rendering.
 mod render;
 
+/// Performance HUD.
+mod hud;
+
+/// ANSI escape sequence serialization.
+mod ansi;
+
+/// Application entry points.
+mod run;
+
 // Re-export ratatui crate.
 pub use ratatui;
 
@@ -29,6 +41,8 @@ pub use backend::{
     canvas::CanvasBackend,
     cursor::CursorShape,
     dom::DomBackend,
+    stacking::CssPosition,
     webgl2::{SelectionMode, WebGl2Backend},
 };
 pub use render::WebRenderer;
+pub use run::{run, run_with_events};
@@ -1,8 +1,823 @@
-use ratatui::{prelude::Backend, Frame, Terminal};
-use std::{cell::RefCell, rc::Rc};
-use web_sys::{wasm_bindgen::prelude::*, window};
+use ratatui::{
+    buffer::{Buffer, Cell as BufferCell},
+    layout::Position,
+    prelude::Backend,
+    style::Color,
+    Frame, Terminal,
+};
+use std::{
+    cell::{Cell, RefCell},
+    hash::{DefaultHasher, Hash, Hasher},
+    rc::Rc,
+    time::Duration,
+};
+use web_sys::{
+    js_sys::{Array, Reflect},
+    wasm_bindgen::prelude::*,
+    window, Element, EventTarget, Node,
+};
 
-use crate::event::{KeyEvent, MouseEvent};
+use crate::{
+    ansi,
+    backend::{
+        cursor::CursorShape,
+        multi::BackendType,
+        utils::{get_document, performance},
+    },
+    error::Error,
+    event::{
+        EventQueue, HandlerId, KeyCode, KeyEvent, KeyEventKind, MouseEvent, OverflowPolicy,
+        PasteEvent, TextInput,
+    },
+    hud, utils,
+    widgets::graphics,
+};
+
+/// Time in milliseconds that a frame of [`WebRenderer::draw_web_capped`] may
+/// be early, so that the jitter of the animation frames doesn't skip frames.
+const CAPPED_FRAME_TOLERANCE_MS: f64 = 2.0;
+
+/// Events that count as user activity for [`WebRenderer::draw_web_idle`] and
+/// [`WebRenderer::draw_web_retained`].
+const ACTIVITY_EVENTS: [&str; 6] = [
+    "keydown",
+    "mousedown",
+    "mousemove",
+    "mouseup",
+    "wheel",
+    "touchstart",
+];
+
+/// An event listener that was registered via [`add_event_listener`].
+struct Listener {
+    /// The target that the listener was added to.
+    target: EventTarget,
+    /// The event type, e.g. `keydown`.
+    event: &'static str,
+    /// The callback of the listener.
+    closure: Rc<Closure<dyn FnMut(web_sys::Event)>>,
+}
+
+/// A console function that was patched via [`patch_console`].
+struct ConsolePatch {
+    /// The console object.
+    console: JsValue,
+    /// The name of the function, e.g. `log`.
+    name: &'static str,
+    /// The original function, which is restored by [`shutdown_all`].
+    original: JsValue,
+    /// The closure that is called by the patched function.
+    _closure: Closure<dyn FnMut(Array)>,
+}
+
+/// State of a render loop.
+#[derive(Default)]
+struct RenderLoop {
+    /// The callback that is called on every animation frame.
+    callback: Option<Closure<dyn FnMut()>>,
+    /// The ID of the pending animation frame request.
+    frame_id: Option<i32>,
+    /// The container elements of the backends that the loop renders to.
+    containers: Vec<Element>,
+    /// Whether the loop only renders when a redraw is requested.
+    on_demand: bool,
+}
+
+impl RenderLoop {
+    /// Returns `true` if any of the containers was removed from the document.
+    fn is_detached(&self) -> bool {
+        self.containers
+            .iter()
+            .any(|container| !container.is_connected())
+    }
+
+    /// Stops the render loop because its container was removed.
+    ///
+    /// The callback owns the terminal, so it is dropped once the current
+    /// animation frame is done instead of from within itself.
+    fn stop_detached(render_loop: &RefCell<Self>) {
+        let callback = render_loop.borrow_mut().callback.take();
+        render_loop.borrow_mut().frame_id = None;
+        REGISTRY.with(|registry| {
+            registry
+                .borrow_mut()
+                .render_loops
+                .retain(|other| !std::ptr::eq(&**other, render_loop))
+        });
+        if let Some(window) = window() {
+            let drop_callback = Closure::once_into_js(move || drop(callback));
+            let _ = window.set_timeout_with_callback(drop_callback.unchecked_ref());
+        }
+        // The callback is taken out while it runs since it may shut down
+        if let Some(mut on_removed) = ON_CONTAINER_REMOVED.take() {
+            on_removed();
+            let running = REGISTRY.with(|registry| !registry.borrow().render_loops.is_empty());
+            if running {
+                ON_CONTAINER_REMOVED.with_borrow_mut(|callback| {
+                    callback.get_or_insert(on_removed);
+                });
+            }
+        }
+    }
+
+    /// Requests an animation frame for the render loop, unless it is shut down.
+    fn request_animation_frame(render_loop: &RefCell<Self>) {
+        let render_loop = &mut *render_loop.borrow_mut();
+        if let Some(callback) = &render_loop.callback {
+            let frame_id = window()
+                .expect("window is available")
+                .request_animation_frame(callback.as_ref().unchecked_ref())
+                .expect("failed to request animation frame");
+            render_loop.frame_id = Some(frame_id);
+        }
+    }
+
+    /// Requests an animation frame for the render loop, unless one is pending.
+    fn request_redraw(render_loop: &RefCell<Self>) {
+        if render_loop.borrow().frame_id.is_none() {
+            Self::request_animation_frame(render_loop);
+        }
+    }
+}
+
+/// The callback of a key handler, which returns `true` if it consumed the event.
+type KeyHandlerCallback = Rc<RefCell<dyn FnMut(&KeyEvent) -> bool>>;
+
+/// A key handler that was added via [`add_key_handler`].
+struct KeyHandler {
+    /// The identifier of the handler.
+    id: HandlerId,
+    /// The priority of the handler.
+    priority: i32,
+    /// The callback of the handler.
+    callback: KeyHandlerCallback,
+}
+
+/// Key handlers that the key events are dispatched to.
+#[derive(Default)]
+struct KeyHandlers {
+    /// Registered handlers, ordered by descending priority.
+    handlers: Vec<KeyHandler>,
+    /// The identifier of the next handler.
+    next_id: u64,
+    /// Whether the `keydown` listener for dispatching the events is added.
+    listening: bool,
+}
+
+/// The callback of an event listener.
+pub(crate) type ListenerClosure = Rc<Closure<dyn FnMut(web_sys::Event)>>;
+
+/// Paste handlers that were added via [`WebRenderer::on_paste_event`].
+#[derive(Default)]
+struct PasteHandlers {
+    /// The `paste` listeners of the handlers.
+    handlers: Vec<(HandlerId, ListenerClosure)>,
+    /// The identifier of the next handler.
+    next_id: u64,
+}
+
+/// Event listeners and render loops that are torn down by [`shutdown_all`].
+#[derive(Default)]
+struct Registry {
+    /// Registered event listeners.
+    listeners: Vec<Listener>,
+    /// Running render loops.
+    render_loops: Vec<Rc<RefCell<RenderLoop>>>,
+    /// Patched console functions.
+    console_patches: Vec<ConsolePatch>,
+}
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::default();
+    /// The buffer of the last frame that was drawn by a render loop.
+    static LAST_FRAME: RefCell<Option<Buffer>> = const { RefCell::new(None) };
+    /// How long drawing the last frame took.
+    static LAST_FRAME_DURATION: Cell<Option<Duration>> = const { Cell::new(None) };
+    /// The start time of the last frame in milliseconds.
+    static LAST_FRAME_START: Cell<Option<f64>> = const { Cell::new(None) };
+    /// The time since the last frame, in frames at the reference rate.
+    static FRAME_STEP: Cell<f64> = const { Cell::new(1.0) };
+    /// Handlers of the key events, see [`WebRenderer::add_key_handler`].
+    static KEY_HANDLERS: RefCell<KeyHandlers> = RefCell::default();
+    /// Handlers of the paste events, see [`WebRenderer::on_paste_event`].
+    static PASTE_HANDLERS: RefCell<PasteHandlers> = RefCell::default();
+    /// The grid and canvas elements of the backends, see [`watch_focus`].
+    static FOCUS_TARGETS: RefCell<Vec<Element>> = const { RefCell::new(Vec::new()) };
+    /// The `focusin` and `focusout` listeners of the focus handler, see
+    /// [`WebRenderer::on_focus_event`].
+    static FOCUS_HANDLER: RefCell<Option<(ListenerClosure, ListenerClosure)>> = const { RefCell::new(None) };
+    /// Handler of the resize events, see [`WebRenderer::on_resize`].
+    static RESIZE_HANDLER: RefCell<Option<ResizeHandler>> = const { RefCell::new(None) };
+    /// The type of the backend that was created last.
+    static BACKEND_TYPE: Cell<Option<BackendType>> = const { Cell::new(None) };
+    /// The position of the visible cursor, as reported by the backend.
+    static CURSOR_POSITION: Cell<Option<Position>> = const { Cell::new(None) };
+    /// Whether the backends skip the current frame, see
+    /// [`WebRenderer::draw_web_retained`].
+    static SKIP_FRAME: Cell<bool> = const { Cell::new(false) };
+    /// Whether a frame was requested since the last frame, see
+    /// [`request_redraw`].
+    static REDRAW_REQUESTED: Cell<bool> = const { Cell::new(false) };
+    /// Whether the render loop draws continuously, so that the cursor can
+    /// blink.
+    static CURSOR_BLINKING: Cell<bool> = const { Cell::new(true) };
+    /// The cursor shape to switch to in the next frame.
+    static CURSOR_SHAPE: Cell<Option<CursorShape>> = const { Cell::new(None) };
+    /// The containers of the backends that aren't rendered to by a loop yet.
+    static PENDING_CONTAINERS: RefCell<Vec<Element>> = const { RefCell::new(Vec::new()) };
+    /// Callback for when the container of a render loop is removed.
+    static ON_CONTAINER_REMOVED: RefCell<Option<Box<dyn FnMut()>>> = const { RefCell::new(None) };
+}
+
+/// Watches the given container element of a backend.
+///
+/// The next render loop that is started renders to this backend, so the loop
+/// is stopped once the container is removed from the document.
+pub(crate) fn watch_container(container: &Element) {
+    PENDING_CONTAINERS.with_borrow_mut(|containers| containers.push(container.clone()));
+}
+
+/// Tracks the focus of the given grid or canvas element of a backend.
+///
+/// Once a focus handler is set (see [`WebRenderer::on_focus_event`]), the
+/// element is made focusable and its focus is reported to the handler. This is
+/// called by the backends whenever they create the element.
+pub(crate) fn watch_focus(element: &Element) -> Result<(), Error> {
+    FOCUS_TARGETS.with_borrow_mut(|targets| {
+        // Drop the replaced elements, e.g. the grid of the DOM backend
+        targets.retain(|target| target.is_connected());
+        targets.push(element.clone());
+    });
+    match FOCUS_HANDLER.with_borrow(Clone::clone) {
+        Some(handler) => listen_focus(element, &handler),
+        None => Ok(()),
+    }
+}
+
+/// Sets the handler of the focus events.
+///
+/// See [`WebRenderer::on_focus_event`].
+pub(crate) fn set_focus_handler<F>(callback: F) -> Result<(), Error>
+where
+    F: FnMut(bool) + 'static,
+{
+    if let Some((focus_in, focus_out)) = FOCUS_HANDLER.take() {
+        remove_event_listener(&focus_in);
+        remove_event_listener(&focus_out);
+    }
+    let callback = Rc::new(RefCell::new(callback));
+    let listener = |focused: bool| {
+        let callback = callback.clone();
+        Rc::new(Closure::<dyn FnMut(_)>::new(
+            move |event: web_sys::Event| {
+                let event = event.unchecked_into::<web_sys::FocusEvent>();
+                // Moving the focus within the element (e.g. to a link) is ignored
+                let within = event
+                    .current_target()
+                    .and_then(|target| target.dyn_into::<Node>().ok())
+                    .is_some_and(|element| {
+                        let related = event.related_target();
+                        element.contains(related.as_ref().and_then(|target| target.dyn_ref()))
+                    });
+                if !within {
+                    callback.borrow_mut()(focused);
+                    // Draw the changes of the callback in the on-demand mode
+                    request_redraw();
+                }
+            },
+        ))
+    };
+    let handler = (listener(true), listener(false));
+    FOCUS_HANDLER.set(Some(handler.clone()));
+    let targets = FOCUS_TARGETS.with_borrow(Clone::clone);
+    for target in targets {
+        listen_focus(&target, &handler)?;
+    }
+    Ok(())
+}
+
+/// Makes the given element focusable and adds the listeners of the focus
+/// handler to it.
+fn listen_focus(
+    element: &Element,
+    handler: &(ListenerClosure, ListenerClosure),
+) -> Result<(), Error> {
+    if !element.has_attribute("tabindex") {
+        element.set_attribute("tabindex", "0")?;
+    }
+    add_event_listener(element, "focusin", &handler.0)?;
+    add_event_listener(element, "focusout", &handler.1)
+}
+
+/// Stores the type of the backend that is in use.
+///
+/// This is called by the backends when they are created.
+pub(crate) fn record_backend_type(backend_type: BackendType) {
+    BACKEND_TYPE.set(Some(backend_type));
+}
+
+/// Stores the position of the cursor, or `None` if the cursor is hidden.
+///
+/// This is called by the backends when the cursor is moved or hidden.
+pub(crate) fn record_cursor_position(position: Option<Position>) {
+    CURSOR_POSITION.set(position);
+}
+
+/// Returns the position of the visible cursor.
+///
+/// See [`WebRenderer::cursor_position`].
+pub(crate) fn cursor_position() -> Option<Position> {
+    CURSOR_POSITION.get()
+}
+
+/// Returns whether the backends skip drawing and flushing the current frame.
+///
+/// This is set by [`WebRenderer::draw_web_retained`] when nothing changed
+/// since the previous frame and no redraw was requested.
+pub(crate) fn is_frame_skipped() -> bool {
+    SKIP_FRAME.get()
+}
+
+/// Returns whether the blinking cursor shapes blink.
+///
+/// The render loops that stop drawing frames (e.g.
+/// [`WebRenderer::draw_web_on_demand`]) disable blinking, since the cursor
+/// could stay hidden until the next frame otherwise.
+pub(crate) fn is_cursor_blinking() -> bool {
+    CURSOR_BLINKING.get()
+}
+
+/// Requests the backend to switch to the given cursor shape.
+///
+/// See [`WebRenderer::set_cursor_shape`].
+pub(crate) fn set_cursor_shape(shape: CursorShape) {
+    CURSOR_SHAPE.set(Some(shape));
+    request_redraw();
+}
+
+/// Returns the cursor shape that was requested since the last call, if any.
+///
+/// This is called by the backends when drawing a frame.
+pub(crate) fn take_cursor_shape() -> Option<CursorShape> {
+    CURSOR_SHAPE.take()
+}
+
+/// Time that the size of the terminal has to be stable for before the resize
+/// callback is called, in milliseconds.
+const RESIZE_DEBOUNCE_MS: i32 = 100;
+
+/// Callback that receives the new size of the terminal in columns and rows.
+type ResizeCallback = Rc<RefCell<dyn FnMut((u16, u16))>>;
+
+/// Handler of the resize events, see [`WebRenderer::on_resize`].
+struct ResizeHandler {
+    /// The callback that receives the new size.
+    callback: ResizeCallback,
+    /// The size of the last drawn frame.
+    size: Option<(u16, u16)>,
+    /// The ID of the pending timeout for calling the callback.
+    timeout_id: Option<i32>,
+}
+
+/// Calls the resize callback (debounced) if the size of the given frame
+/// differs from the last one.
+fn check_resize(buffer: &Buffer) {
+    let size = (buffer.area.width, buffer.area.height);
+    RESIZE_HANDLER.with_borrow_mut(|handler| {
+        let Some(handler) = handler else {
+            return;
+        };
+        if handler
+            .size
+            .replace(size)
+            .is_none_or(|previous| previous == size)
+        {
+            return;
+        }
+        let Some(window) = window() else {
+            return;
+        };
+        if let Some(timeout_id) = handler.timeout_id.take() {
+            window.clear_timeout_with_handle(timeout_id);
+        }
+        let callback = handler.callback.clone();
+        let on_timeout = Closure::once_into_js(move || {
+            RESIZE_HANDLER.with_borrow_mut(|handler| {
+                if let Some(handler) = handler {
+                    handler.timeout_id = None;
+                }
+            });
+            callback.borrow_mut()(size);
+        });
+        handler.timeout_id = window
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                on_timeout.unchecked_ref(),
+                RESIZE_DEBOUNCE_MS,
+            )
+            .ok();
+    });
+}
+
+/// The reference frame rate of [`frame_step`].
+const REFERENCE_FPS: f64 = 60.0;
+
+/// The maximum of [`frame_step`], so that animations don't jump ahead after
+/// the render loop was paused (e.g. in a background tab).
+const MAX_FRAME_STEP: f64 = 4.0;
+
+/// Updates the frame step for the frame that starts at the given time in
+/// milliseconds.
+fn begin_frame(now_ms: f64) {
+    let step = LAST_FRAME_START
+        .replace(Some(now_ms))
+        .map_or(1.0, |last| compute_frame_step(now_ms - last));
+    FRAME_STEP.set(step);
+}
+
+/// Converts the time between two frames in milliseconds into the number of
+/// frames at the reference rate.
+fn compute_frame_step(elapsed_ms: f64) -> f64 {
+    (elapsed_ms * REFERENCE_FPS / 1000.0).clamp(0.0, MAX_FRAME_STEP)
+}
+
+/// Returns the time since the last frame, in frames at 60 FPS.
+///
+/// See [`WebRenderer::frame_step`].
+pub(crate) fn frame_step() -> f64 {
+    FRAME_STEP.get()
+}
+
+/// Stores the buffer of the frame that was just drawn for [`cell_at`], along
+/// with the time it took to draw it in milliseconds.
+fn record_frame(buffer: &Buffer, duration_ms: f64) {
+    LAST_FRAME.with(|last_frame| *last_frame.borrow_mut() = Some(buffer.clone()));
+    check_resize(buffer);
+    LAST_FRAME_DURATION.set(Some(Duration::from_secs_f64(duration_ms.max(0.0) / 1000.0)));
+    hud::record_frame();
+}
+
+/// Draws a frame of the terminal that started at the given time in
+/// milliseconds.
+///
+/// After the render callback, `skip` decides whether the backends skip the
+/// rendered frame (see [`is_frame_skipped`]). A frame is never skipped if a
+/// redraw was requested or the [`Graphics`] widgets changed. Returns `true` if
+/// the frame was drawn.
+///
+/// [`Graphics`]: crate::widgets::Graphics
+fn draw_frame<T, F, S>(
+    terminal: &mut Terminal<T>,
+    performance: &web_sys::Performance,
+    start: f64,
+    render_callback: &mut F,
+    mut skip: S,
+) -> bool
+where
+    T: Backend,
+    F: FnMut(&mut Frame),
+    S: FnMut(&mut Frame) -> bool,
+{
+    begin_frame(start);
+    let mut skipped = false;
+    let frame = terminal
+        .draw(|frame| {
+            render_callback(frame);
+            let requested = REDRAW_REQUESTED.take();
+            skipped = skip(frame) && !requested && !graphics::shapes_changed();
+            if skipped {
+                graphics::discard_shapes();
+            }
+            SKIP_FRAME.set(skipped);
+        })
+        .expect("failed to draw the terminal");
+    SKIP_FRAME.set(false);
+    if !skipped {
+        record_frame(frame.buffer, performance.now() - start);
+    }
+    !skipped
+}
+
+/// Returns how long drawing the last frame took.
+///
+/// See [`WebRenderer::last_frame_duration`].
+pub(crate) fn last_frame_duration() -> Option<Duration> {
+    LAST_FRAME_DURATION.get()
+}
+
+/// Returns `true` if a frame has been drawn by a render loop.
+///
+/// See [`WebRenderer::is_ready`].
+pub(crate) fn is_ready() -> bool {
+    LAST_FRAME.with(|last_frame| last_frame.borrow().is_some())
+}
+
+/// Returns the cell at the given position of the last drawn frame.
+///
+/// See [`WebRenderer::cell_at`].
+pub(crate) fn cell_at(position: Position) -> Option<BufferCell> {
+    LAST_FRAME.with(|last_frame| {
+        last_frame
+            .borrow()
+            .as_ref()
+            .and_then(|buffer| buffer.cell(position).cloned())
+    })
+}
+
+/// Adds an event listener to the given target.
+///
+/// The listener is kept alive until [`shutdown_all`] is called.
+pub(crate) fn add_event_listener(
+    target: &EventTarget,
+    event: &'static str,
+    closure: &Rc<Closure<dyn FnMut(web_sys::Event)>>,
+) -> Result<(), Error> {
+    target.add_event_listener_with_callback(event, (**closure).as_ref().unchecked_ref())?;
+    REGISTRY.with(|registry| {
+        registry.borrow_mut().listeners.push(Listener {
+            target: target.clone(),
+            event,
+            closure: closure.clone(),
+        })
+    });
+    Ok(())
+}
+
+/// Replaces the console function with the given name by the patched one.
+///
+/// The closure that is called by the patched function is kept alive until
+/// [`shutdown_all`] is called, which restores the original function.
+pub(crate) fn patch_console(
+    console: &JsValue,
+    name: &'static str,
+    original: JsValue,
+    patched: &JsValue,
+    closure: Closure<dyn FnMut(Array)>,
+) -> Result<(), Error> {
+    Reflect::set(console, &JsValue::from_str(name), patched)?;
+    REGISTRY.with(|registry| {
+        registry.borrow_mut().console_patches.push(ConsolePatch {
+            console: console.clone(),
+            name,
+            original,
+            _closure: closure,
+        })
+    });
+    Ok(())
+}
+
+/// Adds a key handler with the given priority.
+///
+/// See [`WebRenderer::add_key_handler`].
+pub(crate) fn add_key_handler<F>(priority: i32, handler: F) -> Result<HandlerId, Error>
+where
+    F: FnMut(&KeyEvent) -> bool + 'static,
+{
+    // A single listener dispatches the events to all the handlers
+    if !KEY_HANDLERS.with_borrow(|handlers| handlers.listening) {
+        let closure = Closure::<dyn FnMut(_)>::new(|event: web_sys::Event| {
+            dispatch_key_event(&event.unchecked_into::<web_sys::KeyboardEvent>().into());
+        });
+        let document = get_document()?;
+        add_event_listener(&document, "keydown", &Rc::new(closure))?;
+    }
+    Ok(KEY_HANDLERS.with_borrow_mut(|handlers| {
+        handlers.listening = true;
+        let id = HandlerId(handlers.next_id);
+        handlers.next_id += 1;
+        // Handlers with the same priority are called in the order they were added
+        let index = handlers
+            .handlers
+            .partition_point(|handler| handler.priority >= priority);
+        handlers.handlers.insert(
+            index,
+            KeyHandler {
+                id,
+                priority,
+                callback: Rc::new(RefCell::new(handler)),
+            },
+        );
+        id
+    }))
+}
+
+/// Removes the key handler with the given identifier.
+///
+/// See [`WebRenderer::remove_key_handler`].
+pub(crate) fn remove_key_handler(id: HandlerId) {
+    KEY_HANDLERS.with_borrow_mut(|handlers| handlers.handlers.retain(|handler| handler.id != id));
+}
+
+/// Adds a paste handler.
+///
+/// See [`WebRenderer::on_paste_event`].
+pub(crate) fn add_paste_handler<F>(mut callback: F) -> Result<HandlerId, Error>
+where
+    F: FnMut(PasteEvent) + 'static,
+{
+    let closure = Rc::new(Closure::<dyn FnMut(_)>::new(
+        move |event: web_sys::Event| {
+            event.prevent_default();
+            callback(event.unchecked_into::<web_sys::ClipboardEvent>().into());
+        },
+    ));
+    let document = get_document()?;
+    add_event_listener(&document, "paste", &closure)?;
+    Ok(PASTE_HANDLERS.with_borrow_mut(|handlers| {
+        let id = HandlerId(handlers.next_id);
+        handlers.next_id += 1;
+        handlers.handlers.push((id, closure));
+        id
+    }))
+}
+
+/// Removes the paste handler with the given identifier.
+///
+/// See [`WebRenderer::remove_paste_handler`].
+pub(crate) fn remove_paste_handler(id: HandlerId) {
+    let closure = PASTE_HANDLERS.with_borrow_mut(|handlers| {
+        let index = handlers
+            .handlers
+            .iter()
+            .position(|(handler_id, _)| *handler_id == id)?;
+        Some(handlers.handlers.remove(index).1)
+    });
+    if let Some(closure) = closure {
+        remove_event_listener(&closure);
+    }
+}
+
+/// Calls the key handlers in the order of their priority until one of them
+/// consumes the event.
+fn dispatch_key_event(event: &KeyEvent) {
+    // The handlers may add or remove handlers, so they are called without
+    // borrowing the list
+    let handlers: Vec<(HandlerId, KeyHandlerCallback)> = KEY_HANDLERS.with_borrow(|handlers| {
+        handlers
+            .handlers
+            .iter()
+            .map(|handler| (handler.id, handler.callback.clone()))
+            .collect()
+    });
+    for (id, callback) in handlers {
+        let removed = KEY_HANDLERS
+            .with_borrow(|handlers| !handlers.handlers.iter().any(|handler| handler.id == id));
+        if removed {
+            continue;
+        }
+        if (callback.borrow_mut())(event) {
+            break;
+        }
+    }
+}
+
+/// Removes the event listeners with the given callback that were added via
+/// [`add_event_listener`].
+pub(crate) fn remove_event_listener(closure: &ListenerClosure) {
+    let listeners = REGISTRY.with(|registry| {
+        let listeners = &mut registry.borrow_mut().listeners;
+        let (removed, kept) = std::mem::take(listeners)
+            .into_iter()
+            .partition(|listener| Rc::ptr_eq(&listener.closure, closure));
+        *listeners = kept;
+        removed
+    });
+    for listener in listeners {
+        let _ = listener.target.remove_event_listener_with_callback(
+            listener.event,
+            (*listener.closure).as_ref().unchecked_ref(),
+        );
+    }
+}
+
+/// Starts a render loop with the given callback.
+///
+/// The callback receives the state of the render loop so that it can request
+/// the next animation frame via [`RenderLoop::request_animation_frame`].
+fn start_render_loop<F>(mut callback: F) -> Rc<RefCell<RenderLoop>>
+where
+    F: FnMut(&Rc<RefCell<RenderLoop>>) + 'static,
+{
+    let render_loop = Rc::new(RefCell::new(RenderLoop {
+        containers: PENDING_CONTAINERS.take(),
+        ..Default::default()
+    }));
+    render_loop.borrow_mut().callback = Some(Closure::wrap(Box::new({
+        // A weak reference avoids a reference cycle between the loop and its callback
+        let render_loop = Rc::downgrade(&render_loop);
+        move || {
+            if let Some(render_loop) = render_loop.upgrade() {
+                if render_loop.borrow().is_detached() {
+                    RenderLoop::stop_detached(&render_loop);
+                    return;
+                }
+                // The requested frame is running now
+                render_loop.borrow_mut().frame_id = None;
+                callback(&render_loop);
+            }
+        }
+    }) as Box<dyn FnMut()>));
+    REGISTRY.with(|registry| registry.borrow_mut().render_loops.push(render_loop.clone()));
+    RenderLoop::request_animation_frame(&render_loop);
+    render_loop
+}
+
+/// Requests the render loops of [`WebRenderer::draw_web_on_demand`] to draw
+/// a frame.
+///
+/// Besides the application (see [`WebRenderer::request_redraw`]), this is
+/// called by the backends when they need another frame to finish their work
+/// even if the content doesn't change:
+///
+/// - the animation of the smooth cursor of the canvas backend,
+/// - the cells that exceeded the cell budget of the DOM backend,
+/// - the restoration of a lost WebGL context,
+/// - a new cursor shape, cursor color or background color.
+///
+/// A change of the device pixel ratio (e.g. zooming) fires a window resize,
+/// which the on-demand loops draw a frame for anyway.
+///
+/// The next frame of [`WebRenderer::draw_web_retained`] isn't skipped either.
+pub(crate) fn request_redraw() {
+    REDRAW_REQUESTED.set(true);
+    let render_loops: Vec<_> = REGISTRY.with(|registry| {
+        registry
+            .borrow()
+            .render_loops
+            .iter()
+            .filter(|render_loop| render_loop.borrow().on_demand)
+            .cloned()
+            .collect()
+    });
+    for render_loop in render_loops {
+        RenderLoop::request_redraw(&render_loop);
+    }
+}
+
+/// Serializes the last drawn frame into text with ANSI escape sequences.
+///
+/// See [`WebRenderer::to_ansi_string`].
+pub(crate) fn to_ansi_string() -> String {
+    LAST_FRAME.with(|last_frame| {
+        last_frame
+            .borrow()
+            .as_ref()
+            .map(ansi::buffer_to_ansi)
+            .unwrap_or_default()
+    })
+}
+
+/// Removes all event listeners and stops all render loops.
+///
+/// See [`WebRenderer::shutdown_all`].
+pub(crate) fn shutdown_all() {
+    let registry = REGISTRY.with(|registry| registry.take());
+    LAST_FRAME.with(|last_frame| last_frame.take());
+    LAST_FRAME_DURATION.set(None);
+    CURSOR_POSITION.set(None);
+    CURSOR_SHAPE.set(None);
+    SKIP_FRAME.set(false);
+    CURSOR_BLINKING.set(true);
+    KEY_HANDLERS.take();
+    PASTE_HANDLERS.take();
+    FOCUS_TARGETS.take();
+    FOCUS_HANDLER.take();
+    LAST_FRAME_START.set(None);
+    FRAME_STEP.set(1.0);
+    if let Some(timeout_id) = RESIZE_HANDLER.take().and_then(|handler| handler.timeout_id) {
+        if let Some(window) = window() {
+            window.clear_timeout_with_handle(timeout_id);
+        }
+    }
+    PENDING_CONTAINERS.take();
+    ON_CONTAINER_REMOVED.take();
+    hud::disable();
+    for listener in registry.listeners {
+        let _ = listener.target.remove_event_listener_with_callback(
+            listener.event,
+            (*listener.closure).as_ref().unchecked_ref(),
+        );
+    }
+    // The functions are restored in reverse order, in case one was patched twice
+    for patch in registry.console_patches.into_iter().rev() {
+        let _ = Reflect::set(
+            &patch.console,
+            &JsValue::from_str(patch.name),
+            &patch.original,
+        );
+    }
+    for render_loop in registry.render_loops {
+        let mut render_loop = render_loop.borrow_mut();
+        if let Some(frame_id) = render_loop.frame_id.take() {
+            if let Some(window) = window() {
+                let _ = window.cancel_animation_frame(frame_id);
+            }
+        }
+        // Dropping the callback also drops the terminal that it owns
+        let callback = render_loop.callback.take();
+        drop(render_loop);
+        drop(callback);
+    }
+}
 
 /// Trait for rendering on the web.
 ///
@@ -21,6 +836,114 @@ pub trait WebRenderer {
     where
         F: FnMut(&mut Frame) + 'static;
 
+    /// Renders the terminal on the web with a fallible render callback.
+    ///
+    /// This works like [`WebRenderer::draw_web`], except that the render
+    /// callback returns a [`Result`]. Errors are passed to the `error_handler`
+    /// after the frame is drawn, which can e.g. log them or update the
+    /// application state for showing an error screen on the next frame.
+    fn draw_web_try<F, E, H>(self, mut render_callback: F, mut error_handler: H)
+    where
+        Self: Sized,
+        F: FnMut(&mut Frame) -> Result<(), E> + 'static,
+        H: FnMut(E) + 'static,
+    {
+        self.draw_web(move |frame| {
+            if let Err(error) = render_callback(frame) {
+                error_handler(error);
+            }
+        });
+    }
+
+    /// Renders the terminal on the web and pauses rendering when idle.
+    ///
+    /// This works like [`WebRenderer::draw_web`], except that the render loop
+    /// stops requesting animation frames after `idle_ms` milliseconds without
+    /// any user input or change in the rendered content.
+    ///
+    /// Rendering resumes on the next input event (key, mouse, wheel, touch) or
+    /// window resize, so the application stays fully responsive while using
+    /// no CPU when nothing happens on screen. The blinking cursor shapes are
+    /// shown steadily, since the cursor could stay hidden otherwise.
+    fn draw_web_idle<F>(self, idle_ms: u32, render_callback: F)
+    where
+        F: FnMut(&mut Frame) + 'static;
+
+    /// Renders the terminal on the web and skips the backend work for
+    /// unchanged frames.
+    ///
+    /// This works like [`WebRenderer::draw_web`], except that a hash of the
+    /// rendered buffer is compared with the previous frame. While the content,
+    /// the cursor and the [`Graphics`] widgets don't change (and there is no
+    /// user input), the backend skips drawing and flushing. The backends still
+    /// get the frames they request, e.g. for the smooth cursor animation.
+    ///
+    /// Unlike [`WebRenderer::draw_web_idle`], the render callback is still
+    /// called on every frame, so time based content (e.g. a clock) is shown
+    /// without any user input. The blinking cursor shapes are shown steadily,
+    /// since blinking would keep the frames from being skipped.
+    ///
+    /// [`Graphics`]: crate::widgets::Graphics
+    fn draw_web_retained<F>(self, render_callback: F)
+    where
+        F: FnMut(&mut Frame) + 'static;
+
+    /// Renders the terminal on the web at no more than the given frames per
+    /// second.
+    ///
+    /// This works like [`WebRenderer::draw_web`], except that the animation
+    /// frames are skipped until at least `1000 / fps` milliseconds passed since
+    /// the last drawn frame. This saves work on high refresh rate displays for
+    /// applications that don't need to draw e.g. 120 frames per second.
+    ///
+    /// Only the drawing is limited, the events are still handled as they
+    /// occur, so the application stays responsive to input. An `fps` of zero
+    /// is treated as one.
+    fn draw_web_capped<F>(self, fps: u32, render_callback: F)
+    where
+        F: FnMut(&mut Frame) + 'static;
+
+    /// Renders the terminal on the web only when a redraw is needed.
+    ///
+    /// Unlike [`WebRenderer::draw_web`], which draws on every animation frame,
+    /// a frame is only drawn after an input event (key, mouse, wheel, touch,
+    /// paste), a window resize, or a call to [`WebRenderer::request_redraw`].
+    /// This keeps a mostly static UI (e.g. a dashboard) from using any CPU
+    /// while nothing happens.
+    ///
+    /// Animations have to request the next frame themselves, e.g. by calling
+    /// [`utils::request_redraw`] from the render callback. The backends
+    /// request the frames they need on their own, e.g. for the smooth cursor
+    /// animation or a restored WebGL context. The blinking cursor shapes are
+    /// shown steadily, since the cursor could stay hidden otherwise.
+    fn draw_web_on_demand<F>(self, render_callback: F)
+    where
+        F: FnMut(&mut Frame) + 'static;
+
+    /// Requests a frame to be drawn by [`WebRenderer::draw_web_on_demand`].
+    ///
+    /// Multiple requests before the next animation frame result in a single
+    /// frame. See [`utils::request_redraw`] for calling this after the terminal
+    /// was moved into the render loop, e.g. when data arrives.
+    fn request_redraw(&self) {
+        request_redraw();
+    }
+
+    /// Handles the removal of the container element of the backend.
+    ///
+    /// If the container (i.e. the element with the `grid_id` of the backend
+    /// options, or its canvas) is removed from the document without calling
+    /// [`WebRenderer::shutdown_all`], e.g. when closing a panel, the render loop
+    /// stops on the next animation frame and drops the terminal. The given
+    /// callback is called afterwards, which can e.g. clean up the event
+    /// listeners via [`utils::shutdown_all`].
+    fn on_container_removed<F>(&self, callback: F)
+    where
+        F: FnMut() + 'static,
+    {
+        ON_CONTAINER_REMOVED.set(Some(Box::new(callback)));
+    }
+
     /// Handles key events.
     ///
     /// This method takes a closure that will be called on every `keydown`
@@ -29,40 +952,389 @@ pub trait WebRenderer {
     where
         F: FnMut(KeyEvent) + 'static,
     {
-        let closure = Closure::<dyn FnMut(_)>::new(move |event: web_sys::KeyboardEvent| {
-            callback(event.into());
+        let closure = Closure::<dyn FnMut(_)>::new(move |event: web_sys::Event| {
+            callback(event.unchecked_into::<web_sys::KeyboardEvent>().into());
         });
         let window = window().unwrap();
         let document = window.document().unwrap();
-        document
-            .add_event_listener_with_callback("keydown", closure.as_ref().unchecked_ref())
-            .unwrap();
-        closure.forget();
+        add_event_listener(&document, "keydown", &Rc::new(closure)).unwrap();
+    }
+
+    /// Handles the key events of the given kinds.
+    ///
+    /// [`WebRenderer::on_key_event`] only reports key presses (including
+    /// repeats). Games and other applications that need to know when keys are
+    /// let go can opt in to [`KeyEventKind::Release`] events with this method,
+    /// which listens to `keyup` events as well. The kind of each event is
+    /// available via [`KeyEvent::kind`].
+    fn on_key_event_kinds<F>(&self, kinds: &[KeyEventKind], callback: F) -> Result<(), Error>
+    where
+        F: FnMut(KeyEvent) + 'static,
+    {
+        let callback = Rc::new(RefCell::new(callback));
+        let kinds = kinds.to_vec();
+        let closure = Closure::<dyn FnMut(_)>::new(move |event: web_sys::Event| {
+            let event = KeyEvent::from(event.unchecked_into::<web_sys::KeyboardEvent>());
+            if kinds.contains(&event.kind) {
+                callback.borrow_mut()(event);
+            }
+        });
+        let closure = Rc::new(closure);
+        let document = get_document()?;
+        add_event_listener(&document, "keydown", &closure)?;
+        add_event_listener(&document, "keyup", &closure)
+    }
+
+    /// Adds a key handler with the given priority.
+    ///
+    /// Unlike [`WebRenderer::on_key_event`], any number of handlers can be
+    /// added, e.g. a global handler for shortcuts and a handler for the focused
+    /// widget. Each `keydown` event is passed to the handlers in the order of
+    /// their priority (highest first) until one of them returns `true` to mark
+    /// the event as consumed, which stops the propagation to the remaining
+    /// handlers. Handlers with the same priority are called in the order they
+    /// were added.
+    ///
+    /// The returned identifier can be passed to
+    /// [`WebRenderer::remove_key_handler`]. See [`utils::add_key_handler`] for
+    /// calling this after the terminal was moved into
+    /// [`WebRenderer::draw_web`].
+    fn add_key_handler<F>(&self, priority: i32, handler: F) -> Result<HandlerId, Error>
+    where
+        F: FnMut(&KeyEvent) -> bool + 'static,
+    {
+        add_key_handler(priority, handler)
+    }
+
+    /// Removes the key handler with the given identifier.
+    ///
+    /// See [`utils::remove_key_handler`] for calling this after the terminal
+    /// was moved into [`WebRenderer::draw_web`].
+    fn remove_key_handler(&self, id: HandlerId) {
+        remove_key_handler(id);
+    }
+
+    /// Returns a bounded queue that receives the key events.
+    ///
+    /// Instead of handling each `keydown` event in a callback, the events can be
+    /// processed in the render callback by draining the queue. The queue holds at
+    /// most `capacity` events and the overflowing events are handled according to
+    /// the given [`OverflowPolicy`].
+    fn key_event_queue(&self, capacity: usize, policy: OverflowPolicy) -> EventQueue<KeyEvent> {
+        let queue = EventQueue::new(capacity, policy);
+        self.on_key_event({
+            let queue = queue.clone();
+            move |event| {
+                queue.push(event);
+            }
+        });
+        queue
     }
 
     /// Handles mouse events.
     ///
-    /// This method takes a closure that will be called on every `mousemove`, 'mousedown', and `mouseup`
-    /// event.
-    fn on_mouse_event<F>(&self, mut callback: F)
+    /// This method takes a closure that will be called on every `mousemove`, 'mousedown', `mouseup`
+    /// and `wheel` event.
+    ///
+    /// The wheel events carry the raw deltas in pixels. For scrolling by lines, also with touch
+    /// screens, use [`utils::on_scroll`](crate::utils::on_scroll) instead.
+    ///
+    /// Returns [`Error::MouseEventsNotSupported`] if the [`WebGl2Backend`] is in use, e.g. when
+    /// it is selected by the [`MultiBackendBuilder`].
+    ///
+    /// [`WebGl2Backend`]: crate::WebGl2Backend
+    /// [`MultiBackendBuilder`]: crate::backend::multi::MultiBackendBuilder
+    fn on_mouse_event<F>(&self, mut callback: F) -> Result<(), Error>
     where
         F: FnMut(MouseEvent) + 'static,
     {
-        let closure = Closure::<dyn FnMut(_)>::new(move |event: web_sys::MouseEvent| {
-            callback(event.into());
+        if BACKEND_TYPE.get() == Some(BackendType::WebGl2) {
+            return Err(Error::MouseEventsNotSupported);
+        }
+        let closure = Rc::new(Closure::<dyn FnMut(_)>::new(
+            move |event: web_sys::Event| {
+                callback(event.unchecked_into::<web_sys::MouseEvent>().into());
+            },
+        ));
+        let document = get_document()?;
+        for event in ["mousemove", "mousedown", "mouseup", "wheel"] {
+            add_event_listener(&document, event, &closure)?;
+        }
+        Ok(())
+    }
+
+    /// Handles paste events.
+    ///
+    /// This method takes a closure that will be called on every `paste` event.
+    /// The callback is called once per paste with the full content, including
+    /// multiple lines. Pasting doesn't emit a key event for each character,
+    /// only the shortcut itself (e.g. `Ctrl+V`) is reported to
+    /// [`WebRenderer::on_key_event`].
+    ///
+    /// The pasted content is always available as plain text, even when it is
+    /// copied from a rich source such as a web page. The HTML content is
+    /// provided as well for applications that want to handle it.
+    ///
+    /// The returned identifier can be passed to
+    /// [`WebRenderer::remove_paste_handler`].
+    fn on_paste_event<F>(&self, callback: F) -> Result<HandlerId, Error>
+    where
+        F: FnMut(PasteEvent) + 'static,
+    {
+        add_paste_handler(callback)
+    }
+
+    /// Removes the paste handler with the given identifier.
+    ///
+    /// Other `paste` listeners, e.g. the one of
+    /// [`WebRenderer::draw_web_on_demand`], are kept. See
+    /// [`utils::remove_paste_handler`] for calling this after the terminal was
+    /// moved into [`WebRenderer::draw_web`].
+    fn remove_paste_handler(&self, id: HandlerId) {
+        remove_paste_handler(id);
+    }
+
+    /// Handles the find-in-page shortcut of the browser.
+    ///
+    /// With the [`DomBackend`], the text of the cells is part of the page, so
+    /// the native find-in-page (`Ctrl+F`, or `Cmd+F` on Apple platforms) finds
+    /// and highlights the text in the terminal. This method intercepts the
+    /// shortcut instead and calls the given callback, e.g. for opening the
+    /// search of the application. This also works with the canvas-based
+    /// backends, whose text the native search can't find.
+    ///
+    /// The key event of the shortcut is still passed to
+    /// [`WebRenderer::on_key_event`].
+    ///
+    /// [`DomBackend`]: crate::DomBackend
+    fn on_find_shortcut<F>(&self, mut callback: F) -> Result<(), Error>
+    where
+        F: FnMut() + 'static,
+    {
+        let apple = utils::is_apple_platform();
+        let closure = Closure::<dyn FnMut(_)>::new(move |event: web_sys::Event| {
+            let event = event.unchecked_into::<web_sys::KeyboardEvent>();
+            if KeyEvent::from(event.clone()).is_find_combo_on(apple) {
+                event.prevent_default();
+                callback();
+            }
         });
-        let window = window().unwrap();
-        let document = window.document().unwrap();
-        document
-            .add_event_listener_with_callback("mousemove", closure.as_ref().unchecked_ref())
-            .unwrap();
-        document
-            .add_event_listener_with_callback("mousedown", closure.as_ref().unchecked_ref())
-            .unwrap();
-        document
-            .add_event_listener_with_callback("mouseup", closure.as_ref().unchecked_ref())
-            .unwrap();
-        closure.forget();
+        let document = get_document()?;
+        add_event_listener(&document, "keydown", &Rc::new(closure))
+    }
+
+    /// Handles resize events.
+    ///
+    /// The callback receives the new size of the terminal in columns and rows,
+    /// e.g. for recomputing layout caches once per resize instead of on every
+    /// frame. The size is measured after the backend has recomputed its grid,
+    /// so it reflects the actual number of cells rather than the size of the
+    /// window in pixels.
+    ///
+    /// The callback is debounced: it is called once the size has been stable
+    /// for 100 milliseconds, so dragging the window edge doesn't call it on
+    /// every frame. Calling this again replaces the previous handler.
+    fn on_resize<F>(&self, callback: F)
+    where
+        F: FnMut((u16, u16)) + 'static,
+    {
+        let size = LAST_FRAME.with(|last_frame| {
+            last_frame
+                .borrow()
+                .as_ref()
+                .map(|buffer| (buffer.area.width, buffer.area.height))
+        });
+        RESIZE_HANDLER.set(Some(ResizeHandler {
+            callback: Rc::new(RefCell::new(callback)),
+            size,
+            timeout_id: None,
+        }));
+    }
+
+    /// Handles focus events.
+    ///
+    /// The callback is called with `true` when the terminal gains focus and
+    /// `false` when it loses focus, e.g. for pausing animations and dimming
+    /// the UI. This makes the grid (or the canvas) of the backend focusable,
+    /// so that it can be focused by clicking it or with the Tab key, and
+    /// follows its `focusin` and `focusout` events.
+    ///
+    /// Calling this again replaces the previous handler.
+    fn on_focus_event<F>(&self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(bool) + 'static,
+    {
+        set_focus_handler(callback)
+    }
+
+    /// Handles the text input of virtual (on-screen) keyboards.
+    ///
+    /// This makes text inputs usable on mobile devices, where key events are
+    /// not reliable. See [`utils::on_text_input`] for details.
+    fn on_text_input<F>(&self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(TextInput) + 'static,
+    {
+        utils::on_text_input(callback)
+    }
+
+    /// Handles the browser back button within the application.
+    ///
+    /// The callback is called when the back button is pressed and returns
+    /// whether it handled the navigation. See [`utils::capture_back_button`].
+    fn capture_back_button<F>(&self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut() -> bool + 'static,
+    {
+        utils::capture_back_button(callback)
+    }
+
+    /// Dispatches a synthetic key event.
+    ///
+    /// The event goes through the same path as the real key events, see
+    /// [`utils::simulate_key`] for calling this from event handlers or timers.
+    fn simulate_key(&self, event: KeyEvent) -> Result<(), Error> {
+        utils::simulate_key(event)
+    }
+
+    /// Dispatches a synthetic mouse event.
+    ///
+    /// The event goes through the same path as the real mouse events, see
+    /// [`utils::simulate_mouse`] for calling this from event handlers or timers.
+    fn simulate_mouse(&self, event: MouseEvent) -> Result<(), Error> {
+        utils::simulate_mouse(event)
+    }
+
+    /// Briefly flashes the screen with the given color.
+    ///
+    /// This is useful for grabbing the user's attention, e.g. for a visual
+    /// bell. See [`utils::flash_screen`] for calling this from event handlers.
+    fn flash_screen(&self, color: Color, duration: Duration) -> Result<(), Error> {
+        utils::flash_screen(color, duration)
+    }
+
+    /// Serializes the last drawn frame into text with ANSI escape sequences.
+    ///
+    /// The colors and modifiers of the cells are written as SGR sequences, so
+    /// that the screen can be reproduced by printing the text in a terminal,
+    /// e.g. for a "copy as terminal output" feature. Returns an empty string
+    /// if nothing has been drawn yet.
+    ///
+    /// See [`utils::to_ansi_string`] for calling this from event handlers.
+    fn to_ansi_string(&self) -> String {
+        to_ansi_string()
+    }
+
+    /// Enables the performance HUD, which is toggled with the given key.
+    ///
+    /// The HUD is an overlay in the top right corner of the page that shows
+    /// the current frame rate. If the [`WebGl2Backend`] measures performance
+    /// (see [`WebGl2BackendOptions::measure_performance`]), the time spent on
+    /// synchronizing the buffer and rendering is shown as well.
+    ///
+    /// The HUD works with any backend and is hidden initially.
+    ///
+    /// [`WebGl2Backend`]: crate::WebGl2Backend
+    /// [`WebGl2BackendOptions::measure_performance`]: crate::backend::webgl2::WebGl2BackendOptions::measure_performance
+    fn enable_perf_hud(&self, toggle_key: KeyCode) -> Result<(), Error> {
+        hud::enable(toggle_key)
+    }
+
+    /// Returns the cell at the given position of the last drawn frame.
+    ///
+    /// The returned cell contains the symbol and the style of the content on
+    /// screen, which is useful for testing or for showing details about the
+    /// hovered cell. Returns `None` if the position is out of bounds or if
+    /// nothing has been drawn yet.
+    ///
+    /// Since [`WebRenderer::draw_web`] takes ownership of the terminal, see
+    /// [`utils::cell_at`] for calling this from event handlers.
+    fn cell_at(&self, position: Position) -> Option<BufferCell> {
+        cell_at(position)
+    }
+
+    /// Returns `true` if the backend is ready, i.e. the first frame was drawn.
+    ///
+    /// The backends set up their grid (e.g. measure its size) when the first
+    /// frame is flushed, so this can be used for delaying work that depends on
+    /// the grid. It returns `false` again after [`WebRenderer::shutdown_all`].
+    ///
+    /// Since [`WebRenderer::draw_web`] takes ownership of the terminal, see
+    /// [`utils::is_ready`] for calling this afterwards.
+    fn is_ready(&self) -> bool {
+        is_ready()
+    }
+
+    /// Returns the position of the terminal cursor.
+    ///
+    /// This is the position that was set via [`Frame::set_cursor_position`]
+    /// in the last frame, e.g. for showing the line and column in the status
+    /// line of an editor without tracking it separately. Returns `None` if the
+    /// cursor is hidden.
+    ///
+    /// Since [`WebRenderer::draw_web`] takes ownership of the terminal, see
+    /// [`utils::cursor_position`] for calling this afterwards.
+    fn cursor_position(&self) -> Option<Position> {
+        cursor_position()
+    }
+
+    /// Sets the shape of the cursor at runtime.
+    ///
+    /// The shape is applied by the backend in the next frame, which allows
+    /// modal editors to change the cursor with the editing mode (e.g. a block
+    /// in normal mode and an underscore in replace mode) from the event
+    /// handlers.
+    ///
+    /// Since [`WebRenderer::draw_web`] takes ownership of the terminal, see
+    /// [`utils::set_cursor_shape`] for calling this afterwards.
+    fn set_cursor_shape(&self, shape: CursorShape) {
+        set_cursor_shape(shape);
+    }
+
+    /// Returns the time since the previous frame, in frames at 60 FPS.
+    ///
+    /// The render loop runs at the refresh rate of the display, so animations
+    /// that advance by a fixed amount per frame run faster on 120 Hz or 144 Hz
+    /// displays. Advancing by this step instead (e.g. `offset += frame_step()`)
+    /// makes them run at the same speed everywhere: it is `1.0` at 60 Hz, `0.5`
+    /// at 120 Hz and so on. The step is capped at `4.0`, so that animations
+    /// don't jump ahead after the render loop was paused.
+    ///
+    /// Since [`WebRenderer::draw_web`] takes ownership of the terminal, see
+    /// [`utils::frame_step`] for calling this from the render callback.
+    fn frame_step(&self) -> f64 {
+        frame_step()
+    }
+
+    /// Returns how long drawing the last frame took.
+    ///
+    /// This is measured around [`Terminal::draw`] in the render loop, so it
+    /// covers both the render callback and the flushing of the backend, the
+    /// same way on all backends. Applications can use this for detecting slow
+    /// frames and adapting to them, e.g. by reducing animations.
+    ///
+    /// Returns `None` if no frame has been drawn yet. Since
+    /// [`WebRenderer::draw_web`] takes ownership of the terminal, see
+    /// [`utils::last_frame_duration`] for calling this afterwards.
+    fn last_frame_duration(&self) -> Option<Duration> {
+        last_frame_duration()
+    }
+
+    /// Stops rendering and removes all event listeners.
+    ///
+    /// This cancels the pending animation frame of every render loop, removes
+    /// the key, mouse and resize listeners that were added by Ratzilla and drops
+    /// their callbacks along with the terminals that are owned by them.
+    ///
+    /// Note that this is global: it tears down every terminal on the page, not
+    /// only the one that it is called on.
+    ///
+    /// This is useful for single page applications that create and discard
+    /// terminals, e.g. on route changes. Since [`WebRenderer::draw_web`] takes
+    /// ownership of the terminal, see [`utils::shutdown_all`] for calling this
+    /// afterwards.
+    fn shutdown_all(&self) {
+        shutdown_all();
     }
 
     /// Requests an animation frame.
@@ -85,17 +1357,163 @@ where
     where
         F: FnMut(&mut Frame) + 'static,
     {
-        let callback = Rc::new(RefCell::new(None));
-        *callback.borrow_mut() = Some(Closure::wrap(Box::new({
-            let cb = callback.clone();
-            move || {
-                self.draw(|frame| {
-                    render_callback(frame);
-                })
-                .unwrap();
-                Self::request_animation_frame(cb.borrow().as_ref().unwrap());
+        let performance = performance().expect("performance API is available");
+        start_render_loop(move |render_loop| {
+            let start = performance.now();
+            draw_frame(&mut self, &performance, start, &mut render_callback, |_| {
+                false
+            });
+            RenderLoop::request_animation_frame(render_loop);
+        });
+    }
+
+    fn draw_web_idle<F>(mut self, idle_ms: u32, mut render_callback: F)
+    where
+        F: FnMut(&mut Frame) + 'static,
+    {
+        // The cursor could stay hidden once idle
+        CURSOR_BLINKING.set(false);
+        let performance = performance().expect("performance API is available");
+        let last_activity = Rc::new(Cell::new(performance.now()));
+        let running = Rc::new(Cell::new(true));
+        let mut prev_buffer: Option<Buffer> = None;
+
+        let render_loop = start_render_loop({
+            let last_activity = last_activity.clone();
+            let running = running.clone();
+            let performance = performance.clone();
+            move |render_loop| {
+                let start = performance.now();
+                draw_frame(
+                    &mut self,
+                    &performance,
+                    start,
+                    &mut render_callback,
+                    |frame| {
+                        // A change in the rendered content counts as activity
+                        if prev_buffer.as_ref() != Some(frame.buffer_mut()) {
+                            prev_buffer = Some(frame.buffer_mut().clone());
+                            last_activity.set(start);
+                        }
+                        false
+                    },
+                );
+
+                if performance.now() - last_activity.get() < idle_ms as f64 {
+                    RenderLoop::request_animation_frame(render_loop);
+                } else {
+                    running.set(false);
+                }
             }
-        }) as Box<dyn FnMut()>));
-        Self::request_animation_frame(callback.borrow().as_ref().unwrap());
+        });
+
+        // Resume the render loop on user activity
+        let on_activity = Rc::new(Closure::<dyn FnMut(_)>::new(move |_: web_sys::Event| {
+            last_activity.set(performance.now());
+            if !running.replace(true) {
+                RenderLoop::request_animation_frame(&render_loop);
+            }
+        }));
+        let window = window().expect("window is available");
+        let document = window.document().expect("document is available");
+        for event in ACTIVITY_EVENTS {
+            add_event_listener(&document, event, &on_activity)
+                .expect("failed to add activity listener");
+        }
+        add_event_listener(&window, "resize", &on_activity).expect("failed to add resize listener");
+    }
+
+    fn draw_web_capped<F>(mut self, fps: u32, mut render_callback: F)
+    where
+        F: FnMut(&mut Frame) + 'static,
+    {
+        let performance = performance().expect("performance API is available");
+        let interval = 1000.0 / fps.max(1) as f64;
+        let mut last_draw: Option<f64> = None;
+        start_render_loop(move |render_loop| {
+            let start = performance.now();
+            let elapsed = last_draw.map_or(f64::INFINITY, |last_draw| start - last_draw);
+            if elapsed + CAPPED_FRAME_TOLERANCE_MS >= interval {
+                last_draw = Some(start);
+                draw_frame(&mut self, &performance, start, &mut render_callback, |_| {
+                    false
+                });
+            }
+            RenderLoop::request_animation_frame(render_loop);
+        });
+    }
+
+    fn draw_web_on_demand<F>(mut self, mut render_callback: F)
+    where
+        F: FnMut(&mut Frame) + 'static,
+    {
+        // The cursor could stay hidden until the next input
+        CURSOR_BLINKING.set(false);
+        let performance = performance().expect("performance API is available");
+        let render_loop = start_render_loop(move |_| {
+            let start = performance.now();
+            draw_frame(&mut self, &performance, start, &mut render_callback, |_| {
+                false
+            });
+        });
+        render_loop.borrow_mut().on_demand = true;
+
+        // Draw a frame on input
+        let on_event = Rc::new(Closure::<dyn FnMut(_)>::new(move |_: web_sys::Event| {
+            RenderLoop::request_redraw(&render_loop);
+        }));
+        let window = window().expect("window is available");
+        let document = window.document().expect("document is available");
+        for event in ACTIVITY_EVENTS.into_iter().chain(["keyup", "paste"]) {
+            add_event_listener(&document, event, &on_event).expect("failed to add input listener");
+        }
+        add_event_listener(&window, "resize", &on_event).expect("failed to add resize listener");
+    }
+
+    fn draw_web_retained<F>(mut self, mut render_callback: F)
+    where
+        F: FnMut(&mut Frame) + 'static,
+    {
+        // Blinking would keep the content changing
+        CURSOR_BLINKING.set(false);
+        let performance = performance().expect("performance API is available");
+        let mut last_hash = None;
+        let mut last_cursor = None;
+
+        start_render_loop(move |render_loop| {
+            let start = performance.now();
+            draw_frame(
+                &mut self,
+                &performance,
+                start,
+                &mut render_callback,
+                |frame| {
+                    let mut hasher = DefaultHasher::new();
+                    frame.buffer_mut().hash(&mut hasher);
+                    let hash = hasher.finish();
+                    last_hash.replace(hash) == Some(hash)
+                },
+            );
+
+            // The cursor is only known after drawing, so moving it is
+            // shown in the next frame
+            let cursor = cursor_position();
+            if std::mem::replace(&mut last_cursor, cursor) != cursor {
+                request_redraw();
+            }
+            RenderLoop::request_animation_frame(render_loop);
+        });
+
+        // Draw the frames on user activity
+        let on_activity = Rc::new(Closure::<dyn FnMut(_)>::new(move |_: web_sys::Event| {
+            request_redraw();
+        }));
+        let window = window().expect("window is available");
+        let document = window.document().expect("document is available");
+        for event in ACTIVITY_EVENTS {
+            add_event_listener(&document, event, &on_activity)
+                .expect("failed to add activity listener");
+        }
+        add_event_listener(&window, "resize", &on_activity).expect("failed to add resize listener");
     }
 }
@@ -0,0 +1,48 @@
+use std::io::Result as IoResult;
+
+use ratatui::{prelude::Backend, Frame, Terminal};
+
+use crate::{event::KeyEvent, WebRenderer};
+
+/// Runs a Ratzilla application with the given backend.
+///
+/// This is the canonical way of starting a Ratzilla application. It installs
+/// a panic hook that logs panics to the browser console, creates a [`Terminal`]
+/// with the given backend and starts the render loop via [`WebRenderer::draw_web`].
+///
+/// ```rust no_run
+/// use ratzilla::{ratatui::widgets::Paragraph, DomBackend};
+///
+/// fn main() -> std::io::Result<()> {
+///     ratzilla::run(DomBackend::new()?, |frame| {
+///         frame.render_widget(Paragraph::new("Hello, world!"), frame.area());
+///     })
+/// }
+/// ```
+pub fn run<B, F>(backend: B, render_callback: F) -> IoResult<()>
+where
+    B: Backend<Error = std::io::Error> + 'static,
+    F: FnMut(&mut Frame) + 'static,
+{
+    console_error_panic_hook::set_once();
+    let terminal = Terminal::new(backend)?;
+    terminal.draw_web(render_callback);
+    Ok(())
+}
+
+/// Runs a Ratzilla application with the given backend and key event handler.
+///
+/// This works like [`run`], but also registers the given callback for key
+/// events via [`WebRenderer::on_key_event`].
+pub fn run_with_events<B, K, F>(backend: B, key_callback: K, render_callback: F) -> IoResult<()>
+where
+    B: Backend<Error = std::io::Error> + 'static,
+    K: FnMut(KeyEvent) + 'static,
+    F: FnMut(&mut Frame) + 'static,
+{
+    console_error_panic_hook::set_once();
+    let terminal = Terminal::new(backend)?;
+    terminal.on_key_event(key_callback);
+    terminal.draw_web(render_callback);
+    Ok(())
+}
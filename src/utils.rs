@@ -1,16 +1,36 @@
-use ratatui::layout::Size;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use ratatui::{
+    buffer::Cell,
+    layout::{Position, Size},
+    style::Color,
+};
 
 use crate::{
-    backend::utils::{get_raw_screen_size, get_raw_window_size},
+    backend::{
+        cursor::CursorShape,
+        utils::{get_canvas_color, get_document, get_raw_screen_size, get_raw_window_size},
+    },
     error::Error,
+    event::{
+        normalize_wheel_delta, HandlerId, KeyCode, KeyEvent, KeyEventKind, MouseEvent,
+        MouseEventKind, TextInput, PIXELS_PER_LINE,
+    },
+    render::{add_event_listener, patch_console, remove_event_listener, ListenerClosure},
 };
 
 use web_sys::{
     js_sys::{Array, Function, Reflect},
     wasm_bindgen::{prelude::*, JsValue},
+    CanvasRenderingContext2d, HtmlCanvasElement, HtmlElement, HtmlTextAreaElement,
+    KeyboardEventInit, MouseEventInit, WheelEventInit,
 };
 
 /// Sets the document title.
+///
+/// The backends also call this when a cell symbol contains the `OSC 0` or
+/// `OSC 2` escape sequence (e.g. `"\x1b]2;title\x07"`), so that applications
+/// which set their title via the terminal output work as expected.
 pub fn set_document_title(title: &str) -> Result<(), Error> {
     web_sys::window()
         .ok_or(Error::UnableToRetrieveWindow)?
@@ -20,6 +40,259 @@ pub fn set_document_title(title: &str) -> Result<(), Error> {
     Ok(())
 }
 
+/// Stops rendering and removes all event listeners of every terminal.
+///
+/// This is the same as [`WebRenderer::shutdown_all`] but can be called after the
+/// terminal was moved into [`WebRenderer::draw_web`], e.g. from an event handler
+/// or when the route of a single page application changes.
+///
+/// [`WebRenderer::shutdown_all`]: crate::WebRenderer::shutdown_all
+/// [`WebRenderer::draw_web`]: crate::WebRenderer::draw_web
+pub fn shutdown_all() {
+    crate::render::shutdown_all();
+}
+
+/// Returns the cell at the given position of the last drawn frame.
+///
+/// This is the same as [`WebRenderer::cell_at`] but can be called after the
+/// terminal was moved into [`WebRenderer::draw_web`], e.g. from a mouse event
+/// handler for showing details about the hovered cell.
+///
+/// [`WebRenderer::cell_at`]: crate::WebRenderer::cell_at
+/// [`WebRenderer::draw_web`]: crate::WebRenderer::draw_web
+pub fn cell_at(position: Position) -> Option<Cell> {
+    crate::render::cell_at(position)
+}
+
+/// Returns `true` if the first frame has been drawn.
+///
+/// This is the same as [`WebRenderer::is_ready`] but can be called after the
+/// terminal was moved into [`WebRenderer::draw_web`], e.g. from a callback that
+/// receives data which should only be shown once the grid is set up.
+///
+/// [`WebRenderer::is_ready`]: crate::WebRenderer::is_ready
+/// [`WebRenderer::draw_web`]: crate::WebRenderer::draw_web
+pub fn is_ready() -> bool {
+    crate::render::is_ready()
+}
+
+/// Returns the position of the terminal cursor, or `None` if it is hidden.
+///
+/// This is the same as [`WebRenderer::cursor_position`] but can be called
+/// after the terminal was moved into [`WebRenderer::draw_web`], e.g. from the
+/// render callback for showing the cursor position in a status line.
+///
+/// [`WebRenderer::cursor_position`]: crate::WebRenderer::cursor_position
+/// [`WebRenderer::draw_web`]: crate::WebRenderer::draw_web
+pub fn cursor_position() -> Option<Position> {
+    crate::render::cursor_position()
+}
+
+/// Sets the shape of the cursor at runtime.
+///
+/// This is the same as [`WebRenderer::set_cursor_shape`] but can be called
+/// after the terminal was moved into [`WebRenderer::draw_web`], e.g. from a
+/// key event handler when switching the editing mode.
+///
+/// [`WebRenderer::set_cursor_shape`]: crate::WebRenderer::set_cursor_shape
+/// [`WebRenderer::draw_web`]: crate::WebRenderer::draw_web
+pub fn set_cursor_shape(shape: CursorShape) {
+    crate::render::set_cursor_shape(shape);
+}
+
+/// Adds a key handler with the given priority.
+///
+/// This is the same as [`WebRenderer::add_key_handler`] but can be called
+/// after the terminal was moved into [`WebRenderer::draw_web`], e.g. when a
+/// widget gains focus.
+///
+/// [`WebRenderer::add_key_handler`]: crate::WebRenderer::add_key_handler
+/// [`WebRenderer::draw_web`]: crate::WebRenderer::draw_web
+pub fn add_key_handler<F>(priority: i32, handler: F) -> Result<HandlerId, Error>
+where
+    F: FnMut(&KeyEvent) -> bool + 'static,
+{
+    crate::render::add_key_handler(priority, handler)
+}
+
+/// Removes the key handler with the given identifier.
+///
+/// This is the same as [`WebRenderer::remove_key_handler`] but can be called
+/// after the terminal was moved into [`WebRenderer::draw_web`], e.g. when a
+/// widget loses focus.
+///
+/// [`WebRenderer::remove_key_handler`]: crate::WebRenderer::remove_key_handler
+/// [`WebRenderer::draw_web`]: crate::WebRenderer::draw_web
+pub fn remove_key_handler(id: HandlerId) {
+    crate::render::remove_key_handler(id);
+}
+
+/// Removes the paste handler with the given identifier.
+///
+/// This is the same as [`WebRenderer::remove_paste_handler`] but can be called
+/// after the terminal was moved into [`WebRenderer::draw_web`], e.g. when a
+/// text input loses focus.
+///
+/// [`WebRenderer::remove_paste_handler`]: crate::WebRenderer::remove_paste_handler
+/// [`WebRenderer::draw_web`]: crate::WebRenderer::draw_web
+pub fn remove_paste_handler(id: HandlerId) {
+    crate::render::remove_paste_handler(id);
+}
+
+/// Requests a frame to be drawn by [`WebRenderer::draw_web_on_demand`].
+///
+/// This is the same as [`WebRenderer::request_redraw`] but can be called after
+/// the terminal was moved into [`WebRenderer::draw_web_on_demand`], e.g. when
+/// data arrives or from the render callback of an animation.
+///
+/// [`WebRenderer::draw_web_on_demand`]: crate::WebRenderer::draw_web_on_demand
+/// [`WebRenderer::request_redraw`]: crate::WebRenderer::request_redraw
+pub fn request_redraw() {
+    crate::render::request_redraw();
+}
+
+/// Returns the time since the previous frame, in frames at 60 FPS.
+///
+/// This is the same as [`WebRenderer::frame_step`] but can be called after the
+/// terminal was moved into [`WebRenderer::draw_web`], e.g. from the render
+/// callback for advancing animations at the same speed on all displays.
+///
+/// [`WebRenderer::frame_step`]: crate::WebRenderer::frame_step
+/// [`WebRenderer::draw_web`]: crate::WebRenderer::draw_web
+pub fn frame_step() -> f64 {
+    crate::render::frame_step()
+}
+
+/// Returns how long drawing the last frame took.
+///
+/// This is the same as [`WebRenderer::last_frame_duration`] but can be called
+/// after the terminal was moved into [`WebRenderer::draw_web`], e.g. from the
+/// render callback for adapting to slow frames.
+///
+/// [`WebRenderer::last_frame_duration`]: crate::WebRenderer::last_frame_duration
+/// [`WebRenderer::draw_web`]: crate::WebRenderer::draw_web
+pub fn last_frame_duration() -> Option<Duration> {
+    crate::render::last_frame_duration()
+}
+
+/// Enables or disables logging the keys that aren't recognized.
+///
+/// Keys that don't map to any [`KeyCode`] are reported as
+/// [`KeyCode::Unidentified`], which makes it hard to tell why a key doesn't
+/// work. When enabled, the raw `key` and `code` of such keys (e.g. keypad or
+/// media keys) are logged to the console as warnings, once per distinct key.
+///
+/// [`KeyCode`]: crate::event::KeyCode
+/// [`KeyCode::Unidentified`]: crate::event::KeyCode::Unidentified
+pub fn debug_unidentified_keys(enabled: bool) {
+    crate::event::set_key_debug(enabled);
+}
+
+/// ID of the favicon element that shows the progress of [`set_tab_progress`].
+const TAB_PROGRESS_FAVICON_ID: &str = "ratzilla-tab-progress";
+
+/// Size of the progress favicon, in pixels.
+const TAB_PROGRESS_FAVICON_SIZE: u32 = 32;
+
+/// Shows the given progress in the browser tab.
+///
+/// The `fraction` (between `0.0` and `1.0`) is shown as a percentage in front
+/// of the document title, e.g. `"(42%) My App"`, and as a progress ring in the
+/// favicon. This surfaces the progress of long running tasks when the tab is
+/// in the background.
+///
+/// Use [`clear_tab_progress`] to restore the title and the favicon.
+pub fn set_tab_progress(fraction: f32) -> Result<(), Error> {
+    let fraction = fraction.clamp(0.0, 1.0);
+    let document = get_document()?;
+    let title = document.title();
+    document.set_title(&format!(
+        "({:.0}%) {}",
+        fraction * 100.0,
+        strip_tab_progress(&title)
+    ));
+
+    let canvas = document
+        .create_element("canvas")?
+        .dyn_into::<HtmlCanvasElement>()
+        .map_err(|_| Error::UnableToRetrieveComponent("HtmlCanvasElement"))?;
+    canvas.set_width(TAB_PROGRESS_FAVICON_SIZE);
+    canvas.set_height(TAB_PROGRESS_FAVICON_SIZE);
+    let context = canvas
+        .get_context("2d")?
+        .ok_or(Error::UnableToRetrieveCanvasContext)?
+        .dyn_into::<CanvasRenderingContext2d>()
+        .map_err(|_| Error::UnableToRetrieveCanvasContext)?;
+
+    let center = TAB_PROGRESS_FAVICON_SIZE as f64 / 2.0;
+    let radius = center - 4.0;
+    let start = -std::f64::consts::FRAC_PI_2;
+    context.set_line_width(6.0);
+    context.set_stroke_style_str("rgba(128, 128, 128, 0.5)");
+    context.begin_path();
+    context.arc(center, center, radius, 0.0, std::f64::consts::TAU)?;
+    context.stroke();
+    context.set_stroke_style_str("#4ade80");
+    context.begin_path();
+    context.arc(
+        center,
+        center,
+        radius,
+        start,
+        start + std::f64::consts::TAU * fraction as f64,
+    )?;
+    context.stroke();
+
+    // The favicon that is declared last takes precedence over the existing ones
+    let link = match document.get_element_by_id(TAB_PROGRESS_FAVICON_ID) {
+        Some(link) => link,
+        None => {
+            let link = document.create_element("link")?;
+            link.set_id(TAB_PROGRESS_FAVICON_ID);
+            link.set_attribute("rel", "icon")?;
+            document
+                .head()
+                .ok_or(Error::UnableToRetrieveComponent("head"))?
+                .append_child(&link)?;
+            link
+        }
+    };
+    link.set_attribute("href", &canvas.to_data_url()?)?;
+    Ok(())
+}
+
+/// Removes the progress that was shown via [`set_tab_progress`].
+pub fn clear_tab_progress() -> Result<(), Error> {
+    let document = get_document()?;
+    let title = document.title();
+    document.set_title(strip_tab_progress(&title));
+    if let Some(link) = document.get_element_by_id(TAB_PROGRESS_FAVICON_ID) {
+        link.remove();
+    }
+    Ok(())
+}
+
+/// Removes the progress prefix (e.g. `"(42%) "`) from the given title.
+fn strip_tab_progress(title: &str) -> &str {
+    title
+        .strip_prefix('(')
+        .and_then(|rest| rest.split_once("%) "))
+        .filter(|(percentage, _)| percentage.chars().all(|c| c.is_ascii_digit()))
+        .map_or(title, |(_, title)| title)
+}
+
+/// Serializes the last drawn frame into text with ANSI escape sequences.
+///
+/// This is the same as [`WebRenderer::to_ansi_string`] but can be called after
+/// the terminal was moved into [`WebRenderer::draw_web`], e.g. from a key event
+/// handler for copying the screen.
+///
+/// [`WebRenderer::to_ansi_string`]: crate::WebRenderer::to_ansi_string
+/// [`WebRenderer::draw_web`]: crate::WebRenderer::draw_web
+pub fn to_ansi_string() -> String {
+    crate::render::to_ansi_string()
+}
+
 /// Open a URL in a new tab or the current tab.
 pub fn open_url(url: &str, new_tab: bool) -> Result<(), Error> {
     let window = web_sys::window().ok_or(Error::UnableToRetrieveWindow)?;
@@ -33,6 +306,740 @@ pub fn open_url(url: &str, new_tab: bool) -> Result<(), Error> {
     Ok(())
 }
 
+/// Briefly flashes the screen with the given color.
+///
+/// An overlay covering the whole viewport is shown with the given color and
+/// faded out over `duration`, after which it is removed from the document.
+/// The overlay does not capture any pointer events.
+pub fn flash_screen(color: Color, duration: Duration) -> Result<(), Error> {
+    let window = web_sys::window().ok_or(Error::UnableToRetrieveWindow)?;
+    let document = window.document().ok_or(Error::UnableToRetrieveDocument)?;
+    let body = document.body().ok_or(Error::UnableToRetrieveBody)?;
+
+    let duration_ms = duration.as_millis() as i32;
+    let style = |opacity: u8| {
+        format!(
+            "position: fixed; inset: 0; pointer-events: none; z-index: 2147483647; \
+             background-color: {}; opacity: {opacity}; transition: opacity {duration_ms}ms ease-out;",
+            get_canvas_color(color, Color::White)
+        )
+    };
+
+    let overlay = document.create_element("div")?;
+    overlay.set_attribute("style", &style(1))?;
+    body.append_child(&overlay)?;
+
+    // Force a layout so that the transition starts from the full opacity
+    if let Some(element) = overlay.dyn_ref::<HtmlElement>() {
+        element.offset_width();
+    }
+    overlay.set_attribute("style", &style(0))?;
+
+    let remove_overlay = Closure::once_into_js(move || overlay.remove());
+    window.set_timeout_with_callback_and_timeout_and_arguments_0(
+        remove_overlay.unchecked_ref(),
+        duration_ms,
+    )?;
+    Ok(())
+}
+
+/// Renders an HTML overlay on top of the page.
+///
+/// The overlay is a `div` element with the given ID that is appended to the
+/// body. If an element with the same ID already exists, its content and style
+/// are replaced instead, so this can be called repeatedly for updating status
+/// bars, footers or toasts. The `style` is set as the inline CSS of the
+/// element, e.g. `"position: fixed; bottom: 0; left: 0; right: 0;"`.
+///
+/// Use [`remove_overlay`] for removing the overlay.
+pub fn render_overlay_html(id: &str, html: &str, style: &str) -> Result<(), Error> {
+    let document = get_document()?;
+    let overlay = match document.get_element_by_id(id) {
+        Some(overlay) => overlay,
+        None => {
+            let overlay = document.create_element("div")?;
+            overlay.set_id(id);
+            document
+                .body()
+                .ok_or(Error::UnableToRetrieveBody)?
+                .append_child(&overlay)?;
+            overlay
+        }
+    };
+    overlay.set_attribute("style", style)?;
+    overlay.set_inner_html(html);
+    Ok(())
+}
+
+/// Removes the overlay with the given ID that was rendered via
+/// [`render_overlay_html`].
+///
+/// Returns `false` if there is no such overlay.
+pub fn remove_overlay(id: &str) -> Result<bool, Error> {
+    let document = get_document()?;
+    let overlay = document.get_element_by_id(id);
+    if let Some(overlay) = &overlay {
+        overlay.remove();
+    }
+    Ok(overlay.is_some())
+}
+
+/// ID of the overlay shown via [`show_busy_overlay`].
+const BUSY_OVERLAY_ID: &str = "ratzilla-busy-overlay";
+
+/// Shows a spinner overlay with the given message on top of the page.
+///
+/// The message is shown as plain text below the spinner. This gives feedback
+/// during long synchronous work (e.g. in an event handler), which otherwise
+/// freezes the page without any indication. Call [`hide_busy_overlay`] once
+/// the work is done.
+///
+/// The overlay is inserted and a style and layout recalculation is forced
+/// before returning. Note that the browser still can't paint while the main
+/// thread is blocked, so whether the overlay becomes visible before the work
+/// finishes depends on the browser: the spinner animates on the compositor in
+/// most browsers, but for reliable feedback the work should be started in the
+/// next frame (e.g. via `requestAnimationFrame`) or moved off the main thread.
+pub fn show_busy_overlay(message: &str) -> Result<(), Error> {
+    let html = format!(
+        "<style>@keyframes {BUSY_OVERLAY_ID}-spin {{ to {{ transform: rotate(360deg); }} }}</style>\
+         <div style=\"width: 2em; height: 2em; border: 0.25em solid rgba(255, 255, 255, 0.3); \
+         border-top-color: white; border-radius: 50%; will-change: transform; \
+         animation: {BUSY_OVERLAY_ID}-spin 0.8s linear infinite;\"></div>"
+    );
+    render_overlay_html(
+        BUSY_OVERLAY_ID,
+        &html,
+        "position: fixed; inset: 0; z-index: 2147483647; display: flex; \
+         flex-direction: column; align-items: center; justify-content: center; gap: 1em; \
+         background: rgba(0, 0, 0, 0.5); color: white; font-family: monospace; cursor: wait;",
+    )?;
+    let document = get_document()?;
+    let overlay = document
+        .get_element_by_id(BUSY_OVERLAY_ID)
+        .ok_or_else(|| Error::UnableToRetrieveElementById(BUSY_OVERLAY_ID.to_string()))?;
+    let text = document.create_element("div")?;
+    text.set_text_content(Some(message));
+    overlay.append_child(&text)?;
+    // Reading the layout flushes the pending style and layout changes
+    if let Some(overlay) = overlay.dyn_ref::<HtmlElement>() {
+        overlay.offset_height();
+    }
+    Ok(())
+}
+
+/// Hides the overlay shown via [`show_busy_overlay`].
+pub fn hide_busy_overlay() -> Result<(), Error> {
+    remove_overlay(BUSY_OVERLAY_ID).map(|_| ())
+}
+
+/// Tag name of the custom element registered via [`register_terminal_element`].
+pub const TERMINAL_ELEMENT_NAME: &str = "ratzilla-terminal";
+
+/// Registers the `<ratzilla-terminal>` custom element.
+///
+/// The element is a block-level container for the terminal, so it can be
+/// dropped into any HTML page or front-end framework and sized with CSS like
+/// any other element. Pointing a backend at the element via its ID (e.g.
+/// [`DomBackendOptions::new`] or [`CanvasBackendOptions::grid_id`]) renders
+/// the application inside of it:
+///
+/// ```html
+/// <ratzilla-terminal id="terminal" style="height: 400px"></ratzilla-terminal>
+/// ```
+///
+/// Elements that are already in the page are upgraded when the element is
+/// registered. Registering the element more than once has no effect.
+///
+/// [`DomBackendOptions::new`]: crate::backend::dom::DomBackendOptions::new
+/// [`CanvasBackendOptions::grid_id`]: crate::backend::canvas::CanvasBackendOptions::grid_id
+pub fn register_terminal_element() -> Result<(), Error> {
+    let define = Function::new_with_args(
+        "name",
+        "if (customElements.get(name)) return;
+        customElements.define(name, class extends HTMLElement {
+            connectedCallback() {
+                this.style.display ||= 'block';
+                this.style.position ||= 'relative';
+                this.style.overflow ||= 'hidden';
+            }
+        });",
+    );
+    define.call1(&JsValue::NULL, &JsValue::from_str(TERMINAL_ELEMENT_NAME))?;
+    Ok(())
+}
+
+/// Dispatches a synthetic key event.
+///
+/// The event is dispatched as a `keydown` event on the document (or `keyup` for
+/// [`KeyEventKind::Release`]), so it goes through the same path as the real key
+/// events and reaches the handlers that were registered via
+/// [`WebRenderer::on_key_event`]. This is useful for
+/// self-running demos and for testing the event handlers of an application.
+///
+/// [`WebRenderer::on_key_event`]: crate::WebRenderer::on_key_event
+/// [`KeyEventKind::Release`]: crate::event::KeyEventKind::Release
+pub fn simulate_key(event: KeyEvent) -> Result<(), Error> {
+    let init = KeyboardEventInit::new();
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    init.set_key(&event.code.to_key());
+    init.set_ctrl_key(event.ctrl);
+    init.set_alt_key(event.alt);
+    init.set_shift_key(event.shift);
+    init.set_meta_key(event.meta);
+    init.set_repeat(event.kind == KeyEventKind::Repeat);
+    init.set_modifier_caps_lock(event.state.caps_lock);
+    init.set_modifier_num_lock(event.state.num_lock);
+    if event.state.keypad {
+        init.set_location(web_sys::KeyboardEvent::DOM_KEY_LOCATION_NUMPAD);
+    }
+    let event_type = match event.kind {
+        KeyEventKind::Release => "keyup",
+        _ => "keydown",
+    };
+    let event = web_sys::KeyboardEvent::new_with_keyboard_event_init_dict(event_type, &init)?;
+    get_document()?.dispatch_event(&event)?;
+    Ok(())
+}
+
+/// Dispatches a synthetic mouse event.
+///
+/// The event is dispatched on the document with the type that corresponds to
+/// the [`MouseEventKind`], so it reaches the handlers that were registered via
+/// [`WebRenderer::on_mouse_event`]. Events of [`MouseEventKind::Unidentified`]
+/// are ignored.
+///
+/// [`MouseEventKind::Unidentified`]: crate::event::MouseEventKind::Unidentified
+/// [`WebRenderer::on_mouse_event`]: crate::WebRenderer::on_mouse_event
+pub fn simulate_mouse(event: MouseEvent) -> Result<(), Error> {
+    let Some(event_type) = event.event.event_type() else {
+        return Ok(());
+    };
+    let init = MouseEventInit::new();
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    init.set_button((&event.button).into());
+    init.set_client_x(event.x as i32);
+    init.set_client_y(event.y as i32);
+    init.set_ctrl_key(event.ctrl);
+    init.set_alt_key(event.alt);
+    init.set_shift_key(event.shift);
+    let event: web_sys::Event = match event.event {
+        MouseEventKind::Wheel { delta_x, delta_y } => {
+            let init: &WheelEventInit = init.unchecked_ref();
+            init.set_delta_mode(web_sys::WheelEvent::DOM_DELTA_PIXEL);
+            init.set_delta_x(delta_x as f64);
+            init.set_delta_y(delta_y as f64);
+            web_sys::WheelEvent::new_with_event_init_dict(event_type, init)?.into()
+        }
+        _ => web_sys::MouseEvent::new_with_mouse_event_init_dict(event_type, &init)?.into(),
+    };
+    get_document()?.dispatch_event(&event)?;
+    Ok(())
+}
+
+/// Handles the browser back button within the application.
+///
+/// This pushes a history entry for the application and intercepts the
+/// [`popstate`] event, so that pressing the back button calls the given
+/// callback instead of leaving the page.
+///
+/// The callback returns whether it handled the navigation, e.g. by going to
+/// the previous screen. If it returns `false` (e.g. on the top level screen),
+/// the back button is no longer captured and the browser navigates back.
+///
+/// [`popstate`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/popstate_event
+pub fn capture_back_button<F>(mut callback: F) -> Result<(), Error>
+where
+    F: FnMut() -> bool + 'static,
+{
+    let window = web_sys::window().ok_or(Error::UnableToRetrieveWindow)?;
+    let history = window.history()?;
+    history.push_state_with_url(&JsValue::NULL, "", None)?;
+
+    let capturing = Rc::new(std::cell::Cell::new(true));
+    let closure = Closure::<dyn FnMut(_)>::new(move |_: web_sys::Event| {
+        if !capturing.get() {
+            return;
+        }
+        if callback() {
+            // Push the entry again to capture the next back button press
+            let _ = history.push_state_with_url(&JsValue::NULL, "", None);
+        } else {
+            capturing.set(false);
+            let _ = history.back();
+        }
+    });
+    add_event_listener(&window, "popstate", &Rc::new(closure))
+}
+
+/// ID of the hidden textarea element that receives the input of [`on_text_input`].
+const TEXT_INPUT_ID: &str = "ratzilla-text-input";
+
+/// Receives the text input of virtual (on-screen) keyboards.
+///
+/// Mobile browsers don't deliver reliable `keydown` events for on-screen
+/// keyboards, and they only show the keyboard for focused text fields. This
+/// adds a hidden `<textarea>` that is focused on every click or tap and
+/// translates its [`input`] events into [`TextInput`]s.
+///
+/// Keys that are identified by the `keydown` event (e.g. on physical keyboards)
+/// are not inserted into the textarea, so they are only delivered as key events
+/// and not twice.
+///
+/// [`input`]: https://developer.mozilla.org/en-US/docs/Web/API/Element/input_event
+pub fn on_text_input<F>(callback: F) -> Result<(), Error>
+where
+    F: FnMut(TextInput) + 'static,
+{
+    // Keeps a character in the textarea so that backspace always fires an event
+    const SENTINEL: &str = " ";
+
+    let document = get_document()?;
+    let textarea = document
+        .create_element("textarea")?
+        .dyn_into::<HtmlTextAreaElement>()
+        .map_err(|_| Error::UnableToRetrieveComponent("HtmlTextAreaElement"))?;
+    textarea.set_id(TEXT_INPUT_ID);
+    textarea.set_attribute("autocapitalize", "off")?;
+    textarea.set_attribute("autocomplete", "off")?;
+    textarea.set_attribute("spellcheck", "false")?;
+    textarea.set_value(SENTINEL);
+    let style = textarea.style();
+    style.set_property("position", "fixed")?;
+    style.set_property("left", "0")?;
+    style.set_property("bottom", "0")?;
+    style.set_property("width", "1px")?;
+    style.set_property("height", "1px")?;
+    style.set_property("opacity", "0")?;
+    style.set_property("pointer-events", "none")?;
+    document
+        .body()
+        .ok_or(Error::UnableToRetrieveBody)?
+        .append_child(&textarea)?;
+
+    let callback = Rc::new(RefCell::new(callback));
+    let reset = {
+        let textarea = textarea.clone();
+        move || {
+            textarea.set_value(SENTINEL);
+            let _ = textarea.set_selection_range(1, 1);
+        }
+    };
+
+    let on_keydown = Closure::<dyn FnMut(_)>::new(|event: web_sys::Event| {
+        let event = event.unchecked_into::<web_sys::KeyboardEvent>();
+        // Virtual keyboards report unidentified keys while composing
+        if event.key() != "Unidentified" && !event.is_composing() {
+            event.prevent_default();
+        }
+    });
+    add_event_listener(&textarea, "keydown", &Rc::new(on_keydown))?;
+
+    let on_input = Closure::<dyn FnMut(_)>::new({
+        let callback = callback.clone();
+        let reset = reset.clone();
+        move |event: web_sys::Event| {
+            let event = event.unchecked_into::<web_sys::InputEvent>();
+            if event.is_composing() {
+                return;
+            }
+            if let Some(input) = TextInput::from_input_type(&event.input_type(), event.data()) {
+                callback.borrow_mut()(input);
+            }
+            reset();
+        }
+    });
+    add_event_listener(&textarea, "input", &Rc::new(on_input))?;
+
+    let on_composition_end = Closure::<dyn FnMut(_)>::new({
+        let reset = reset.clone();
+        move |event: web_sys::Event| {
+            let event = event.unchecked_into::<web_sys::CompositionEvent>();
+            if let Some(data) = event.data().filter(|data| !data.is_empty()) {
+                callback.borrow_mut()(TextInput::Insert(data));
+            }
+            reset();
+        }
+    });
+    add_event_listener(&textarea, "compositionend", &Rc::new(on_composition_end))?;
+
+    // Focus the textarea on every click or tap to show the virtual keyboard
+    let on_click = Closure::<dyn FnMut(_)>::new(move |_: web_sys::Event| {
+        let _ = textarea.focus();
+        reset();
+    });
+    add_event_listener(&document, "click", &Rc::new(on_click))
+}
+
+/// Registers a callback that is called when the page is unloaded.
+///
+/// This is useful for cleaning up external resources such as WebSockets or
+/// intervals, e.g. along with [`shutdown_all`].
+///
+/// The callback is called on the [`pagehide`] event instead of `unload`, since
+/// the latter prevents the page from being stored in the back/forward cache.
+/// Note that `pagehide` also fires when the page is put into that cache, so the
+/// page may be restored after the callback has run (and the callback may be
+/// called again when the page is hidden the next time).
+///
+/// The listener is removed by [`shutdown_all`] as well.
+///
+/// [`pagehide`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/pagehide_event
+pub fn on_unload<F>(mut callback: F) -> Result<(), Error>
+where
+    F: FnMut() + 'static,
+{
+    let window = web_sys::window().ok_or(Error::UnableToRetrieveWindow)?;
+    let closure = Closure::<dyn FnMut(_)>::new(move |_: web_sys::Event| callback());
+    add_event_listener(&window, "pagehide", &Rc::new(closure))
+}
+
+/// Level of a console message, see [`capture_console`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleLevel {
+    /// `console.debug`
+    Debug,
+    /// `console.log`
+    Log,
+    /// `console.info`
+    Info,
+    /// `console.warn`
+    Warn,
+    /// `console.error`
+    Error,
+}
+
+impl ConsoleLevel {
+    /// All console levels.
+    const ALL: [Self; 5] = [Self::Debug, Self::Log, Self::Info, Self::Warn, Self::Error];
+
+    /// Returns the name of the console function of this level.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Debug => "debug",
+            Self::Log => "log",
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// Forwards the console messages to the given callback.
+///
+/// This patches `console.log` and the other console functions (see
+/// [`ConsoleLevel`]) to also pass their messages to the callback, which is
+/// useful for showing them inside the application, e.g. for debugging on
+/// mobile devices without developer tools. The messages are still logged to
+/// the console as well.
+///
+/// The arguments of a console call are joined with spaces, and values other
+/// than strings are formatted as JSON. Messages that are logged by the callback
+/// itself are not forwarded again, which prevents an infinite recursion.
+///
+/// The original console functions are restored by [`shutdown_all`].
+pub fn capture_console<F>(callback: F) -> Result<(), Error>
+where
+    F: FnMut(ConsoleLevel, String) + 'static,
+{
+    /// Resets the forwarding flag when dropped, even if the callback panics.
+    struct Forwarding<'a>(&'a std::cell::Cell<bool>);
+    impl Drop for Forwarding<'_> {
+        fn drop(&mut self) {
+            self.0.set(false);
+        }
+    }
+
+    let window = web_sys::window().ok_or(Error::UnableToRetrieveWindow)?;
+    let console = Reflect::get(&window, &JsValue::from_str("console"))?;
+    let callback = Rc::new(RefCell::new(callback));
+    // The callback must not be called while it is running, which would happen
+    // if it logs a message itself
+    let forwarding = Rc::new(std::cell::Cell::new(false));
+    // Calls the original function and the forwarding closure with the arguments
+    let wrap = Function::new_with_args(
+        "console, original, forward",
+        "return function(...args) {
+            original.apply(console, args);
+            forward(args);
+        };",
+    );
+    for level in ConsoleLevel::ALL {
+        let name = level.as_str();
+        let original = Reflect::get(&console, &JsValue::from_str(name))?;
+        let forward = Closure::<dyn FnMut(_)>::new({
+            let callback = callback.clone();
+            let forwarding = forwarding.clone();
+            move |args: Array| {
+                if forwarding.replace(true) {
+                    return;
+                }
+                let _forwarding = Forwarding(&forwarding);
+                let message = args
+                    .iter()
+                    .map(|arg| {
+                        arg.as_string().unwrap_or_else(|| {
+                            web_sys::js_sys::JSON::stringify(&arg)
+                                .ok()
+                                .and_then(|json| json.as_string())
+                                .unwrap_or_else(|| format!("{arg:?}"))
+                        })
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                callback.borrow_mut()(level, message);
+            }
+        });
+        let patched = wrap.call3(&JsValue::NULL, &console, &original, forward.as_ref())?;
+        patch_console(&console, name, original, &patched, forward)?;
+    }
+    Ok(())
+}
+
+/// Handles two-finger pinch gestures.
+///
+/// The callback is called on every movement of a pinch gesture with the change
+/// in the distance of the fingers since the last call, e.g. `1.1` when the
+/// fingers moved 10% apart. Multiplying the factors gives the total scale.
+///
+/// Note that this does not prevent the browser from zooming the page, which
+/// can be disabled with the `touch-action` CSS property.
+pub fn on_pinch<F>(mut callback: F) -> Result<(), Error>
+where
+    F: FnMut(f64) + 'static,
+{
+    /// Returns the distance between the first two touches, if any.
+    fn touch_distance(event: &web_sys::TouchEvent) -> Option<f64> {
+        let touches = event.touches();
+        let (a, b) = (touches.get(0)?, touches.get(1)?);
+        let (dx, dy) = (a.client_x() - b.client_x(), a.client_y() - b.client_y());
+        Some(((dx * dx + dy * dy) as f64).sqrt())
+    }
+
+    let document = get_document()?;
+    let distance = Rc::new(std::cell::Cell::new(None));
+    let on_touch_start = Closure::<dyn FnMut(_)>::new({
+        let distance = distance.clone();
+        move |event: web_sys::Event| {
+            distance.set(touch_distance(event.unchecked_ref()));
+        }
+    });
+    add_event_listener(&document, "touchstart", &Rc::new(on_touch_start))?;
+    let on_touch_move = Closure::<dyn FnMut(_)>::new(move |event: web_sys::Event| {
+        let Some(current) = touch_distance(event.unchecked_ref()) else {
+            return;
+        };
+        if let Some(previous) = distance.replace(Some(current)).filter(|d| *d > 0.0) {
+            callback(current / previous);
+        }
+    });
+    add_event_listener(&document, "touchmove", &Rc::new(on_touch_move))
+}
+
+/// Handles scrolling with the mouse wheel, trackpad or touch screen.
+///
+/// The callback is called with the scrolled distance in lines, where positive
+/// values scroll down. Wheel deltas are normalized regardless of whether the
+/// browser reports them in pixels, lines or pages, and one-finger drags on touch
+/// screens scroll in the direction of the content, like native scrolling.
+///
+/// Use this together with [`ScrollInertia`] for scrolling with momentum.
+///
+/// [`ScrollInertia`]: crate::event::ScrollInertia
+pub fn on_scroll<F>(callback: F) -> Result<(), Error>
+where
+    F: FnMut(f64) + 'static,
+{
+    let document = get_document()?;
+    let callback = Rc::new(RefCell::new(callback));
+    let on_wheel = Closure::<dyn FnMut(_)>::new({
+        let callback = callback.clone();
+        move |event: web_sys::Event| {
+            let event = event.unchecked_into::<web_sys::WheelEvent>();
+            let delta = normalize_wheel_delta(event.delta_y(), event.delta_mode());
+            callback.borrow_mut()(delta);
+        }
+    });
+    add_event_listener(&document, "wheel", &Rc::new(on_wheel))?;
+
+    let last_y = Rc::new(std::cell::Cell::new(None));
+    let on_touch_start = Closure::<dyn FnMut(_)>::new({
+        let last_y = last_y.clone();
+        move |event: web_sys::Event| {
+            let touches = event.unchecked_into::<web_sys::TouchEvent>().touches();
+            last_y.set(touches.get(0).map(|touch| touch.client_y()));
+        }
+    });
+    add_event_listener(&document, "touchstart", &Rc::new(on_touch_start))?;
+    let on_touch_move = Closure::<dyn FnMut(_)>::new(move |event: web_sys::Event| {
+        let touches = event.unchecked_into::<web_sys::TouchEvent>().touches();
+        // Multiple fingers are a pinch gesture
+        let Some(touch) = touches.get(0).filter(|_| touches.length() == 1) else {
+            return;
+        };
+        if let Some(previous) = last_y.replace(Some(touch.client_y())) {
+            callback.borrow_mut()((previous - touch.client_y()) as f64 / PIXELS_PER_LINE);
+        }
+    });
+    add_event_listener(&document, "touchmove", &Rc::new(on_touch_move))
+}
+
+/// ID of the on-screen keyboard element.
+const VIRTUAL_KEYBOARD_ID: &str = "ratzilla-virtual-keyboard";
+
+thread_local! {
+    /// The click listener of the shown on-screen keyboard.
+    static VIRTUAL_KEYBOARD_LISTENER: RefCell<Option<ListenerClosure>> =
+        const { RefCell::new(None) };
+}
+
+/// The default layout of the on-screen keyboard, see [`show_virtual_keyboard`].
+pub const VIRTUAL_KEYBOARD_LAYOUT: &[&[KeyCode]] = &[
+    &[
+        KeyCode::Char('1'),
+        KeyCode::Char('2'),
+        KeyCode::Char('3'),
+        KeyCode::Char('4'),
+        KeyCode::Char('5'),
+        KeyCode::Char('6'),
+        KeyCode::Char('7'),
+        KeyCode::Char('8'),
+        KeyCode::Char('9'),
+        KeyCode::Char('0'),
+    ],
+    &[
+        KeyCode::Char('q'),
+        KeyCode::Char('w'),
+        KeyCode::Char('e'),
+        KeyCode::Char('r'),
+        KeyCode::Char('t'),
+        KeyCode::Char('y'),
+        KeyCode::Char('u'),
+        KeyCode::Char('i'),
+        KeyCode::Char('o'),
+        KeyCode::Char('p'),
+    ],
+    &[
+        KeyCode::Char('a'),
+        KeyCode::Char('s'),
+        KeyCode::Char('d'),
+        KeyCode::Char('f'),
+        KeyCode::Char('g'),
+        KeyCode::Char('h'),
+        KeyCode::Char('j'),
+        KeyCode::Char('k'),
+        KeyCode::Char('l'),
+    ],
+    &[
+        KeyCode::Char('z'),
+        KeyCode::Char('x'),
+        KeyCode::Char('c'),
+        KeyCode::Char('v'),
+        KeyCode::Char('b'),
+        KeyCode::Char('n'),
+        KeyCode::Char('m'),
+        KeyCode::Backspace,
+    ],
+    &[
+        KeyCode::Esc,
+        KeyCode::Tab,
+        KeyCode::Char(' '),
+        KeyCode::Left,
+        KeyCode::Up,
+        KeyCode::Down,
+        KeyCode::Right,
+        KeyCode::Enter,
+    ],
+];
+
+/// Returns the label of the given key on the on-screen keyboard.
+fn virtual_key_label(code: &KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char('<') => "&lt;".to_string(),
+        KeyCode::Char('>') => "&gt;".to_string(),
+        KeyCode::Char('&') => "&amp;".to_string(),
+        KeyCode::Backspace => "⌫".to_string(),
+        KeyCode::Enter => "⏎".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        code => code.to_key(),
+    }
+}
+
+/// Shows an on-screen keyboard at the bottom of the page.
+///
+/// The keyboard consists of the given rows of keys (e.g.
+/// [`VIRTUAL_KEYBOARD_LAYOUT`]) and is rendered as an overlay. Tapping a key
+/// dispatches a synthetic key event via [`simulate_key`], so the application
+/// receives it exactly like a key press on a physical keyboard. This makes
+/// keyboard-driven applications usable on touch-only devices without relying
+/// on the native soft keyboard.
+///
+/// Calling this again replaces the keyboard with the given layout.
+pub fn show_virtual_keyboard(layout: &[&[KeyCode]]) -> Result<(), Error> {
+    hide_virtual_keyboard()?;
+    let mut keys = Vec::new();
+    let mut html = String::new();
+    for row in layout {
+        html.push_str("<div style=\"display: flex; justify-content: center; gap: 4px;\">");
+        for code in row.iter() {
+            let grow = if *code == KeyCode::Char(' ') { 4 } else { 1 };
+            html.push_str(&format!(
+                "<button data-key=\"{}\" style=\"flex: {grow} 1 0; max-width: {}em; \
+                 padding: 12px 0; font: inherit; color: inherit; background: #333; \
+                 border: none; border-radius: 4px; touch-action: manipulation;\">{}</button>",
+                keys.len(),
+                3 * grow,
+                virtual_key_label(code)
+            ));
+            keys.push(code.clone());
+        }
+        html.push_str("</div>");
+    }
+    render_overlay_html(
+        VIRTUAL_KEYBOARD_ID,
+        &html,
+        "position: fixed; bottom: 0; left: 0; right: 0; z-index: 1000; \
+         display: flex; flex-direction: column; gap: 4px; padding: 4px; \
+         background: rgba(0, 0, 0, 0.8); color: white; font-family: monospace;",
+    )?;
+
+    let keyboard = get_document()?
+        .get_element_by_id(VIRTUAL_KEYBOARD_ID)
+        .ok_or_else(|| Error::UnableToRetrieveElementById(VIRTUAL_KEYBOARD_ID.to_string()))?;
+    let on_click = Closure::<dyn FnMut(_)>::new(move |event: web_sys::Event| {
+        // Keep the click from focusing the text input, which would bring up
+        // the native keyboard
+        event.stop_propagation();
+        let key = event
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+            .and_then(|target| target.closest("[data-key]").ok().flatten())
+            .and_then(|button| button.get_attribute("data-key"))
+            .and_then(|index| keys.get(index.parse::<usize>().ok()?).cloned());
+        if let Some(code) = key {
+            let _ = simulate_key(code.into());
+        }
+    });
+    let on_click = Rc::new(on_click);
+    add_event_listener(&keyboard, "click", &on_click)?;
+    VIRTUAL_KEYBOARD_LISTENER.set(Some(on_click));
+    Ok(())
+}
+
+/// Hides the on-screen keyboard that was shown via [`show_virtual_keyboard`].
+pub fn hide_virtual_keyboard() -> Result<(), Error> {
+    if let Some(on_click) = VIRTUAL_KEYBOARD_LISTENER.take() {
+        remove_event_listener(&on_click);
+    }
+    remove_overlay(VIRTUAL_KEYBOARD_ID).map(|_| ())
+}
+
 /// Returns `true` if the screen is a mobile device.
 pub fn is_mobile() -> bool {
     let user_agent = web_sys::window().and_then(|w| w.navigator().user_agent().ok());
@@ -42,6 +1049,142 @@ pub fn is_mobile() -> bool {
     })
 }
 
+/// Returns `true` if the platform is made by Apple (macOS or iOS).
+///
+/// On these platforms, the Cmd key (reported as `meta`) takes the role of the
+/// Ctrl key in keyboard shortcuts.
+pub fn is_apple_platform() -> bool {
+    let user_agent = web_sys::window().and_then(|w| w.navigator().user_agent().ok());
+    user_agent.is_some_and(|agent| {
+        ["Macintosh", "Mac OS X", "iPhone", "iPad"]
+            .iter()
+            .any(|platform| agent.contains(platform))
+    })
+}
+
+/// Returns `true` if the key event is the combo for submitting text.
+///
+/// Text widgets usually insert a newline on Enter and submit on a modified
+/// Enter instead. This follows the convention of the platform, which is
+/// `Cmd+Enter` on Apple platforms (see [`is_apple_platform`]) and `Ctrl+Enter`
+/// elsewhere.
+pub fn is_submit_combo(event: &KeyEvent) -> bool {
+    event.is_submit_combo_on(is_apple_platform())
+}
+
+/// A web browser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Browser {
+    /// Google Chrome (or Chromium).
+    Chrome,
+    /// Microsoft Edge.
+    Edge,
+    /// Mozilla Firefox.
+    Firefox,
+    /// Opera.
+    Opera,
+    /// Apple Safari.
+    Safari,
+    /// Unknown browser.
+    Unknown,
+}
+
+/// The engine of a web browser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrowserEngine {
+    /// Blink (Chrome, Edge, Opera).
+    Blink,
+    /// Gecko (Firefox).
+    Gecko,
+    /// WebKit (Safari and all browsers on iOS).
+    WebKit,
+    /// Unknown engine.
+    Unknown,
+}
+
+/// Information about the web browser, see [`browser_info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BrowserInfo {
+    /// The browser.
+    pub browser: Browser,
+    /// The major version of the browser, if known.
+    pub version: Option<u32>,
+    /// The engine of the browser.
+    pub engine: BrowserEngine,
+}
+
+impl BrowserInfo {
+    /// Unknown browser information.
+    pub const UNKNOWN: Self = Self {
+        browser: Browser::Unknown,
+        version: None,
+        engine: BrowserEngine::Unknown,
+    };
+
+    /// Parses the given user agent string.
+    ///
+    /// Returns [`BrowserInfo::UNKNOWN`] if the user agent is not recognized.
+    pub fn from_user_agent(user_agent: &str) -> Self {
+        // The order matters since most user agents mention multiple browsers
+        const TOKENS: [(&str, Browser); 8] = [
+            ("Edg/", Browser::Edge),
+            ("EdgiOS/", Browser::Edge),
+            ("OPR/", Browser::Opera),
+            ("Firefox/", Browser::Firefox),
+            ("FxiOS/", Browser::Firefox),
+            ("CriOS/", Browser::Chrome),
+            ("Chrome/", Browser::Chrome),
+            ("Version/", Browser::Safari),
+        ];
+        let Some((browser, version)) = TOKENS.iter().find_map(|(token, browser)| {
+            let (_, rest) = user_agent.split_once(token)?;
+            if *browser == Browser::Safari && !user_agent.contains("Safari/") {
+                return None;
+            }
+            let version = rest.split(|c: char| !c.is_ascii_digit()).next()?;
+            Some((*browser, version.parse().ok()))
+        }) else {
+            return Self::UNKNOWN;
+        };
+        let engine = if user_agent.contains("iPhone") || user_agent.contains("iPad") {
+            BrowserEngine::WebKit
+        } else {
+            match browser {
+                Browser::Chrome | Browser::Edge | Browser::Opera => BrowserEngine::Blink,
+                Browser::Firefox => BrowserEngine::Gecko,
+                Browser::Safari => BrowserEngine::WebKit,
+                Browser::Unknown => BrowserEngine::Unknown,
+            }
+        };
+        Self {
+            browser,
+            version,
+            engine,
+        }
+    }
+
+    /// Returns `true` if the browser is known to have an unreliable WebGL2
+    /// implementation.
+    ///
+    /// This is the case for Safari before version 15, where WebGL2 was
+    /// experimental and disabled by default.
+    pub fn has_broken_webgl2(&self) -> bool {
+        self.browser == Browser::Safari && self.version.is_some_and(|version| version < 15)
+    }
+}
+
+/// Returns information about the web browser, parsed from the user agent.
+///
+/// This is a heuristic that is useful for working around browser-specific
+/// issues and for bug reports. Since user agents can be spoofed, unrecognized
+/// values result in [`BrowserInfo::UNKNOWN`].
+pub fn browser_info() -> BrowserInfo {
+    web_sys::window()
+        .and_then(|w| w.navigator().user_agent().ok())
+        .map(|agent| BrowserInfo::from_user_agent(&agent))
+        .unwrap_or(BrowserInfo::UNKNOWN)
+}
+
 /// Returns the number of characters that can fit in the window (viewport of the browser or terminal).
 pub fn get_window_size() -> Size {
     let (w, h) = get_raw_window_size();
@@ -176,3 +1319,43 @@ where
 {
     call_js_function_with_context(name, JsValue::NULL, args)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_browser_info_from_user_agent() {
+        let chrome = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/126.0.0.0 Safari/537.36";
+        assert_eq!(
+            BrowserInfo::from_user_agent(chrome),
+            BrowserInfo {
+                browser: Browser::Chrome,
+                version: Some(126),
+                engine: BrowserEngine::Blink,
+            }
+        );
+        let edge = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/126.0.0.0 Safari/537.36 Edg/126.0.2592.87";
+        assert_eq!(BrowserInfo::from_user_agent(edge).browser, Browser::Edge);
+        let firefox = "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0";
+        assert_eq!(
+            BrowserInfo::from_user_agent(firefox).engine,
+            BrowserEngine::Gecko
+        );
+        let safari = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/14.1.2 Safari/605.1.15";
+        let safari = BrowserInfo::from_user_agent(safari);
+        assert_eq!(safari.browser, Browser::Safari);
+        assert_eq!(safari.version, Some(14));
+        assert!(safari.has_broken_webgl2());
+        let chrome_ios = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_5 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) CriOS/126.0.6478.54 Mobile/15E148 Safari/604.1";
+        assert_eq!(
+            BrowserInfo::from_user_agent(chrome_ios).engine,
+            BrowserEngine::WebKit
+        );
+        assert_eq!(
+            BrowserInfo::from_user_agent("curl/8.0"),
+            BrowserInfo::UNKNOWN
+        );
+        assert_eq!(BrowserInfo::from_user_agent(""), BrowserInfo::UNKNOWN);
+    }
+}
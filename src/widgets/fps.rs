@@ -0,0 +1,154 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+use crate::backend::utils::performance;
+
+/// Number of frames that the [`FpsRecorder`] keeps.
+///
+/// This is a power of two for fast modulus operations.
+const RECORDED_FRAMES: usize = 16;
+
+/// Records the frame timings and calculates the frames per second.
+///
+/// The timestamps of the last frames are kept in a ring buffer, so the rate
+/// is averaged over them. Since the frames are recorded by the application,
+/// this works the same with all backends.
+///
+/// ```rust no_run
+/// use ratzilla::widgets::{Fps, FpsRecorder};
+///
+/// let mut recorder = FpsRecorder::new();
+///
+/// // In the render callback:
+/// // recorder.record();
+/// // frame.render_widget(Fps::new(&recorder), area);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FpsRecorder {
+    /// Current position in the ring buffer.
+    tail: usize,
+    /// Ring buffer of frame timestamps in milliseconds.
+    recorded_frames: [f64; RECORDED_FRAMES],
+}
+
+impl Default for FpsRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FpsRecorder {
+    /// Constructs a new [`FpsRecorder`].
+    pub fn new() -> Self {
+        Self {
+            tail: 0,
+            recorded_frames: [now(); RECORDED_FRAMES],
+        }
+    }
+
+    /// Records a new frame.
+    pub fn record(&mut self) {
+        self.record_at(now());
+    }
+
+    /// Records a new frame at the given time in milliseconds.
+    fn record_at(&mut self, time: f64) {
+        self.recorded_frames[self.tail] = time;
+        self.tail = (self.tail + 1) & (RECORDED_FRAMES - 1);
+    }
+
+    /// Returns the current frames per second.
+    pub fn fps(&self) -> f32 {
+        // The newest timestamp is the one just before the tail
+        let newest = self.recorded_frames[self.tail.wrapping_sub(1) & (RECORDED_FRAMES - 1)];
+        let elapsed = ((newest - self.recorded_frames[self.tail]) / 1000.0).max(0.001);
+        // There are one fewer intervals than frames
+        ((RECORDED_FRAMES - 1) as f64 / elapsed) as f32
+    }
+}
+
+/// Returns the current time in milliseconds.
+fn now() -> f64 {
+    performance().map_or(0.0, |performance| performance.now())
+}
+
+/// A widget that shows the frames per second of a [`FpsRecorder`].
+///
+/// ```rust no_run
+/// use ratzilla::{
+///     ratatui::style::{Color, Style},
+///     widgets::{Fps, FpsRecorder},
+/// };
+///
+/// let recorder = FpsRecorder::new();
+/// let fps = Fps::new(&recorder)
+///     .label_style(Style::default().fg(Color::DarkGray))
+///     .value_style(Style::default().fg(Color::Green));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Fps<'a> {
+    /// The recorder of the frames.
+    recorder: &'a FpsRecorder,
+    /// The style of the label.
+    label_style: Style,
+    /// The style of the value.
+    value_style: Style,
+}
+
+impl<'a> Fps<'a> {
+    /// Constructs a new [`Fps`] widget for the given recorder.
+    pub fn new(recorder: &'a FpsRecorder) -> Self {
+        Self {
+            recorder,
+            label_style: Style::default(),
+            value_style: Style::default(),
+        }
+    }
+
+    /// Sets the style of the `FPS` label.
+    pub fn label_style<S: Into<Style>>(mut self, style: S) -> Self {
+        self.label_style = style.into();
+        self
+    }
+
+    /// Sets the style of the frames per second.
+    pub fn value_style<S: Into<Style>>(mut self, style: S) -> Self {
+        self.value_style = style.into();
+        self
+    }
+}
+
+impl Widget for Fps<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        Line::from(vec![
+            Span::styled("FPS: ", self.label_style),
+            Span::styled(format!("{:.1}", self.recorder.fps()), self.value_style),
+        ])
+        .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fps() {
+        let mut recorder = FpsRecorder {
+            tail: 0,
+            recorded_frames: [0.0; RECORDED_FRAMES],
+        };
+        for frame in 1..=RECORDED_FRAMES * 2 {
+            recorder.record_at(frame as f64 * 20.0);
+        }
+        assert_eq!(recorder.fps(), 50.0);
+    }
+}
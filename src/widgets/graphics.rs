@@ -0,0 +1,463 @@
+use std::cell::{Cell, RefCell};
+
+use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
+use web_sys::{wasm_bindgen::JsCast, CanvasRenderingContext2d, Element, HtmlCanvasElement};
+
+use crate::{
+    backend::utils::{create_canvas_in_element, get_canvas_color},
+    error::Error,
+};
+
+thread_local! {
+    /// Whether a backend with a graphics layer exists.
+    static ENABLED: Cell<bool> = const { Cell::new(false) };
+    /// The shapes of the current frame, in cell coordinates.
+    static SHAPES: RefCell<Vec<Shape>> = const { RefCell::new(Vec::new()) };
+    /// The shapes that were drawn in the last frame.
+    static LAST_SHAPES: RefCell<Vec<Shape>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A shape that can be drawn with the [`Graphics`] widget.
+///
+/// The coordinates are given in the coordinate space of the widget, see
+/// [`Graphics::x_bounds`] and [`Graphics::y_bounds`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// A line from `(x1, y1)` to `(x2, y2)`.
+    Line {
+        /// The x coordinate of the start.
+        x1: f64,
+        /// The y coordinate of the start.
+        y1: f64,
+        /// The x coordinate of the end.
+        x2: f64,
+        /// The y coordinate of the end.
+        y2: f64,
+        /// The color of the line.
+        color: Color,
+    },
+    /// A line through the given points, e.g. the data points of a chart.
+    ///
+    /// The segments are joined with rounded corners.
+    Polyline {
+        /// The points of the line.
+        points: Vec<(f64, f64)>,
+        /// The color of the line.
+        color: Color,
+    },
+    /// A filled rectangle with its bottom left corner at `(x, y)`.
+    Rectangle {
+        /// The x coordinate of the bottom left corner.
+        x: f64,
+        /// The y coordinate of the bottom left corner.
+        y: f64,
+        /// The width of the rectangle.
+        width: f64,
+        /// The height of the rectangle.
+        height: f64,
+        /// The color of the rectangle.
+        color: Color,
+    },
+    /// A filled circle with its center at `(x, y)`.
+    ///
+    /// The radius is given in units of the x axis.
+    Circle {
+        /// The x coordinate of the center.
+        x: f64,
+        /// The y coordinate of the center.
+        y: f64,
+        /// The radius of the circle.
+        radius: f64,
+        /// The color of the circle.
+        color: Color,
+    },
+}
+
+/// Context for drawing the shapes of a [`Graphics`] widget.
+#[derive(Debug)]
+pub struct Painter {
+    /// The area of the widget.
+    area: Rect,
+    /// The bounds of the x axis.
+    x_bounds: [f64; 2],
+    /// The bounds of the y axis.
+    y_bounds: [f64; 2],
+    /// The shapes that are drawn, in cell coordinates.
+    shapes: Vec<Shape>,
+}
+
+impl Painter {
+    /// Draws the given shape.
+    pub fn draw(&mut self, shape: Shape) {
+        let shape = match shape {
+            Shape::Line {
+                x1,
+                y1,
+                x2,
+                y2,
+                color,
+            } => {
+                let (x1, y1) = self.to_cells(x1, y1);
+                let (x2, y2) = self.to_cells(x2, y2);
+                Shape::Line {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    color,
+                }
+            }
+            Shape::Polyline { points, color } => Shape::Polyline {
+                points: points
+                    .into_iter()
+                    .map(|(x, y)| self.to_cells(x, y))
+                    .collect(),
+                color,
+            },
+            Shape::Rectangle {
+                x,
+                y,
+                width,
+                height,
+                color,
+            } => {
+                let (x1, y1) = self.to_cells(x, y + height);
+                let (x2, y2) = self.to_cells(x + width, y);
+                Shape::Rectangle {
+                    x: x1,
+                    y: y1,
+                    width: x2 - x1,
+                    height: y2 - y1,
+                    color,
+                }
+            }
+            Shape::Circle {
+                x,
+                y,
+                radius,
+                color,
+            } => {
+                let (x, y) = self.to_cells(x, y);
+                Shape::Circle {
+                    x,
+                    y,
+                    radius: radius * self.scale().0,
+                    color,
+                }
+            }
+        };
+        self.shapes.push(shape);
+    }
+
+    /// Returns the number of cells per unit on the x and y axes.
+    fn scale(&self) -> (f64, f64) {
+        let [x_min, x_max] = self.x_bounds;
+        let [y_min, y_max] = self.y_bounds;
+        (
+            self.area.width as f64 / (x_max - x_min),
+            self.area.height as f64 / (y_max - y_min),
+        )
+    }
+
+    /// Converts the given point to cell coordinates.
+    ///
+    /// The y axis points up, the same as in Ratatui's canvas widget.
+    fn to_cells(&self, x: f64, y: f64) -> (f64, f64) {
+        let (scale_x, scale_y) = self.scale();
+        (
+            self.area.x as f64 + (x - self.x_bounds[0]) * scale_x,
+            self.area.y as f64 + (self.y_bounds[1] - y) * scale_y,
+        )
+    }
+}
+
+/// A widget for drawing shapes with pixel precision.
+///
+/// Unlike Ratatui's canvas widget, which draws with Braille or block
+/// characters, the shapes are drawn on a graphics layer that is composited
+/// on top of the text grid. This requires a backend with an enabled graphics
+/// layer (see [`CanvasBackendOptions::graphics_layer`] and
+/// [`WebGl2BackendOptions::graphics_layer`]), otherwise nothing is drawn.
+///
+/// ```rust no_run
+/// use ratzilla::{
+///     ratatui::style::Color,
+///     widgets::{Graphics, Shape},
+/// };
+///
+/// let graphics = Graphics::new(|painter| {
+///     painter.draw(Shape::Circle {
+///         x: 50.0,
+///         y: 50.0,
+///         radius: 2.5,
+///         color: Color::White,
+///     });
+/// })
+/// .x_bounds([0.0, 100.0])
+/// .y_bounds([0.0, 100.0]);
+///
+/// // Then you can render it as usual:
+/// // frame.render_widget(graphics, frame.area());
+/// ```
+///
+/// [`CanvasBackendOptions::graphics_layer`]: crate::backend::canvas::CanvasBackendOptions::graphics_layer
+/// [`WebGl2BackendOptions::graphics_layer`]: crate::backend::webgl2::WebGl2BackendOptions::graphics_layer
+pub struct Graphics<F>
+where
+    F: Fn(&mut Painter),
+{
+    /// The bounds of the x axis.
+    x_bounds: [f64; 2],
+    /// The bounds of the y axis.
+    y_bounds: [f64; 2],
+    /// The function that draws the shapes.
+    paint: F,
+}
+
+impl<F> Graphics<F>
+where
+    F: Fn(&mut Painter),
+{
+    /// Constructs a new [`Graphics`] widget with the given paint function.
+    ///
+    /// The bounds of both axes default to `[0.0, 1.0]`.
+    pub fn new(paint: F) -> Self {
+        Self {
+            x_bounds: [0.0, 1.0],
+            y_bounds: [0.0, 1.0],
+            paint,
+        }
+    }
+
+    /// Sets the bounds of the x axis.
+    pub fn x_bounds(mut self, bounds: [f64; 2]) -> Self {
+        self.x_bounds = bounds;
+        self
+    }
+
+    /// Sets the bounds of the y axis.
+    pub fn y_bounds(mut self, bounds: [f64; 2]) -> Self {
+        self.y_bounds = bounds;
+        self
+    }
+}
+
+impl<F> Widget for Graphics<F>
+where
+    F: Fn(&mut Painter),
+{
+    fn render(self, area: Rect, _buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        if !ENABLED.get() || area.is_empty() {
+            return;
+        }
+        let mut painter = Painter {
+            area,
+            x_bounds: self.x_bounds,
+            y_bounds: self.y_bounds,
+            shapes: Vec::new(),
+        };
+        (self.paint)(&mut painter);
+        SHAPES.with_borrow_mut(|shapes| shapes.extend(painter.shapes));
+    }
+}
+
+/// Returns whether a backend with a graphics layer exists.
+pub(crate) fn is_enabled() -> bool {
+    ENABLED.get()
+}
+
+/// Returns whether the shapes of the current frame differ from the shapes
+/// that were drawn in the last frame.
+pub(crate) fn shapes_changed() -> bool {
+    SHAPES.with_borrow(|shapes| LAST_SHAPES.with_borrow(|last_shapes| shapes != last_shapes))
+}
+
+/// Discards the shapes of the current frame, e.g. when the frame is skipped.
+pub(crate) fn discard_shapes() {
+    SHAPES.with_borrow_mut(Vec::clear);
+}
+
+/// A canvas on top of the text grid for drawing the [`Graphics`] widgets.
+#[derive(Debug)]
+pub(crate) struct GraphicsLayer {
+    /// Canvas element.
+    canvas: HtmlCanvasElement,
+    /// Rendering context.
+    context: CanvasRenderingContext2d,
+}
+
+impl GraphicsLayer {
+    /// Constructs a new [`GraphicsLayer`] in the given parent element.
+    pub(crate) fn new(parent: &Element) -> Result<Self, Error> {
+        let canvas = create_canvas_in_element(parent, 0, 0)?;
+        let style = canvas.style();
+        style.set_property("position", "absolute")?;
+        style.set_property("pointer-events", "none")?;
+        let context = canvas
+            .get_context("2d")?
+            .ok_or(Error::UnableToRetrieveCanvasContext)?
+            .dyn_into::<CanvasRenderingContext2d>()
+            .expect("Unable to cast canvas context");
+        ENABLED.set(true);
+        Ok(Self { canvas, context })
+    }
+
+    /// Draws the shapes of the current frame on top of the given canvas.
+    ///
+    /// The `cell_size` is the size of a single cell of the text grid in pixels.
+    pub(crate) fn draw(
+        &self,
+        grid_canvas: &HtmlCanvasElement,
+        cell_size: (f64, f64),
+    ) -> Result<(), Error> {
+        // Follow the position and size of the text grid
+        let style = self.canvas.style();
+        // The margin of the grid canvas is drawn as a border
+        let left = grid_canvas.offset_left() + grid_canvas.client_left();
+        let top = grid_canvas.offset_top() + grid_canvas.client_top();
+        style.set_property("left", &format!("{left}px"))?;
+        style.set_property("top", &format!("{top}px"))?;
+        style.set_property("width", &format!("{}px", grid_canvas.client_width()))?;
+        style.set_property("height", &format!("{}px", grid_canvas.client_height()))?;
+        if self.canvas.width() != grid_canvas.width() {
+            self.canvas.set_width(grid_canvas.width());
+        }
+        if self.canvas.height() != grid_canvas.height() {
+            self.canvas.set_height(grid_canvas.height());
+        }
+
+        let context = &self.context;
+        context.clear_rect(
+            0.0,
+            0.0,
+            self.canvas.width() as f64,
+            self.canvas.height() as f64,
+        );
+        let (cell_width, cell_height) = cell_size;
+        let shapes = SHAPES.take();
+        for shape in &shapes {
+            match *shape {
+                Shape::Line {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    color,
+                } => {
+                    context.set_stroke_style_str(&get_canvas_color(color, Color::White));
+                    context.begin_path();
+                    context.move_to(x1 * cell_width, y1 * cell_height);
+                    context.line_to(x2 * cell_width, y2 * cell_height);
+                    context.stroke();
+                }
+                Shape::Polyline { ref points, color } => {
+                    let mut points = points.iter();
+                    let Some(&(x, y)) = points.next() else {
+                        continue;
+                    };
+                    context.save();
+                    context.set_stroke_style_str(&get_canvas_color(color, Color::White));
+                    context.set_line_join("round");
+                    context.set_line_cap("round");
+                    context.begin_path();
+                    context.move_to(x * cell_width, y * cell_height);
+                    for &(x, y) in points {
+                        context.line_to(x * cell_width, y * cell_height);
+                    }
+                    context.stroke();
+                    context.restore();
+                }
+                Shape::Rectangle {
+                    x,
+                    y,
+                    width,
+                    height,
+                    color,
+                } => {
+                    context.set_fill_style_str(&get_canvas_color(color, Color::White));
+                    context.fill_rect(
+                        x * cell_width,
+                        y * cell_height,
+                        width * cell_width,
+                        height * cell_height,
+                    );
+                }
+                Shape::Circle {
+                    x,
+                    y,
+                    radius,
+                    color,
+                } => {
+                    context.set_fill_style_str(&get_canvas_color(color, Color::White));
+                    context.begin_path();
+                    context.arc(
+                        x * cell_width,
+                        y * cell_height,
+                        radius * cell_width,
+                        0.0,
+                        std::f64::consts::TAU,
+                    )?;
+                    context.fill();
+                }
+            }
+        }
+        LAST_SHAPES.set(shapes);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_painter_to_cells() {
+        let mut painter = Painter {
+            area: Rect::new(2, 1, 10, 5),
+            x_bounds: [0.0, 100.0],
+            y_bounds: [0.0, 50.0],
+            shapes: Vec::new(),
+        };
+        painter.draw(Shape::Rectangle {
+            x: 50.0,
+            y: 0.0,
+            width: 10.0,
+            height: 10.0,
+            color: Color::Red,
+        });
+        assert_eq!(
+            painter.shapes,
+            vec![Shape::Rectangle {
+                x: 7.0,
+                y: 5.0,
+                width: 1.0,
+                height: 1.0,
+                color: Color::Red,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_painter_polyline() {
+        let mut painter = Painter {
+            area: Rect::new(0, 0, 10, 10),
+            x_bounds: [0.0, 1.0],
+            y_bounds: [0.0, 1.0],
+            shapes: Vec::new(),
+        };
+        painter.draw(Shape::Polyline {
+            points: vec![(0.0, 0.0), (0.5, 1.0), (1.0, 0.5)],
+            color: Color::Green,
+        });
+        assert_eq!(
+            painter.shapes,
+            vec![Shape::Polyline {
+                points: vec![(0.0, 10.0), (5.0, 0.0), (10.0, 5.0)],
+                color: Color::Green,
+            }]
+        );
+    }
+}
@@ -8,6 +8,10 @@ pub(crate) const HYPERLINK_MODIFIER: Modifier = Modifier::SLOW_BLINK;
 
 /// A widget that can be used to render hyperlinks.
 ///
+/// Consider enabling hyperlinks on the backend instead (e.g. via
+/// [`DomBackendOptions::enable_hyperlinks`]), which makes any URL in the
+/// rendered text clickable without a dedicated widget.
+///
 /// ```rust no_run
 /// use ratzilla::widgets::Hyperlink;
 ///
@@ -16,6 +20,8 @@ pub(crate) const HYPERLINK_MODIFIER: Modifier = Modifier::SLOW_BLINK;
 /// // Then you can render it as usual:
 /// // frame.render_widget(link, frame.area());
 /// ```
+///
+/// [`DomBackendOptions::enable_hyperlinks`]: crate::backend::dom::DomBackendOptions::enable_hyperlinks
 pub struct Hyperlink<'a> {
     /// Line.
     line: Span<'a>,
@@ -0,0 +1,138 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    symbols::Marker,
+    widgets::{Axis, Block, Chart, Dataset, GraphType, Widget},
+};
+
+use super::graphics::{self, Graphics, Shape};
+
+/// A line chart that is drawn with smooth lines.
+///
+/// Ratatui's [`Chart`] widget is quantized to the cells of the grid (even with
+/// Braille characters). If the backend has a graphics layer (see
+/// [`CanvasBackendOptions::graphics_layer`] and
+/// [`WebGl2BackendOptions::graphics_layer`]), this widget draws each series as
+/// a polyline with pixel precision instead, aligned to the area of the widget.
+/// Otherwise, it falls back to a [`Chart`] with Braille markers, so that it
+/// renders on all backends.
+///
+/// ```rust no_run
+/// use ratzilla::{
+///     ratatui::{style::Color, widgets::Block},
+///     widgets::LineChart,
+/// };
+///
+/// let points = (0..100)
+///     .map(|x| (x as f64, (x as f64 / 10.0).sin()))
+///     .collect::<Vec<_>>();
+/// let chart = LineChart::new()
+///     .series(&points, Color::Cyan)
+///     .x_bounds([0.0, 100.0])
+///     .y_bounds([-1.0, 1.0])
+///     .block(Block::bordered().title("sin(x)"));
+///
+/// // Then you can render it as usual:
+/// // frame.render_widget(chart, frame.area());
+/// ```
+///
+/// [`CanvasBackendOptions::graphics_layer`]: crate::backend::canvas::CanvasBackendOptions::graphics_layer
+/// [`WebGl2BackendOptions::graphics_layer`]: crate::backend::webgl2::WebGl2BackendOptions::graphics_layer
+#[derive(Debug, Clone, Default)]
+pub struct LineChart<'a> {
+    /// The data points and the color of each series.
+    series: Vec<(&'a [(f64, f64)], Color)>,
+    /// The bounds of the x axis.
+    x_bounds: [f64; 2],
+    /// The bounds of the y axis.
+    y_bounds: [f64; 2],
+    /// The block to wrap the chart in.
+    block: Option<Block<'a>>,
+}
+
+impl<'a> LineChart<'a> {
+    /// Constructs a new [`LineChart`] without any series.
+    ///
+    /// The bounds of both axes default to `[0.0, 1.0]`.
+    pub fn new() -> Self {
+        Self {
+            x_bounds: [0.0, 1.0],
+            y_bounds: [0.0, 1.0],
+            ..Default::default()
+        }
+    }
+
+    /// Adds a series with the given data points and color.
+    pub fn series(mut self, points: &'a [(f64, f64)], color: Color) -> Self {
+        self.series.push((points, color));
+        self
+    }
+
+    /// Sets the bounds of the x axis.
+    pub fn x_bounds(mut self, bounds: [f64; 2]) -> Self {
+        self.x_bounds = bounds;
+        self
+    }
+
+    /// Sets the bounds of the y axis.
+    pub fn y_bounds(mut self, bounds: [f64; 2]) -> Self {
+        self.y_bounds = bounds;
+        self
+    }
+
+    /// Wraps the chart in the given block.
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+}
+
+impl Widget for LineChart<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        if !graphics::is_enabled() {
+            let datasets = self
+                .series
+                .iter()
+                .map(|(points, color)| {
+                    Dataset::default()
+                        .data(points)
+                        .graph_type(GraphType::Line)
+                        .marker(Marker::Braille)
+                        .style(Style::default().fg(*color))
+                })
+                .collect();
+            let mut chart = Chart::new(datasets)
+                .x_axis(Axis::default().bounds(self.x_bounds))
+                .y_axis(Axis::default().bounds(self.y_bounds));
+            if let Some(block) = self.block {
+                chart = chart.block(block);
+            }
+            chart.render(area, buf);
+            return;
+        }
+
+        let inner = match self.block {
+            Some(block) => {
+                let inner = block.inner(area);
+                block.render(area, buf);
+                inner
+            }
+            None => area,
+        };
+        Graphics::new(|painter| {
+            for (points, color) in &self.series {
+                painter.draw(Shape::Polyline {
+                    points: points.to_vec(),
+                    color: *color,
+                });
+            }
+        })
+        .x_bounds(self.x_bounds)
+        .y_bounds(self.y_bounds)
+        .render(inner, buf);
+    }
+}
@@ -0,0 +1,176 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    style::Color,
+    widgets::Widget,
+};
+
+/// A widget that renders a downscaled overview of large content.
+///
+/// Similar to the minimap of code editors, the content (e.g. a document or a
+/// scrollback that is rendered into an off-screen [`Buffer`]) is shrunk into
+/// the area of the widget. Each cell of the minimap shows two blocks of the
+/// content on top of each other (using half blocks), colored with the color of
+/// the first visible character in the block. The visible part of the content
+/// (the viewport) is highlighted.
+///
+/// Since the overview consists of colored cells, it looks the same on all
+/// backends. Use [`Minimap::line_at`] for jumping to the clicked position:
+///
+/// ```rust no_run
+/// use ratzilla::{
+///     ratatui::{buffer::Buffer, layout::{Position, Rect}},
+///     widgets::Minimap,
+/// };
+///
+/// let content = Buffer::empty(Rect::new(0, 0, 80, 1000));
+/// let minimap = Minimap::new(&content).viewport(0, 40);
+///
+/// // Then you can render it as usual:
+/// // frame.render_widget(&minimap, minimap_area);
+///
+/// // And scroll the main view on click:
+/// // if let Some(line) = minimap.line_at(minimap_area, Position::new(event.x, event.y)) {
+/// //     scroll = line;
+/// // }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Minimap<'a> {
+    /// The content to show.
+    content: &'a Buffer,
+    /// The first line and the number of lines of the viewport.
+    viewport: Option<(u16, u16)>,
+    /// Color of the viewport highlight.
+    viewport_color: Color,
+}
+
+impl<'a> Minimap<'a> {
+    /// Constructs a new [`Minimap`] of the given content.
+    pub fn new(content: &'a Buffer) -> Self {
+        Self {
+            content,
+            viewport: None,
+            viewport_color: Color::DarkGray,
+        }
+    }
+
+    /// Sets the visible part of the content, given by its first line and the
+    /// number of lines.
+    pub fn viewport(mut self, offset: u16, height: u16) -> Self {
+        self.viewport = Some((offset, height));
+        self
+    }
+
+    /// Sets the color of the viewport highlight. Defaults to dark gray.
+    pub fn viewport_color(mut self, color: Color) -> Self {
+        self.viewport_color = color;
+        self
+    }
+
+    /// Returns the line of the content that is shown at the given position of
+    /// the minimap, which is rendered in the given area.
+    ///
+    /// Returns `None` if the position is outside of the area.
+    pub fn line_at(&self, area: Rect, position: Position) -> Option<u16> {
+        if !area.contains(position) {
+            return None;
+        }
+        let (_, scale_y) = self.scale(area);
+        let line = (position.y - area.y) as u32 * 2 * scale_y as u32;
+        Some(line.min(self.content.area.height.saturating_sub(1) as u32) as u16)
+    }
+
+    /// Returns the number of columns and lines of the content per half block.
+    fn scale(&self, area: Rect) -> (u16, u16) {
+        let content = self.content.area;
+        (
+            content.width.div_ceil(area.width.max(1)).max(1),
+            content.height.div_ceil(area.height.max(1) * 2).max(1),
+        )
+    }
+
+    /// Returns the color of the block of content starting at the given column
+    /// and line.
+    fn block_color(&self, column: u16, line: u16, (width, height): (u16, u16)) -> Option<Color> {
+        let content = self.content.area;
+        for y in line..(line + height).min(content.height) {
+            for x in column..(column + width).min(content.width) {
+                let cell = &self.content[(content.x + x, content.y + y)];
+                if !cell.symbol().trim().is_empty() {
+                    return Some(match cell.fg {
+                        Color::Reset => Color::Gray,
+                        color => color,
+                    });
+                }
+                if cell.bg != Color::Reset {
+                    return Some(cell.bg);
+                }
+            }
+        }
+        let in_viewport = self
+            .viewport
+            .is_some_and(|(offset, lines)| line + height > offset && line < offset + lines);
+        in_viewport.then_some(self.viewport_color)
+    }
+}
+
+impl Widget for &Minimap<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let area = area.intersection(buf.area);
+        let scale = self.scale(area);
+        for position in area.positions() {
+            let column = (position.x - area.x) * scale.0;
+            let line = (position.y - area.y) * 2 * scale.1;
+            let top = self.block_color(column, line, scale);
+            let bottom = self.block_color(column, line + scale.1, scale);
+            let cell = &mut buf[position];
+            cell.set_symbol("▀")
+                .set_fg(top.unwrap_or(Color::Reset))
+                .set_bg(bottom.unwrap_or(Color::Reset));
+            if top.is_none() && bottom.is_none() {
+                cell.set_symbol(" ");
+            }
+        }
+    }
+}
+
+impl Widget for Minimap<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        (&self).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Style;
+
+    use super::*;
+
+    #[test]
+    fn test_minimap() {
+        let mut content = Buffer::empty(Rect::new(0, 0, 4, 8));
+        content.set_string(0, 0, "ab", Style::new().fg(Color::Red));
+        content.set_string(2, 7, "c", Style::new().fg(Color::Blue));
+        let minimap = Minimap::new(&content).viewport(0, 2);
+
+        let area = Rect::new(0, 0, 2, 2);
+        let mut buf = Buffer::empty(area);
+        (&minimap).render(area, &mut buf);
+        // Each half block covers 2 columns and 2 lines
+        assert_eq!(buf[(0, 0)].symbol(), "▀");
+        assert_eq!(buf[(0, 0)].fg, Color::Red);
+        assert_eq!(buf[(0, 0)].bg, Color::Reset);
+        assert_eq!(buf[(1, 0)].fg, Color::DarkGray);
+        assert_eq!(buf[(0, 1)].symbol(), " ");
+        assert_eq!(buf[(1, 1)].bg, Color::Blue);
+
+        assert_eq!(minimap.line_at(area, Position::new(1, 1)), Some(4));
+        assert_eq!(minimap.line_at(area, Position::new(2, 1)), None);
+    }
+}
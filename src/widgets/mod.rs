@@ -2,6 +2,18 @@
 //!
 //! **Ratzilla** provides web-only widgets that you can use while building TUIs.
 
+pub(crate) mod fps;
+pub(crate) mod graphics;
 pub(crate) mod hyperlink;
+pub(crate) mod line_chart;
+pub(crate) mod minimap;
+pub(crate) mod scrim;
+pub(crate) mod tooltip;
 
+pub use fps::{Fps, FpsRecorder};
+pub use graphics::{Graphics, Painter, Shape};
 pub use hyperlink::Hyperlink;
+pub use line_chart::LineChart;
+pub use minimap::Minimap;
+pub use scrim::Scrim;
+pub use tooltip::FollowMouseTooltip;
@@ -0,0 +1,69 @@
+use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
+
+use crate::backend::color::blend;
+
+/// A widget that dims the content below it, e.g. behind a modal dialog.
+///
+/// The colors of the covered cells are blended with the scrim color, so the
+/// content stays visible as if it was behind a translucent layer. Since the
+/// blending is done in the buffer, the scrim looks the same on all backends.
+///
+/// ```rust no_run
+/// use ratzilla::widgets::Scrim;
+///
+/// let scrim = Scrim::new().opacity(0.6);
+///
+/// // Render it before the dialog:
+/// // frame.render_widget(scrim, frame.area());
+/// // frame.render_widget(dialog, dialog_area);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scrim {
+    /// The color of the scrim.
+    color: Color,
+    /// The opacity of the scrim.
+    opacity: f32,
+}
+
+impl Default for Scrim {
+    fn default() -> Self {
+        Self {
+            color: Color::Black,
+            opacity: 0.5,
+        }
+    }
+}
+
+impl Scrim {
+    /// Constructs a new black [`Scrim`] with an opacity of `0.5`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the color of the scrim.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets the opacity of the scrim.
+    ///
+    /// The value is clamped between `0.0` (invisible) and `1.0` (opaque).
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+}
+
+impl Widget for Scrim {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        for position in area.intersection(buf.area).positions() {
+            let cell = &mut buf[position];
+            cell.fg = blend(cell.fg, self.color, self.opacity, 0xffffff);
+            cell.bg = blend(cell.bg, self.color, self.opacity, 0x000000);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::{cell::Cell, rc::Rc};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect, Size},
+    widgets::{Clear, Widget},
+};
+
+use crate::event::{CoordinateRounding, MouseEvent};
+
+/// A tooltip that follows the mouse, e.g. for hover help.
+///
+/// The tooltip tracks the position of the latest mouse event and renders the
+/// given content next to it. Near the edges of the screen, the tooltip is
+/// flipped to the other side of the mouse so that it stays visible.
+///
+/// The tooltip is a cheaply cloneable handle, so the mouse callback and the
+/// render callback can share it:
+///
+/// ```rust no_run
+/// use ratzilla::{
+///     ratatui::{layout::Size, widgets::{Block, Paragraph}},
+///     widgets::FollowMouseTooltip,
+/// };
+///
+/// let tooltip = FollowMouseTooltip::new((10.0, 19.0));
+///
+/// // Track the mouse in the mouse callback:
+/// // terminal.on_mouse_event({
+/// //     let tooltip = tooltip.clone();
+/// //     move |event| tooltip.track(&event)
+/// // })?;
+///
+/// // And render the content in the render callback:
+/// // let help = Paragraph::new("42 requests").block(Block::bordered());
+/// // tooltip.render(help, Size::new(16, 3), frame.buffer_mut());
+/// ```
+#[derive(Debug, Clone)]
+pub struct FollowMouseTooltip {
+    /// The grid position of the mouse, if the tooltip is shown.
+    mouse: Rc<Cell<Option<Position>>>,
+    /// The size of a cell in pixels.
+    cell_size: (f64, f64),
+    /// The distance between the mouse and the tooltip in cells.
+    offset: (u16, u16),
+}
+
+impl FollowMouseTooltip {
+    /// Constructs a new [`FollowMouseTooltip`] for a backend with the given
+    /// cell size in pixels.
+    ///
+    /// The tooltip is hidden until the first mouse event is tracked.
+    pub fn new(cell_size: (f64, f64)) -> Self {
+        Self {
+            mouse: Rc::new(Cell::new(None)),
+            cell_size,
+            offset: (1, 1),
+        }
+    }
+
+    /// Sets the distance between the mouse and the tooltip in cells.
+    ///
+    /// Defaults to one cell in each direction.
+    pub fn offset(mut self, x: u16, y: u16) -> Self {
+        self.offset = (x, y);
+        self
+    }
+
+    /// Moves the tooltip to the position of the given mouse event.
+    pub fn track(&self, event: &MouseEvent) {
+        let (x, y) = event.grid_coords(self.cell_size, CoordinateRounding::Floor);
+        self.mouse.set(Some(Position::new(x, y)));
+    }
+
+    /// Hides the tooltip until the next mouse event is tracked.
+    pub fn hide(&self) {
+        self.mouse.set(None);
+    }
+
+    /// Returns the grid position of the mouse, if the tooltip is shown.
+    ///
+    /// This can be used for finding the hovered content.
+    pub fn mouse_position(&self) -> Option<Position> {
+        self.mouse.get()
+    }
+
+    /// Returns the area of a tooltip with the given size within the given
+    /// bounds, if the tooltip is shown.
+    pub fn area(&self, size: Size, bounds: Rect) -> Option<Rect> {
+        let mouse = self.mouse.get()?;
+        Some(tooltip_area(mouse, size, self.offset, bounds))
+    }
+
+    /// Renders the given widget as the tooltip with the given size.
+    ///
+    /// The content below the tooltip is cleared first. Nothing is rendered if
+    /// the tooltip is hidden.
+    pub fn render<W: Widget>(&self, widget: W, size: Size, buf: &mut Buffer) {
+        if let Some(area) = self.area(size, buf.area) {
+            Clear.render(area, buf);
+            widget.render(area, buf);
+        }
+    }
+}
+
+/// Returns the area of a tooltip with the given size next to the mouse.
+///
+/// The tooltip is placed below and to the right of the mouse, unless it would
+/// leave the bounds on that side; then it is flipped to the other side of the
+/// mouse. If it doesn't fit on either side, it is moved into the bounds.
+fn tooltip_area(mouse: Position, size: Size, offset: (u16, u16), bounds: Rect) -> Rect {
+    let width = size.width.min(bounds.width);
+    let height = size.height.min(bounds.height);
+    let place = |mouse: u16, offset: u16, length: u16, start: u16, end: u16| {
+        let after = mouse.saturating_add(offset);
+        let before = mouse.saturating_sub(offset).checked_sub(length);
+        match before {
+            Some(before) if after.saturating_add(length) > end && before >= start => before,
+            _ => after.min(end.saturating_sub(length)).max(start),
+        }
+    };
+    Rect::new(
+        place(mouse.x, offset.0, width, bounds.left(), bounds.right()),
+        place(mouse.y, offset.1, height, bounds.top(), bounds.bottom()),
+        width,
+        height,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tooltip_area() {
+        let bounds = Rect::new(0, 0, 80, 24);
+        let size = Size::new(20, 5);
+        let area = |x, y| tooltip_area(Position::new(x, y), size, (1, 1), bounds);
+        assert_eq!(area(10, 5), Rect::new(11, 6, 20, 5));
+        // Flipped near the right and bottom edges
+        assert_eq!(area(70, 20), Rect::new(49, 14, 20, 5));
+        // Moved into the bounds if it doesn't fit on either side
+        assert_eq!(
+            tooltip_area(Position::new(5, 2), Size::new(100, 5), (1, 1), bounds),
+            Rect::new(0, 3, 80, 5)
+        );
+    }
+}